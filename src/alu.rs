@@ -0,0 +1,167 @@
+use crate::register::{Flag, StatusRegister};
+
+/*
+    Arithmetic and logic operations of the 6502.
+    Every function takes the status register and returns the result byte (if any),
+    so the flag behaviour can be tested independently of the instruction decoding.
+*/
+
+/* A + M + C, BCD addition if the D flag is set */
+pub fn adc(p: &mut StatusRegister, a: u8, value: u8, cmos: bool) -> u8 {
+    if p.get(Flag::D) {
+        return adc_decimal(p, a, value, cmos);
+    }
+    let sum = a as u16 + value as u16 + p.get(Flag::C) as u16;
+    let result = sum as u8;
+    p.update(Flag::C, sum > 0xFF);
+    /* overflow if both operands have the same sign and the result has a different one */
+    p.update(Flag::V, (a ^ result) & (value ^ result) & 0x80 != 0);
+    p.set_nz(result);
+    result
+}
+
+/*
+    BCD addition as done by the chip, see http://www.6502.org/tutorials/decimal_mode.html
+    On the NMOS 6502 Z reflects the binary sum and N, V the sum before the high nibble is adjusted.
+*/
+fn adc_decimal(p: &mut StatusRegister, a: u8, value: u8, cmos: bool) -> u8 {
+    let carry = p.get(Flag::C) as u16;
+    let (a_wide, b_wide) = (a as u16, value as u16);
+
+    let mut low = (a_wide & 0x0F) + (b_wide & 0x0F) + carry;
+    if low >= 0x0A {
+        low = ((low + 0x06) & 0x0F) + 0x10;
+    }
+    let mut result = (a_wide & 0xF0) + (b_wide & 0xF0) + low;
+    let intermediate = result as u8;
+    if result >= 0xA0 {
+        result += 0x60;
+    }
+
+    p.update(
+        Flag::V,
+        (a ^ intermediate) & (value ^ intermediate) & 0x80 != 0,
+    );
+    p.update(Flag::C, result >= 0x100);
+    if cmos {
+        p.set_nz(result as u8);
+    } else {
+        p.update(Flag::Z, (a_wide + b_wide + carry) as u8 == 0);
+        p.update(Flag::N, intermediate & 0x80 != 0);
+    }
+    result as u8
+}
+
+/* A - M - (1 - C), BCD subtraction if the D flag is set */
+pub fn sbc(p: &mut StatusRegister, a: u8, value: u8, cmos: bool) -> u8 {
+    let borrow = !p.get(Flag::C) as i16;
+    let difference = a as i16 - value as i16 - borrow;
+    let result = difference as u8;
+    p.update(Flag::C, difference >= 0);
+    /* overflow if the operands have different signs and the result has the sign of M */
+    p.update(Flag::V, (a ^ value) & (a ^ result) & 0x80 != 0);
+    p.set_nz(result);
+    if p.get(Flag::D) {
+        return sbc_decimal(p, a, value, borrow, cmos);
+    }
+    result
+}
+
+/*
+    BCD subtraction, the flags are the ones of the binary subtraction on the NMOS 6502.
+    The 65C02 adjusts the result differently and sets N and Z from it.
+*/
+fn sbc_decimal(p: &mut StatusRegister, a: u8, value: u8, borrow: i16, cmos: bool) -> u8 {
+    let (a, b) = (a as i16, value as i16);
+    let low = (a & 0x0F) - (b & 0x0F) - borrow;
+    let result = if cmos {
+        let mut result = a - b - borrow;
+        if result < 0 {
+            result -= 0x60;
+        }
+        if low < 0 {
+            result -= 0x06;
+        }
+        result as u8
+    } else {
+        let low = if low < 0 {
+            ((low - 0x06) & 0x0F) - 0x10
+        } else {
+            low
+        };
+        let mut result = (a & 0xF0) - (b & 0xF0) + low;
+        if result < 0 {
+            result -= 0x60;
+        }
+        result as u8
+    };
+    if cmos {
+        p.set_nz(result);
+    }
+    result
+}
+
+/* CMP, CPX and CPY: flags of reg - M without storing the result */
+pub fn compare(p: &mut StatusRegister, reg: u8, value: u8) {
+    p.update(Flag::C, reg >= value);
+    p.set_nz(reg.wrapping_sub(value));
+}
+
+/* Z from A & M, N and V are bit 7 and 6 of M */
+pub fn bit(p: &mut StatusRegister, a: u8, value: u8) {
+    p.update(Flag::Z, a & value == 0);
+    p.update(Flag::N, value & 0x80 != 0);
+    p.update(Flag::V, value & 0x40 != 0);
+}
+
+pub fn asl(p: &mut StatusRegister, value: u8) -> u8 {
+    let result = value << 1;
+    p.update(Flag::C, value & 0x80 != 0);
+    p.set_nz(result);
+    result
+}
+
+pub fn lsr(p: &mut StatusRegister, value: u8) -> u8 {
+    let result = value >> 1;
+    p.update(Flag::C, value & 0x01 != 0);
+    p.set_nz(result);
+    result
+}
+
+pub fn rol(p: &mut StatusRegister, value: u8) -> u8 {
+    let result = (value << 1) | p.get(Flag::C) as u8;
+    p.update(Flag::C, value & 0x80 != 0);
+    p.set_nz(result);
+    result
+}
+
+pub fn ror(p: &mut StatusRegister, value: u8) -> u8 {
+    let result = (value >> 1) | ((p.get(Flag::C) as u8) << 7);
+    p.update(Flag::C, value & 0x01 != 0);
+    p.set_nz(result);
+    result
+}
+
+/*
+    ARR (undocumented): A & M rotated right. C and V come from bit 6 and 5 of the result,
+    in decimal mode the NMOS chip additionally fixes up both nibbles like a BCD addition.
+*/
+pub fn arr(p: &mut StatusRegister, a: u8, value: u8) -> u8 {
+    let and = a & value;
+    let mut result = (and >> 1) | ((p.get(Flag::C) as u8) << 7);
+    p.set_nz(result);
+    p.update(Flag::V, (and ^ result) & 0x40 != 0);
+    if !p.get(Flag::D) {
+        p.update(Flag::C, result & 0x40 != 0);
+        return result;
+    }
+    if (and & 0x0F) + (and & 0x01) > 0x05 {
+        result = (result & 0xF0) | (result.wrapping_add(0x06) & 0x0F);
+    }
+    let high_fixup = (and & 0xF0) as u16 + (and & 0x10) as u16 > 0x50;
+    if high_fixup {
+        result = result.wrapping_add(0x60);
+    }
+    p.update(Flag::C, high_fixup);
+    result
+}
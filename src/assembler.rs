@@ -0,0 +1,965 @@
+use std::collections::BTreeMap;
+
+use crate::cpu::{AddressingMode, Variant};
+use crate::dispatch::{self, Operation};
+use crate::error::{AsmError, AsmErrorKind, LoadError};
+use crate::loader::{self, Image};
+use crate::memory::{Memory, PROGRAM_ROM_S};
+
+/*
+    Two-pass assembler for the usual 6502 syntax:
+
+        value = $20         ; symbols
+                .org $8000  ; also *= $8000, the default origin is PROGRAM_ROM_S
+        start:  lda #<table ; < and > select the low and high byte
+                sta (value),y
+                bne start
+        table:  .byte 1, 2, "text"
+                .word start, table + 2
+
+    The first pass fixes the size of every line, zero page is only used if the operand is known by then.
+    The second pass evaluates all operands and emits the bytes. Opcodes come from the dispatch table.
+*/
+
+/* the result of an assembly: the bytes and every label and constant */
+#[derive(Clone, Debug, PartialEq)]
+pub struct Program {
+    pub image: Image,
+    pub symbols: BTreeMap<String, u16>,
+    /* the line and column of the first byte of every segment of the image */
+    origins: Vec<(usize, usize)>,
+}
+
+impl Program {
+    /* copies the program into the memory, a segment that cannot be placed is reported at its source line */
+    pub fn load(&self, memory: &mut Memory) -> Result<(), AsmError> {
+        loader::load(memory, &self.image).map_err(|error| {
+            let addr = match error {
+                LoadError::OutOfRange { addr, .. } => Some(addr),
+                LoadError::Overlap { addr } => Some(addr as u32),
+                _ => None,
+            };
+            /* a segment added to the image after assembling has no line, the first line stands in */
+            let (line, column) = addr
+                .and_then(|addr| {
+                    self.image
+                        .segments
+                        .iter()
+                        .position(|segment| segment.addr == addr)
+                })
+                .and_then(|index| self.origins.get(index).copied())
+                .unwrap_or((1, 1));
+            AsmError {
+                line,
+                column,
+                kind: AsmErrorKind::Load(error),
+            }
+        })
+    }
+}
+
+pub fn assemble(source: &str, variant: Variant) -> Result<Program, AsmError> {
+    let statements = source
+        .lines()
+        .enumerate()
+        .map(|(index, text)| Parser::new(text, index + 1).statement())
+        .collect::<Result<Vec<Statement>, AsmError>>()?;
+    Assembler {
+        table: dispatch::table(variant),
+        symbols: BTreeMap::new(),
+    }
+    .run(&statements)
+}
+
+/* assembles the source and copies the program into the memory */
+pub fn assemble_into(
+    memory: &mut Memory,
+    source: &str,
+    variant: Variant,
+) -> Result<Program, AsmError> {
+    let program = assemble(source, variant)?;
+    program.load(memory)?;
+    Ok(program)
+}
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+enum BinaryOp {
+    Or,
+    Xor,
+    And,
+    Shl,
+    Shr,
+    Add,
+    Sub,
+    Mul,
+    Div,
+    Mod,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+enum Expr {
+    Number(i64),
+    Symbol {
+        name: String,
+        column: usize,
+    },
+    /* the address of the current line, written as * */
+    Pc,
+    Negate(Box<Expr>),
+    Not(Box<Expr>),
+    Low(Box<Expr>),
+    High(Box<Expr>),
+    Binary {
+        op: BinaryOp,
+        column: usize,
+        left: Box<Expr>,
+        right: Box<Expr>,
+    },
+}
+
+#[derive(Clone, Debug, PartialEq)]
+enum Index {
+    X,
+    Y,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+enum Operand {
+    None,
+    Accumulator,
+    Immediate(Expr),
+    /* zero page, absolute or a branch target */
+    Direct(Expr, Option<Index>),
+    Indirect(Expr),
+    IndirectX(Expr),
+    IndirectY(Expr),
+    /* BBR and BBS: zero page address and branch target */
+    BitBranch(Expr, Expr),
+}
+
+#[derive(Clone, Debug, PartialEq)]
+enum Data {
+    Value(Expr, usize),
+    Text(Vec<u8>, usize),
+}
+
+#[derive(Clone, Debug, PartialEq)]
+enum Body {
+    Empty,
+    Assign {
+        name: String,
+        column: usize,
+        value: Expr,
+    },
+    Org {
+        column: usize,
+        value: Expr,
+    },
+    Bytes(Vec<Data>),
+    Words(Vec<(Expr, usize)>),
+    Instruction {
+        mnemonic: String,
+        column: usize,
+        operand: Operand,
+        operand_column: usize,
+    },
+}
+
+#[derive(Clone, Debug, PartialEq)]
+struct Statement {
+    line: usize,
+    label: Option<(String, usize)>,
+    body: Body,
+}
+
+/* parses one line, columns are counted in characters starting at 1 */
+struct Parser {
+    chars: Vec<char>,
+    pos: usize,
+    line: usize,
+}
+
+impl Parser {
+    fn new(text: &str, line: usize) -> Parser {
+        Parser {
+            chars: text.chars().collect(),
+            pos: 0,
+            line,
+        }
+    }
+    fn error(&self, column: usize, kind: AsmErrorKind) -> AsmError {
+        AsmError {
+            line: self.line,
+            column,
+            kind,
+        }
+    }
+    fn column(&self) -> usize {
+        self.pos + 1
+    }
+    /* the next non blank character, a comment counts as the end of the line */
+    fn peek(&mut self) -> Option<char> {
+        while self.pos < self.chars.len() && self.chars[self.pos].is_whitespace() {
+            self.pos += 1;
+        }
+        match self.chars.get(self.pos) {
+            Some(';') | None => None,
+            Some(c) => Some(*c),
+        }
+    }
+    fn peek_at(&self, offset: usize) -> Option<char> {
+        self.chars.get(self.pos + offset).copied()
+    }
+    fn eat(&mut self, c: char) -> bool {
+        if self.peek() == Some(c) {
+            self.pos += 1;
+            return true;
+        }
+        false
+    }
+    fn expect(&mut self, c: char, what: &'static str) -> Result<(), AsmError> {
+        if self.eat(c) {
+            Ok(())
+        } else {
+            Err(self.error(self.column(), AsmErrorKind::Syntax(what)))
+        }
+    }
+    fn identifier(&mut self) -> Option<(String, usize)> {
+        let column = self.peek().map(|_| self.column())?;
+        let start = self.pos;
+        while self.pos < self.chars.len()
+            && (self.chars[self.pos].is_ascii_alphanumeric() || self.chars[self.pos] == '_')
+            && !(self.pos == start && self.chars[self.pos].is_ascii_digit())
+        {
+            self.pos += 1;
+        }
+        if self.pos == start {
+            return None;
+        }
+        Some((self.chars[start..self.pos].iter().collect(), column))
+    }
+    /* the index register after a comma, the parser is left untouched if there is none */
+    fn index(&mut self) -> Option<Index> {
+        let start = self.pos;
+        if self.eat(',') {
+            match self.identifier() {
+                Some((name, _)) if name.eq_ignore_ascii_case("x") => return Some(Index::X),
+                Some((name, _)) if name.eq_ignore_ascii_case("y") => return Some(Index::Y),
+                _ => {}
+            }
+        }
+        self.pos = start;
+        None
+    }
+    fn end(&mut self) -> Result<(), AsmError> {
+        match self.peek() {
+            None => Ok(()),
+            Some(_) => Err(self.error(self.column(), AsmErrorKind::Syntax("unexpected character"))),
+        }
+    }
+
+    fn statement(mut self) -> Result<Statement, AsmError> {
+        let mut statement = Statement {
+            line: self.line,
+            label: None,
+            body: Body::Empty,
+        };
+        if self.peek().is_none() {
+            return Ok(statement);
+        }
+        if self.eat('*') {
+            let column = self.column() - 1;
+            self.expect('=', "expected =")?;
+            statement.body = Body::Org {
+                column,
+                value: self.expr()?,
+            };
+            self.end()?;
+            return Ok(statement);
+        }
+        let start = self.pos;
+        if let Some((name, column)) = self.identifier() {
+            if self.eat(':') {
+                statement.label = Some((name, column));
+            } else if self.eat('=') {
+                statement.body = Body::Assign {
+                    name,
+                    column,
+                    value: self.expr()?,
+                };
+                self.end()?;
+                return Ok(statement);
+            } else {
+                self.pos = start;
+            }
+        }
+        statement.body = match self.peek() {
+            None => Body::Empty,
+            Some('.') => {
+                self.pos += 1;
+                self.directive()?
+            }
+            Some(_) => {
+                let (mnemonic, column) = self.identifier().ok_or_else(|| {
+                    self.error(self.column(), AsmErrorKind::Syntax("expected instruction"))
+                })?;
+                self.peek();
+                let operand_column = self.column();
+                Body::Instruction {
+                    mnemonic: mnemonic.to_ascii_uppercase(),
+                    column,
+                    operand: self.operand()?,
+                    operand_column,
+                }
+            }
+        };
+        self.end()?;
+        Ok(statement)
+    }
+
+    fn directive(&mut self) -> Result<Body, AsmError> {
+        let column = self.column() - 1;
+        let (name, _) = self
+            .identifier()
+            .ok_or_else(|| self.error(column, AsmErrorKind::Syntax("expected directive")))?;
+        match name.to_ascii_lowercase().as_str() {
+            "org" => Ok(Body::Org {
+                column,
+                value: self.expr()?,
+            }),
+            "byte" => {
+                let mut data = Vec::new();
+                loop {
+                    let next = self.peek();
+                    let column = self.column();
+                    if next == Some('"') {
+                        data.push(Data::Text(self.string()?, column));
+                    } else {
+                        data.push(Data::Value(self.expr()?, column));
+                    }
+                    if !self.eat(',') {
+                        return Ok(Body::Bytes(data));
+                    }
+                }
+            }
+            "word" => {
+                let mut words = Vec::new();
+                loop {
+                    self.peek();
+                    let column = self.column();
+                    words.push((self.expr()?, column));
+                    if !self.eat(',') {
+                        return Ok(Body::Words(words));
+                    }
+                }
+            }
+            _ => Err(self.error(column, AsmErrorKind::UnknownDirective(name))),
+        }
+    }
+
+    fn string(&mut self) -> Result<Vec<u8>, AsmError> {
+        let column = self.column();
+        self.pos += 1;
+        let mut text = Vec::new();
+        loop {
+            match self.peek_at(0) {
+                Some('"') => {
+                    self.pos += 1;
+                    return Ok(text);
+                }
+                Some(c) if c.is_ascii() => text.push(c as u8),
+                Some(_) => {
+                    return Err(
+                        self.error(self.column(), AsmErrorKind::Syntax("non ASCII character"))
+                    )
+                }
+                None => return Err(self.error(column, AsmErrorKind::Syntax("unterminated string"))),
+            }
+            self.pos += 1;
+        }
+    }
+
+    fn operand(&mut self) -> Result<Operand, AsmError> {
+        match self.peek() {
+            None => return Ok(Operand::None),
+            Some('#') => {
+                self.pos += 1;
+                return Ok(Operand::Immediate(self.expr()?));
+            }
+            Some('(') => {
+                /* an operand in parentheses is indirect, unless the parentheses are just part of the expression */
+                let start = self.pos;
+                self.pos += 1;
+                let expr = self.expr()?;
+                if self.index() == Some(Index::X) && self.eat(')') {
+                    return Ok(Operand::IndirectX(expr));
+                }
+                if self.eat(')') {
+                    if self.index() == Some(Index::Y) {
+                        return Ok(Operand::IndirectY(expr));
+                    }
+                    if self.peek().is_none() {
+                        return Ok(Operand::Indirect(expr));
+                    }
+                }
+                self.pos = start;
+            }
+            Some(_) => {}
+        }
+        let start = self.pos;
+        if let Some((name, _)) = self.identifier() {
+            if name.eq_ignore_ascii_case("a") && self.peek().is_none() {
+                return Ok(Operand::Accumulator);
+            }
+            self.pos = start;
+        }
+        let expr = self.expr()?;
+        if let Some(index) = self.index() {
+            return Ok(Operand::Direct(expr, Some(index)));
+        }
+        if self.eat(',') {
+            return Ok(Operand::BitBranch(expr, self.expr()?));
+        }
+        Ok(Operand::Direct(expr, None))
+    }
+
+    /* binary operators from the lowest to the highest precedence */
+    fn expr(&mut self) -> Result<Expr, AsmError> {
+        self.binary(0)
+    }
+    fn binary(&mut self, level: usize) -> Result<Expr, AsmError> {
+        const LEVELS: [&[(&str, BinaryOp)]; 6] = [
+            &[("|", BinaryOp::Or)],
+            &[("^", BinaryOp::Xor)],
+            &[("&", BinaryOp::And)],
+            &[("<<", BinaryOp::Shl), (">>", BinaryOp::Shr)],
+            &[("+", BinaryOp::Add), ("-", BinaryOp::Sub)],
+            &[
+                ("*", BinaryOp::Mul),
+                ("/", BinaryOp::Div),
+                ("%", BinaryOp::Mod),
+            ],
+        ];
+        if level == LEVELS.len() {
+            return self.unary();
+        }
+        let mut left = self.binary(level + 1)?;
+        'operators: loop {
+            self.peek();
+            for (symbol, op) in LEVELS[level] {
+                let matches = symbol
+                    .chars()
+                    .enumerate()
+                    .all(|(i, c)| self.peek_at(i) == Some(c));
+                if matches {
+                    let column = self.column();
+                    self.pos += symbol.len();
+                    let right = self.binary(level + 1)?;
+                    left = Expr::Binary {
+                        op: *op,
+                        column,
+                        left: Box::new(left),
+                        right: Box::new(right),
+                    };
+                    continue 'operators;
+                }
+            }
+            return Ok(left);
+        }
+    }
+    fn unary(&mut self) -> Result<Expr, AsmError> {
+        let next = self.peek();
+        let column = self.column();
+        match next {
+            Some('-') => {
+                self.pos += 1;
+                Ok(Expr::Negate(Box::new(self.unary()?)))
+            }
+            Some('~') => {
+                self.pos += 1;
+                Ok(Expr::Not(Box::new(self.unary()?)))
+            }
+            Some('<') => {
+                self.pos += 1;
+                Ok(Expr::Low(Box::new(self.unary()?)))
+            }
+            Some('>') => {
+                self.pos += 1;
+                Ok(Expr::High(Box::new(self.unary()?)))
+            }
+            Some('(') => {
+                self.pos += 1;
+                let expr = self.expr()?;
+                self.expect(')', "expected )")?;
+                Ok(expr)
+            }
+            Some('*') => {
+                self.pos += 1;
+                Ok(Expr::Pc)
+            }
+            Some('$') => {
+                self.pos += 1;
+                self.number(16, column)
+            }
+            Some('%') => {
+                self.pos += 1;
+                self.number(2, column)
+            }
+            Some('\'') => match (self.peek_at(1), self.peek_at(2)) {
+                (Some(c), Some('\'')) if c.is_ascii() => {
+                    self.pos += 3;
+                    Ok(Expr::Number(c as i64))
+                }
+                _ => Err(self.error(column, AsmErrorKind::Syntax("invalid character literal"))),
+            },
+            Some(c) if c.is_ascii_digit() => self.number(10, column),
+            Some(_) => match self.identifier() {
+                Some((name, column)) => Ok(Expr::Symbol { name, column }),
+                None => Err(self.error(column, AsmErrorKind::Syntax("expected expression"))),
+            },
+            None => Err(self.error(column, AsmErrorKind::Syntax("expected expression"))),
+        }
+    }
+    fn number(&mut self, radix: u32, column: usize) -> Result<Expr, AsmError> {
+        let start = self.pos;
+        while self.pos < self.chars.len() && self.chars[self.pos].is_ascii_alphanumeric() {
+            self.pos += 1;
+        }
+        let digits: String = self.chars[start..self.pos].iter().collect();
+        i64::from_str_radix(&digits, radix)
+            .ok()
+            .filter(|value| *value <= u32::MAX as i64)
+            .map(Expr::Number)
+            .ok_or_else(|| self.error(column, AsmErrorKind::Syntax("invalid number")))
+    }
+}
+
+/* what the first pass decided for an instruction */
+#[derive(Copy, Clone)]
+struct Encoding {
+    opcode: u8,
+    mode: AddressingMode,
+    size: u16,
+}
+
+struct Assembler {
+    table: &'static [Operation; 256],
+    symbols: BTreeMap<String, u16>,
+}
+
+impl Assembler {
+    fn run(mut self, statements: &[Statement]) -> Result<Program, AsmError> {
+        let encodings = self.first_pass(statements)?;
+        let emitter = self.second_pass(statements, &encodings)?;
+        Ok(Program {
+            image: emitter.image,
+            symbols: self.symbols,
+            origins: emitter.origins,
+        })
+    }
+
+    /* defines the labels and picks the encoding of every instruction */
+    fn first_pass(&mut self, statements: &[Statement]) -> Result<Vec<Option<Encoding>>, AsmError> {
+        let mut pc = PROGRAM_ROM_S as i64;
+        let mut encodings = Vec::with_capacity(statements.len());
+        /* constants that use symbols defined further down */
+        let mut pending = Vec::new();
+        for statement in statements {
+            let at = |column, kind| AsmError {
+                line: statement.line,
+                column,
+                kind,
+            };
+            if let Some((name, column)) = &statement.label {
+                self.define(name, pc, statement.line, *column)?;
+            }
+            let mut encoding = None;
+            match &statement.body {
+                Body::Empty => {}
+                Body::Assign {
+                    name,
+                    column,
+                    value,
+                } => match self.eval(value, pc, statement.line, false)? {
+                    Some(value) => self.define(name, value, statement.line, *column)?,
+                    None => pending.push(statement),
+                },
+                Body::Org { column, value } => {
+                    pc = self
+                        .eval(value, pc, statement.line, true)?
+                        .unwrap_or_default();
+                    if !(0..=0xFFFF).contains(&pc) {
+                        return Err(at(*column, AsmErrorKind::ValueOutOfRange(pc)));
+                    }
+                }
+                Body::Bytes(data) => {
+                    for item in data {
+                        pc += match item {
+                            Data::Value(..) => 1,
+                            Data::Text(text, _) => text.len() as i64,
+                        };
+                    }
+                }
+                Body::Words(words) => pc += 2 * words.len() as i64,
+                Body::Instruction {
+                    mnemonic,
+                    column,
+                    operand,
+                    operand_column,
+                } => {
+                    let chosen = self.encode(
+                        mnemonic,
+                        operand,
+                        pc,
+                        statement.line,
+                        *column,
+                        *operand_column,
+                    )?;
+                    pc += chosen.size as i64;
+                    encoding = Some(chosen);
+                }
+            }
+            encodings.push(encoding);
+        }
+        /* constants can depend on each other, define them until nothing changes */
+        loop {
+            let before = pending.len();
+            let mut undefined = Vec::new();
+            for statement in pending {
+                if let Body::Assign {
+                    name,
+                    column,
+                    value,
+                } = &statement.body
+                {
+                    match self.eval(value, 0, statement.line, false)? {
+                        Some(value) => self.define(name, value, statement.line, *column)?,
+                        None => undefined.push(statement),
+                    }
+                }
+            }
+            if undefined.is_empty() {
+                break;
+            }
+            if undefined.len() == before {
+                /* report the first symbol that is really missing */
+                let statement = undefined[0];
+                if let Body::Assign { value, .. } = &statement.body {
+                    self.eval(value, 0, statement.line, true)?;
+                }
+            }
+            pending = undefined;
+        }
+        Ok(encodings)
+    }
+
+    fn second_pass(
+        &self,
+        statements: &[Statement],
+        encodings: &[Option<Encoding>],
+    ) -> Result<Emitter, AsmError> {
+        let mut emitter = Emitter {
+            image: Image::default(),
+            origins: Vec::new(),
+            written: vec![false; u16::MAX as usize + 1],
+            pc: PROGRAM_ROM_S as i64,
+        };
+        for (statement, encoding) in statements.iter().zip(encodings) {
+            let line = statement.line;
+            let at = |column, kind| AsmError { line, column, kind };
+            let pc = emitter.pc;
+            match &statement.body {
+                Body::Empty | Body::Assign { .. } => {}
+                Body::Org { value, .. } => {
+                    emitter.pc = self.eval(value, pc, line, true)?.unwrap_or_default();
+                }
+                Body::Bytes(data) => {
+                    for item in data {
+                        match item {
+                            Data::Value(expr, column) => {
+                                let value = self.value(expr, pc, line)?;
+                                let byte = byte(value)
+                                    .ok_or(at(*column, AsmErrorKind::ValueOutOfRange(value)))?;
+                                emitter.emit(&[byte], line, *column)?;
+                            }
+                            Data::Text(text, column) => emitter.emit(text, line, *column)?,
+                        }
+                    }
+                }
+                Body::Words(words) => {
+                    for (expr, column) in words {
+                        let value = self.value(expr, pc, line)?;
+                        let word =
+                            word(value).ok_or(at(*column, AsmErrorKind::ValueOutOfRange(value)))?;
+                        emitter.emit(&[word as u8, (word >> 8) as u8], line, *column)?;
+                    }
+                }
+                Body::Instruction {
+                    operand,
+                    operand_column,
+                    column,
+                    ..
+                } => {
+                    let encoding = encoding.expect("the first pass encodes every instruction");
+                    let bytes =
+                        self.operand_bytes(&encoding, operand, pc, line, *operand_column)?;
+                    let mut code = vec![encoding.opcode];
+                    code.extend_from_slice(&bytes);
+                    emitter.emit(&code, line, *column)?;
+                }
+            }
+        }
+        Ok(emitter)
+    }
+
+    fn define(
+        &mut self,
+        name: &str,
+        value: i64,
+        line: usize,
+        column: usize,
+    ) -> Result<(), AsmError> {
+        let error = |kind| AsmError { line, column, kind };
+        if self.symbols.contains_key(name) {
+            return Err(error(AsmErrorKind::DuplicateSymbol(name.to_string())));
+        }
+        let value = word(value).ok_or(error(AsmErrorKind::ValueOutOfRange(value)))?;
+        self.symbols.insert(name.to_string(), value);
+        Ok(())
+    }
+
+    /* None for an expression with a symbol that is not defined yet, unless undefined symbols are an error */
+    fn eval(
+        &self,
+        expr: &Expr,
+        pc: i64,
+        line: usize,
+        required: bool,
+    ) -> Result<Option<i64>, AsmError> {
+        let eval = |expr| self.eval(expr, pc, line, required);
+        Ok(match expr {
+            Expr::Number(value) => Some(*value),
+            Expr::Pc => Some(pc),
+            Expr::Symbol { name, column } => match self.symbols.get(name) {
+                Some(value) => Some(*value as i64),
+                None if required => {
+                    return Err(AsmError {
+                        line,
+                        column: *column,
+                        kind: AsmErrorKind::UndefinedSymbol(name.clone()),
+                    })
+                }
+                None => None,
+            },
+            Expr::Negate(expr) => eval(expr)?.map(|value| -value),
+            Expr::Not(expr) => eval(expr)?.map(|value| !value & 0xFFFF),
+            Expr::Low(expr) => eval(expr)?.map(|value| value & 0xFF),
+            Expr::High(expr) => eval(expr)?.map(|value| (value >> 8) & 0xFF),
+            Expr::Binary {
+                op,
+                column,
+                left,
+                right,
+            } => {
+                let (left, right) = match (eval(left)?, eval(right)?) {
+                    (Some(left), Some(right)) => (left, right),
+                    _ => return Ok(None),
+                };
+                let shift = right.clamp(0, 63) as u32;
+                Some(match op {
+                    BinaryOp::Or => left | right,
+                    BinaryOp::Xor => left ^ right,
+                    BinaryOp::And => left & right,
+                    BinaryOp::Shl => left.wrapping_shl(shift),
+                    BinaryOp::Shr => left.wrapping_shr(shift),
+                    BinaryOp::Add => left.wrapping_add(right),
+                    BinaryOp::Sub => left.wrapping_sub(right),
+                    BinaryOp::Mul => left.wrapping_mul(right),
+                    BinaryOp::Div | BinaryOp::Mod if right == 0 => {
+                        return Err(AsmError {
+                            line,
+                            column: *column,
+                            kind: AsmErrorKind::DivisionByZero,
+                        })
+                    }
+                    BinaryOp::Div => left.wrapping_div(right),
+                    BinaryOp::Mod => left.wrapping_rem(right),
+                })
+            }
+        })
+    }
+    fn value(&self, expr: &Expr, pc: i64, line: usize) -> Result<i64, AsmError> {
+        Ok(self.eval(expr, pc, line, true)?.unwrap_or_default())
+    }
+
+    /* the opcode of the mnemonic in that mode, documented opcodes win over undocumented ones */
+    fn find(&self, mnemonic: &str, mode: AddressingMode) -> Option<&Operation> {
+        let mut candidates = self
+            .table
+            .iter()
+            .filter(|operation| operation.mnemonic == mnemonic && operation.mode == mode);
+        let first = candidates.next()?;
+        if !first.opc.undocumented() {
+            return Some(first);
+        }
+        candidates
+            .find(|operation| !operation.opc.undocumented())
+            .or(Some(first))
+    }
+
+    fn encode(
+        &self,
+        mnemonic: &str,
+        operand: &Operand,
+        pc: i64,
+        line: usize,
+        column: usize,
+        operand_column: usize,
+    ) -> Result<Encoding, AsmError> {
+        if !self
+            .table
+            .iter()
+            .any(|operation| operation.mnemonic == mnemonic)
+        {
+            return Err(AsmError {
+                line,
+                column,
+                kind: AsmErrorKind::UnknownMnemonic(mnemonic.to_string()),
+            });
+        }
+        let is_bit_branch = mnemonic.starts_with("BBR") || mnemonic.starts_with("BBS");
+        /* zero page is only picked for operands that are known in the first pass */
+        let fits_zero_page = |expr: &Expr| -> Result<bool, AsmError> {
+            Ok(matches!(self.eval(expr, pc, line, false)?, Some(0..=0xFF)))
+        };
+        let candidates: Vec<AddressingMode> = match operand {
+            Operand::None => vec![AddressingMode::IMPLIED, AddressingMode::ACCUMULATOR],
+            Operand::Accumulator => vec![AddressingMode::ACCUMULATOR],
+            Operand::Immediate(_) => vec![AddressingMode::IMMEDIATE],
+            Operand::BitBranch(..) if is_bit_branch => vec![AddressingMode::ZEROPAGE],
+            Operand::BitBranch(..) => vec![],
+            Operand::Direct(_, None) if is_bit_branch => vec![],
+            Operand::Direct(expr, index) => {
+                let (zero_page, absolute) = match index {
+                    None => (AddressingMode::ZEROPAGE, AddressingMode::ABSOLUTE),
+                    Some(Index::X) => (AddressingMode::ZEROPAGEX, AddressingMode::ABSOLUTEX),
+                    Some(Index::Y) => (AddressingMode::ZEROPAGEY, AddressingMode::ABSOLUTEY),
+                };
+                if index.is_none() && self.find(mnemonic, AddressingMode::RELATIVE).is_some() {
+                    vec![AddressingMode::RELATIVE]
+                } else if fits_zero_page(expr)? {
+                    vec![zero_page, absolute]
+                } else {
+                    vec![absolute, zero_page]
+                }
+            }
+            Operand::Indirect(_) => vec![AddressingMode::INDIRECT, AddressingMode::INDIRECTZP],
+            Operand::IndirectX(_) => vec![AddressingMode::INDIRECTX, AddressingMode::INDIRECTABSX],
+            Operand::IndirectY(_) => vec![AddressingMode::INDIRECTY],
+        };
+        candidates
+            .into_iter()
+            .find_map(|mode| self.find(mnemonic, mode))
+            .map(|operation| Encoding {
+                opcode: operation.opc.byte(),
+                mode: operation.mode,
+                size: operation.size,
+            })
+            .ok_or(AsmError {
+                line,
+                column: operand_column,
+                kind: AsmErrorKind::AddressingMode,
+            })
+    }
+
+    fn operand_bytes(
+        &self,
+        encoding: &Encoding,
+        operand: &Operand,
+        pc: i64,
+        line: usize,
+        column: usize,
+    ) -> Result<Vec<u8>, AsmError> {
+        let at = |kind| AsmError { line, column, kind };
+        let out_of_range = |value| at(AsmErrorKind::ValueOutOfRange(value));
+        /* offset of a branch from the end of the instruction */
+        let branch = |target: &Expr| -> Result<u8, AsmError> {
+            let offset = self.value(target, pc, line)? - (pc + encoding.size as i64);
+            if !(-128..=127).contains(&offset) {
+                return Err(at(AsmErrorKind::BranchOutOfRange(offset)));
+            }
+            Ok(offset as u8)
+        };
+        let expr = match operand {
+            Operand::None | Operand::Accumulator => return Ok(vec![]),
+            Operand::BitBranch(zero_page, target) => {
+                let value = self.value(zero_page, pc, line)?;
+                let zero_page = byte_unsigned(value).ok_or(out_of_range(value))?;
+                return Ok(vec![zero_page, branch(target)?]);
+            }
+            Operand::Direct(expr, _) if encoding.mode == AddressingMode::RELATIVE => {
+                return Ok(vec![branch(expr)?]);
+            }
+            Operand::Immediate(expr) => {
+                let value = self.value(expr, pc, line)?;
+                return Ok(vec![byte(value).ok_or(out_of_range(value))?]);
+            }
+            Operand::Direct(expr, _)
+            | Operand::Indirect(expr)
+            | Operand::IndirectX(expr)
+            | Operand::IndirectY(expr) => expr,
+        };
+        let value = self.value(expr, pc, line)?;
+        if encoding.size == 2 {
+            return Ok(vec![byte_unsigned(value).ok_or(out_of_range(value))?]);
+        }
+        let word = word(value).ok_or(out_of_range(value))?;
+        Ok(vec![word as u8, (word >> 8) as u8])
+    }
+}
+
+/* a byte operand, negative values are written as two's complement */
+fn byte(value: i64) -> Option<u8> {
+    if (-128..=0xFF).contains(&value) {
+        Some(value as u8)
+    } else {
+        None
+    }
+}
+fn byte_unsigned(value: i64) -> Option<u8> {
+    u8::try_from(value).ok()
+}
+fn word(value: i64) -> Option<u16> {
+    u16::try_from(value).ok()
+}
+
+/* collects the bytes of the second pass, every address can only be written once */
+struct Emitter {
+    image: Image,
+    origins: Vec<(usize, usize)>,
+    written: Vec<bool>,
+    pc: i64,
+}
+
+impl Emitter {
+    fn emit(&mut self, bytes: &[u8], line: usize, column: usize) -> Result<(), AsmError> {
+        let at = |kind| AsmError { line, column, kind };
+        if self.pc + bytes.len() as i64 > u16::MAX as i64 + 1 {
+            return Err(at(AsmErrorKind::ValueOutOfRange(
+                self.pc + bytes.len() as i64 - 1,
+            )));
+        }
+        for addr in self.pc..self.pc + bytes.len() as i64 {
+            if self.written[addr as usize] {
+                return Err(at(AsmErrorKind::Overlap(addr as u16)));
+            }
+            self.written[addr as usize] = true;
+        }
+        loader::push_data(&mut self.image, self.pc as u32, bytes);
+        if self.image.segments.len() > self.origins.len() {
+            self.origins.push((line, column));
+        }
+        self.pc += bytes.len() as i64;
+        Ok(())
+    }
+}
@@ -0,0 +1,51 @@
+use crate::error::SnapshotError;
+
+/*
+    Everything the cpu is connected to: RAM, ROM and memory mapped devices.
+    The cpu only talks to the bus, so the address decoding is up to the implementation.
+*/
+pub trait Bus {
+    /* a read done by the cpu, devices may react to it (e.g. clear a status flag) */
+    fn read(&mut self, addr: u16) -> u8;
+    /* the cpu reading the opcode or an operand byte of an instruction, a plain read unless the bus tells them apart */
+    fn fetch(&mut self, addr: u16) -> u8 {
+        self.read(addr)
+    }
+    fn write(&mut self, addr: u16, value: u8);
+    /* the value a read would return, without any side effects (debuggers, tracers) */
+    fn peek(&self, addr: u16) -> u8;
+    /* address of a write the bus refused since the last call, the cpu halts with a BusFault on it */
+    fn take_fault(&mut self) -> Option<u16> {
+        None
+    }
+}
+
+/*
+    A memory mapped peripheral attached to the I/O window of the Memory.
+    The address is relative to the start of the range the device was attached at.
+*/
+pub trait Device {
+    fn read(&mut self, offset: u16) -> u8;
+    fn write(&mut self, offset: u16, value: u8);
+    fn peek(&self, offset: u16) -> u8;
+    /*
+        The internal state for a save state, devices without one keep the defaults.
+        A snapshot that check_snapshot accepted must not be rejected by restore,
+        so a device that overrides restore overrides check_snapshot as well.
+    */
+    fn snapshot(&self) -> Vec<u8> {
+        Vec::new()
+    }
+    fn check_snapshot(&self, snapshot: &[u8]) -> Result<(), SnapshotError> {
+        if !snapshot.is_empty() {
+            return Err(SnapshotError {
+                expected: 0,
+                got: snapshot.len(),
+            });
+        }
+        Ok(())
+    }
+    fn restore(&mut self, snapshot: &[u8]) -> Result<(), SnapshotError> {
+        self.check_snapshot(snapshot)
+    }
+}
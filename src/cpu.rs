@@ -1,8 +1,68 @@
-use crate::memory::{Memory, PROGRAM_ROM_S, ZP_S};
-use crate::register::Register;
-use crate::{Instruction, OPCODE};
+use crate::alu;
+use crate::bus::Bus;
+use crate::dispatch::{self, Operation};
+use crate::error::CpuError;
+use crate::memory::{
+    PROGRAM_ROM_S, STACK_E, STACK_S, VECTOR_ADDR_IRQ_BRK_HIGH, VECTOR_ADDR_IRQ_BRK_LOW,
+    VECTOR_ADDR_NMI_HIGH, VECTOR_ADDR_NMI_LOW, VECTOR_ADDR_RESET_HIGH, VECTOR_ADDR_RESET_LOW, ZP_S,
+};
+use crate::register::{Flag, Register, StatusRegister};
+use crate::trace::Tracer;
+use crate::OPCODE;
 
-enum AddressingMode {
+/* cycles spent by the chip to run the reset or interrupt sequence up to loading the vector */
+const INTERRUPT_CYCLES: u64 = 7;
+
+/* the chip that is emulated, it decides the instruction set and a few behavioural details */
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum Variant {
+    NMOS6502,
+    CMOS65C02,
+    /* 65C02 with the RMB, SMB, BBR and BBS bit instructions */
+    ROCKWELL65C02,
+    /* Rockwell instructions plus WAI and STP */
+    WDC65C02,
+}
+
+/* what a successful call of CPU::execute did */
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum StepOutcome {
+    /* ran the instruction with the given opcode */
+    Executed { opcode: u8 },
+    /* ran the IRQ or NMI sequence instead of an instruction */
+    Interrupt,
+    /* WAI is waiting for an interrupt */
+    Waiting,
+    /* STP stopped the clock until the next reset */
+    Stopped,
+}
+
+/* everything CPU::restore_state needs to put the cpu back to an earlier instruction boundary */
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct CpuState {
+    pub a: u8,
+    pub x: u8,
+    pub y: u8,
+    pub ins: u8,
+    pub s: u8,
+    pub p: StatusRegister,
+    pub program_counter: u16,
+    pub clock_cycles_elapsed: u64,
+    pub irq_line: bool,
+    pub nmi_line: bool,
+    pub nmi_pending: bool,
+    pub halted: Option<CpuError>,
+    pub waiting: bool,
+    pub stopped: bool,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum AddressingMode {
+    IMPLIED,
+    ACCUMULATOR,
+    IMMEDIATE,
+    /* branches, the operand is a signed offset */
+    RELATIVE,
     ZEROPAGE,
     ZEROPAGEX,
     ZEROPAGEY,
@@ -12,6 +72,9 @@ enum AddressingMode {
     INDIRECT,
     INDIRECTX,
     INDIRECTY,
+    /* 65C02: (zp) and JMP (abs,X) */
+    INDIRECTZP,
+    INDIRECTABSX,
 }
 
 pub struct CPU {
@@ -19,124 +82,317 @@ pub struct CPU {
     y: Register<u8>,
     x: Register<u8>,
     ins: Register<u8>,
+    /* stack pointer - offset into page 1 (STACK_S..=STACK_E) */
+    s: Register<u8>,
 
-    n_flag: bool,
-    v_flag: bool,
-    b_flag: bool,
-    d_flag: bool,
-    i_flag: bool,
-    z_flag: bool,
-    c_flag: bool,
+    p: StatusRegister,
 
     program_counter: Register<u16>,
     clock_cycles_elapsed: u64,
+    /* set by get_addr if indexing moved the effective address onto another page */
+    page_crossed: bool,
+
+    /* IRQ is level-triggered, it is serviced as long as the line is asserted and I is clear */
+    irq_line: bool,
+    /* NMI is edge-triggered, only the transition of the line latches a pending interrupt */
+    nmi_line: bool,
+    nmi_pending: bool,
+
+    variant: Variant,
+    /* decoded opcodes of the variant */
+    table: &'static [Operation; 256],
+    /* stop at undocumented opcodes instead of executing them */
+    trap_undocumented: bool,
+    /* error that stopped the cpu, only a reset gets it running again */
+    halted: Option<CpuError>,
+    /* WAI: sleeping until an interrupt line is asserted */
+    waiting: bool,
+    /* STP: the clock is stopped until a reset */
+    stopped: bool,
+    /* gets a line in front of every instruction, None keeps execute free of tracing */
+    tracer: Option<Tracer>,
 }
 
 impl CPU {
-    fn inc_clock_cycles_via_ins(&mut self, ins: &Instruction) {
-        self.clock_cycles_elapsed += (*ins.cycles()) as u64;
+    fn cmos(&self) -> bool {
+        self.variant != Variant::NMOS6502
     }
     fn cmp_op(&mut self, reg_value: u8, value: u8) {
-        if value == reg_value {
-            self.n_flag = false;
-            self.z_flag = true;
-            self.c_flag = true;
-        } else if value >= reg_value {
-            self.z_flag = false;
-            self.c_flag = false;
-        } else if value <= reg_value {
-            self.z_flag = false;
-            self.c_flag = true;
-        }
+        alu::compare(&mut self.p, reg_value, value);
     }
-    fn adc_calc_flags(&mut self, prev_value: &u8) {
-        if self.a.value < *prev_value {
-            self.c_flag = true;
-        }
-        if self.a.value == 0 {
-            self.z_flag = true;
-        }
-        if self.a.value >= 0x80 {
-            self.n_flag = true;
-        }
-        if self.c_flag || (*prev_value < 0x80 && self.a.value >= 0x80) {
-            self.v_flag = true;
+    /* the 65C02 spends an extra cycle on the decimal adjust */
+    fn adc_op(&mut self, value: u8) {
+        let cmos = self.cmos();
+        self.a.value = alu::adc(&mut self.p, self.a.value, value, cmos);
+        if cmos && self.p.get(Flag::D) {
+            self.clock_cycles_elapsed += 1;
         }
     }
-    fn and_calc_flags(&mut self) {
-        if self.a.value == 0 {
-            self.z_flag = true;
-        }
-        if self.a.value >= 0x80 {
-            self.n_flag = true;
+    fn sbc_op(&mut self, value: u8) {
+        let cmos = self.cmos();
+        self.a.value = alu::sbc(&mut self.p, self.a.value, value, cmos);
+        if cmos && self.p.get(Flag::D) {
+            self.clock_cycles_elapsed += 1;
         }
     }
-    fn asl_calc_flags(&mut self, prev_value: &u8) {
-        if self.a.value == 0 {
-            self.z_flag = true;
+    /* writes op(M) back to memory and returns it, used by the undocumented combined instructions */
+    fn modify<B: Bus + ?Sized>(
+        &mut self,
+        bus: &mut B,
+        mode: AddressingMode,
+        op: fn(&mut StatusRegister, u8) -> u8,
+    ) -> u8 {
+        let addr = self.get_addr(bus, mode);
+        let value = op(&mut self.p, bus.read(addr));
+        bus.write(addr, value);
+        value
+    }
+    pub(crate) fn slo<B: Bus + ?Sized>(&mut self, bus: &mut B, mode: AddressingMode) {
+        self.a.value |= self.modify(bus, mode, alu::asl);
+        self.p.set_nz(self.a.value);
+    }
+    pub(crate) fn rla<B: Bus + ?Sized>(&mut self, bus: &mut B, mode: AddressingMode) {
+        self.a.value &= self.modify(bus, mode, alu::rol);
+        self.p.set_nz(self.a.value);
+    }
+    pub(crate) fn sre<B: Bus + ?Sized>(&mut self, bus: &mut B, mode: AddressingMode) {
+        self.a.value ^= self.modify(bus, mode, alu::lsr);
+        self.p.set_nz(self.a.value);
+    }
+    pub(crate) fn rra<B: Bus + ?Sized>(&mut self, bus: &mut B, mode: AddressingMode) {
+        let value = self.modify(bus, mode, alu::ror);
+        self.adc_op(value);
+    }
+    pub(crate) fn dcp<B: Bus + ?Sized>(&mut self, bus: &mut B, mode: AddressingMode) {
+        let value = self.modify(bus, mode, |_, value| value.wrapping_sub(1));
+        self.cmp_op(self.a.value, value);
+    }
+    pub(crate) fn isc<B: Bus + ?Sized>(&mut self, bus: &mut B, mode: AddressingMode) {
+        let value = self.modify(bus, mode, |_, value| value.wrapping_add(1));
+        self.sbc_op(value);
+    }
+    pub(crate) fn lax<B: Bus + ?Sized>(&mut self, bus: &mut B, mode: AddressingMode) {
+        let addr = self.get_addr(bus, mode);
+        self.a.value = bus.read(addr);
+        self.x.value = self.a.value;
+        self.p.set_nz(self.a.value);
+    }
+    /*
+        SHA, SHX, SHY and TAS store value & (high byte of the base address + 1).
+        If the indexing crosses a page the stored value also replaces the high byte of the address.
+    */
+    fn store_and_high<B: Bus + ?Sized>(
+        &mut self,
+        bus: &mut B,
+        mode: AddressingMode,
+        index: u8,
+        value: u8,
+    ) {
+        let addr = self.get_addr(bus, mode);
+        let high = (addr.wrapping_sub(index as u16) >> 8) as u8;
+        let value = value & high.wrapping_add(1);
+        let addr = if self.page_crossed {
+            ((value as u16) << 8) | (addr & 0x00FF)
+        } else {
+            addr
+        };
+        bus.write(addr, value);
+    }
+    fn push_stack<B: Bus + ?Sized>(&mut self, bus: &mut B, value: u8) {
+        bus.write(STACK_S + self.s.value as u16, value);
+        self.s.value = self.s.value.wrapping_sub(1);
+    }
+    fn pull_stack<B: Bus + ?Sized>(&mut self, bus: &mut B) -> u8 {
+        self.s.value = self.s.value.wrapping_add(1);
+        bus.read(STACK_S + self.s.value as u16)
+    }
+    fn push_stack_word<B: Bus + ?Sized>(&mut self, bus: &mut B, value: u16) {
+        self.push_stack(bus, (value >> 8) as u8);
+        self.push_stack(bus, value as u8);
+    }
+    fn pull_stack_word<B: Bus + ?Sized>(&mut self, bus: &mut B) -> u16 {
+        let low = self.pull_stack(bus) as u16;
+        let high = self.pull_stack(bus) as u16;
+        (high << 8) + low
+    }
+    /*
+        The operand is a signed offset relative to the instruction following the branch.
+        A taken branch costs one extra cycle and another one if the target is on a different page.
+    */
+    fn branch<B: Bus + ?Sized>(&mut self, bus: &mut B, condition: bool) {
+        self.relative_branch(bus, condition, 2);
+    }
+    /* the offset is the last byte of an instruction of the given size (BBR and BBS take 3 bytes) */
+    fn relative_branch<B: Bus + ?Sized>(&mut self, bus: &mut B, condition: bool, size: u16) {
+        if !condition {
+            return;
         }
-        if *prev_value >= 0x80 {
-            self.c_flag = true;
+        let offset_addr = self.program_counter.value.wrapping_add(size - 1);
+        let offset = bus.fetch(offset_addr) as i8;
+        let next_ins = self.program_counter.value.wrapping_add(size);
+        let target = next_ins.wrapping_add(offset as u16);
+        self.clock_cycles_elapsed += 1;
+        if next_ins & 0xFF00 != target & 0xFF00 {
+            self.clock_cycles_elapsed += 1;
         }
-        if self.a.value >= 0x80 {
-            self.n_flag = true;
+        /* the size of the branch instruction is added after the execution */
+        self.program_counter.value = target.wrapping_sub(size);
+    }
+    /* RMB and SMB */
+    fn set_zp_bit<B: Bus + ?Sized>(&mut self, bus: &mut B, bit: u8, set: bool) {
+        let addr = self.get_addr(bus, AddressingMode::ZEROPAGE);
+        let value = bus.read(addr);
+        let value = if set {
+            value | (1 << bit)
+        } else {
+            value & !(1 << bit)
+        };
+        bus.write(addr, value);
+    }
+    /* BBR and BBS: zero page operand followed by the branch offset */
+    fn branch_on_bit<B: Bus + ?Sized>(&mut self, bus: &mut B, bit: u8, set: bool) {
+        let addr = self.get_addr(bus, AddressingMode::ZEROPAGE);
+        let value = bus.read(addr);
+        self.relative_branch(bus, (value >> bit) & 0x01 == set as u8, 3);
+    }
+    /* TRB and TSB: Z from A & M, then the bits of A are cleared or set in M */
+    fn test_bits<B: Bus + ?Sized>(&mut self, bus: &mut B, mode: AddressingMode, set: bool) {
+        let addr = self.get_addr(bus, mode);
+        let value = bus.read(addr);
+        self.p.update(Flag::Z, self.a.value & value == 0);
+        let value = if set {
+            value | self.a.value
+        } else {
+            value & !self.a.value
+        };
+        bus.write(addr, value);
+    }
+    /* little endian word from two separately addressed bytes */
+    fn read_word<B: Bus + ?Sized>(bus: &mut B, low_addr: u16, high_addr: u16) -> u16 {
+        (bus.read(low_addr) as u16) + ((bus.read(high_addr) as u16) << 8)
+    }
+    /* the two operand bytes of an absolute address */
+    fn fetch_word<B: Bus + ?Sized>(bus: &mut B, addr: u16) -> u16 {
+        (bus.fetch(addr) as u16) + ((bus.fetch(addr.wrapping_add(1)) as u16) << 8)
+    }
+    /* common interrupt sequence of BRK, IRQ and NMI */
+    fn interrupt<B: Bus + ?Sized>(
+        &mut self,
+        bus: &mut B,
+        return_addr: u16,
+        b_flag: bool,
+        vector_low: u16,
+        vector_high: u16,
+    ) {
+        self.push_stack_word(bus, return_addr);
+        let status = self.p.to_byte(b_flag);
+        self.push_stack(bus, status);
+        self.p.set(Flag::I);
+        if self.cmos() {
+            self.p.clear(Flag::D);
         }
+        self.program_counter.value = CPU::read_word(bus, vector_low, vector_high);
     }
-    fn ora_calc_flags(&mut self) {
-        self.z_flag = self.a.value == 0;
-        self.n_flag = self.a.value >= 0x80;
+    /* an NMI or an unmasked IRQ is serviced before the next instruction */
+    pub fn interrupt_pending(&self) -> bool {
+        self.nmi_pending || (self.irq_line && !self.p.get(Flag::I))
+    }
+    /*
+        Services a pending interrupt instead of executing the next instruction.
+        NMI has priority over IRQ. Returns whether an interrupt was taken.
+    */
+    fn poll_interrupts<B: Bus + ?Sized>(&mut self, bus: &mut B) -> bool {
+        if self.nmi_pending {
+            self.nmi_pending = false;
+            self.interrupt(
+                bus,
+                self.program_counter.value,
+                false,
+                VECTOR_ADDR_NMI_LOW,
+                VECTOR_ADDR_NMI_HIGH,
+            );
+        } else if self.irq_line && !self.p.get(Flag::I) {
+            self.interrupt(
+                bus,
+                self.program_counter.value,
+                false,
+                VECTOR_ADDR_IRQ_BRK_LOW,
+                VECTOR_ADDR_IRQ_BRK_HIGH,
+            );
+        } else {
+            return false;
+        }
+        self.clock_cycles_elapsed += INTERRUPT_CYCLES;
+        true
     }
-    fn get_addr(&self, memory: &mut Memory, mode: AddressingMode) -> u16 {
-        let mut addr: u16;
+    /*
+        Resolves the effective address of the current instruction the way the hardware does:
+        zero page indexing and zero page pointers never leave page zero, everything else wraps at $FFFF.
+    */
+    fn get_addr<B: Bus + ?Sized>(&mut self, bus: &mut B, mode: AddressingMode) -> u16 {
+        let operand_addr = self.program_counter.value.wrapping_add(1);
+        /* only the zero page modes read the operand byte here, the others read it themselves or not at all */
+        let operand = |bus: &mut B| bus.fetch(operand_addr);
         match mode {
-            AddressingMode::ZEROPAGE => {
-                addr = *memory.read_byte(&(self.program_counter.value + 1)) as u16;
-                addr += ZP_S;
-            }
-            AddressingMode::ZEROPAGEX => {
-                addr = *memory.read_byte(&(self.program_counter.value + 1)) as u16;
-                addr += ZP_S + self.x.value as u16
-            }
-            AddressingMode::ZEROPAGEY => {
-                addr = *memory.read_byte(&(self.program_counter.value + 1)) as u16;
-                addr += ZP_S + self.y.value as u16
-            }
-            AddressingMode::ABSOLUTE => {
-                addr = (*memory.read_byte(&(self.program_counter.value + 1)) as u16)
-                    + ((*memory.read_byte(&(self.program_counter.value + 2)) as u16) << 8);
-            }
+            /* there is no operand, the address is never used */
+            AddressingMode::IMPLIED | AddressingMode::ACCUMULATOR => operand_addr,
+            AddressingMode::IMMEDIATE | AddressingMode::RELATIVE => operand_addr,
+            AddressingMode::ZEROPAGE => ZP_S + operand(bus) as u16,
+            AddressingMode::ZEROPAGEX => ZP_S + operand(bus).wrapping_add(self.x.value) as u16,
+            AddressingMode::ZEROPAGEY => ZP_S + operand(bus).wrapping_add(self.y.value) as u16,
+            AddressingMode::ABSOLUTE => CPU::fetch_word(bus, operand_addr),
             AddressingMode::ABSOLUTEX => {
-                addr = (*memory.read_byte(&(self.program_counter.value + 1)) as u16)
-                    + ((*memory.read_byte(&(self.program_counter.value + 2)) as u16) << 8)
-                    + (self.x.value as u16);
+                let base = CPU::fetch_word(bus, operand_addr);
+                self.index(base, self.x.value)
             }
             AddressingMode::ABSOLUTEY => {
-                addr = (*memory.read_byte(&(self.program_counter.value + 1)) as u16)
-                    + ((*memory.read_byte(&(self.program_counter.value + 2)) as u16) << 8)
-                    + (self.y.value as u16);
+                let base = CPU::fetch_word(bus, operand_addr);
+                self.index(base, self.y.value)
             }
             AddressingMode::INDIRECT => {
-                let base_addr = self.program_counter.value + 1;
-                let low_addr = *memory.read_byte(&base_addr) as u16;
-                let high_addr = *memory.read_byte(&(base_addr + 1)) as u16;
-                addr = *memory.read_byte(&((high_addr << 8) + low_addr)) as u16;
-                addr +=
-                    ((*memory.read_byte(&((high_addr << 8) + low_addr + 1)) as u16) << 8) as u16;
+                let pointer = CPU::fetch_word(bus, operand_addr);
+                /* the NMOS 6502 does not carry into the high byte of the pointer: JMP ($xxFF) */
+                let pointer_high = if self.cmos() {
+                    pointer.wrapping_add(1)
+                } else {
+                    (pointer & 0xFF00) | (pointer.wrapping_add(1) & 0x00FF)
+                };
+                CPU::read_word(bus, pointer, pointer_high)
             }
             AddressingMode::INDIRECTX => {
-                let base_addr = *memory.read_byte(&(self.program_counter.value + 1)) as u16;
-                let high_addr: u16 =
-                    *memory.read_byte(&(base_addr + self.x.value as u16 + 1)) as u16;
-                let low_addr: u16 = *memory.read_byte(&(base_addr + self.x.value as u16)) as u16;
-                addr = (high_addr << 8) + low_addr;
+                let pointer = operand(bus).wrapping_add(self.x.value);
+                CPU::read_word(
+                    bus,
+                    ZP_S + pointer as u16,
+                    ZP_S + pointer.wrapping_add(1) as u16,
+                )
             }
             AddressingMode::INDIRECTY => {
-                let base_addr = *memory.read_byte(&(self.program_counter.value + 1)) as u16;
-                let low_addr = *memory.read_byte(&base_addr) as u16;
-                let high_addr = *memory.read_byte(&(base_addr + 1)) as u16;
-                addr = (high_addr << 8) + low_addr + self.y.value as u16;
+                let operand = operand(bus);
+                let base = CPU::read_word(
+                    bus,
+                    ZP_S + operand as u16,
+                    ZP_S + operand.wrapping_add(1) as u16,
+                );
+                self.index(base, self.y.value)
+            }
+            AddressingMode::INDIRECTZP => {
+                let operand = operand(bus);
+                CPU::read_word(
+                    bus,
+                    ZP_S + operand as u16,
+                    ZP_S + operand.wrapping_add(1) as u16,
+                )
+            }
+            AddressingMode::INDIRECTABSX => {
+                let pointer = CPU::fetch_word(bus, operand_addr).wrapping_add(self.x.value as u16);
+                CPU::read_word(bus, pointer, pointer.wrapping_add(1))
             }
         }
+    }
+    fn index(&mut self, base: u16, offset: u8) -> u16 {
+        let addr = base.wrapping_add(offset as u16);
+        self.page_crossed = (base & 0xFF00) != (addr & 0xFF00);
         addr
     }
     pub fn new(value_a: u8, value_x: u8, value_y: u8, ins: u8) -> CPU {
@@ -145,663 +401,633 @@ impl CPU {
             x: Register::new(value_x),
             y: Register::new(value_y),
             ins: Register::new(ins),
+            s: Register::new((STACK_E - STACK_S) as u8),
 
-            n_flag: false,
-            v_flag: false,
-            b_flag: false,
-            d_flag: false,
-            i_flag: false,
-            z_flag: false,
-            c_flag: false,
+            p: StatusRegister::new(0),
 
             program_counter: Register::new(PROGRAM_ROM_S),
             clock_cycles_elapsed: 0,
+            page_crossed: false,
+
+            irq_line: false,
+            nmi_line: false,
+            nmi_pending: false,
+
+            variant: Variant::NMOS6502,
+            table: dispatch::table(Variant::NMOS6502),
+            trap_undocumented: false,
+            halted: None,
+            waiting: false,
+            stopped: false,
+            tracer: None,
+        }
+    }
+    /* creates a cpu in its power-on state and runs the reset sequence on it */
+    pub fn power_on<B: Bus + ?Sized>(bus: &mut B) -> CPU {
+        let mut cpu = CPU::new(0, 0, 0, 0);
+        cpu.s.value = 0;
+        cpu.reset(bus);
+        cpu
+    }
+    /*
+        Runs the reset sequence of the chip: the stack pointer is decremented three times
+        without writing to the stack, I is set and the program counter is loaded from the reset vector.
+    */
+    pub fn reset<B: Bus + ?Sized>(&mut self, bus: &mut B) {
+        self.s.value = self.s.value.wrapping_sub(3);
+        self.p.set(Flag::I);
+        self.nmi_pending = false;
+        self.halted = None;
+        self.waiting = false;
+        self.stopped = false;
+        if self.cmos() {
+            self.p.clear(Flag::D);
         }
+        self.program_counter.value =
+            CPU::read_word(bus, VECTOR_ADDR_RESET_LOW, VECTOR_ADDR_RESET_HIGH);
+        self.clock_cycles_elapsed += INTERRUPT_CYCLES;
     }
-    pub fn execute(&mut self, memory: &mut Memory) {
+    /*
+        Executes one instruction or interrupt sequence.
+        After an error the cpu stays halted and returns the same error until it is reset.
+    */
+    pub fn execute<B: Bus + ?Sized>(&mut self, bus: &mut B) -> Result<StepOutcome, CpuError> {
+        if let Some(error) = self.halted {
+            return Err(error);
+        }
+        if self.stopped {
+            return Ok(StepOutcome::Stopped);
+        }
+        /* WAI: any interrupt wakes the cpu up, a masked IRQ just continues after the WAI */
+        if self.waiting {
+            if !self.irq_line && !self.nmi_pending {
+                self.clock_cycles_elapsed += 1;
+                return Ok(StepOutcome::Waiting);
+            }
+            self.waiting = false;
+        }
+        /* interrupts are only recognized between instructions */
+        if self.poll_interrupts(bus) {
+            self.check_bus(bus);
+            return match self.halted {
+                Some(error) => Err(error),
+                None => Ok(StepOutcome::Interrupt),
+            };
+        }
+        self.page_crossed = false;
+        if let Some(mut tracer) = self.tracer.take() {
+            tracer.record(self, bus);
+            self.tracer = Some(tracer);
+        }
+
         /* obtain the instruction opcode */
         let opcode_addr = self.program_counter.value;
-        let opcode = (*memory.read_byte(&opcode_addr)).clone();
+        let opcode = bus.fetch(opcode_addr);
         self.ins.value = opcode;
-        let instruction = Instruction::try_from(opcode).unwrap();
-        let first_opr = self.program_counter.value + 1;
-        match instruction.opc() {
-            OPCODE::STA_ZP => {
-                let addr = self.get_addr(memory, AddressingMode::ZEROPAGE);
-                memory.write_byte(&addr, &self.a.value);
-            }
-            OPCODE::STA_ZPX => {
-                let addr = self.get_addr(memory, AddressingMode::ZEROPAGEX);
-                memory.write_byte(&addr, &self.a.value);
-            }
-            OPCODE::STA_A => {
-                let addr = self.get_addr(memory, AddressingMode::ABSOLUTE);
-                memory.write_byte(&addr, &self.a.value);
-            }
-            OPCODE::STA_AX => {
-                let addr = self.get_addr(memory, AddressingMode::ABSOLUTEX);
-                memory.write_byte(&addr, &self.a.value);
-            }
-            OPCODE::STA_AY => {
-                let addr = self.get_addr(memory, AddressingMode::ABSOLUTEY);
-                memory.write_byte(&addr, &self.a.value);
-            }
-            OPCODE::STA_IX => {
-                let addr = self.get_addr(memory, AddressingMode::INDIRECTX);
-                memory.write_byte(&addr, &self.a.value);
-            }
-            OPCODE::STA_IY => {
-                let addr = self.get_addr(memory, AddressingMode::INDIRECTY);
-                memory.write_byte(&addr, &self.a.value);
-            }
-
-            OPCODE::STX_ZP => {
-                let addr = self.get_addr(memory, AddressingMode::ZEROPAGE);
-                memory.write_byte(&addr, &self.x.value);
-            }
-            OPCODE::STX_ZPY => {
-                let addr = self.get_addr(memory, AddressingMode::ZEROPAGEY);
-                memory.write_byte(&addr, &self.x.value);
-            }
-            OPCODE::STX_A => {
-                let addr = self.get_addr(memory, AddressingMode::ABSOLUTE);
-                memory.write_byte(&addr, &self.x.value);
-            }
-
-            OPCODE::STY_ZP => {
-                let addr = self.get_addr(memory, AddressingMode::ZEROPAGE);
-                memory.write_byte(&(ZP_S + addr), &self.y.value);
-            }
-            OPCODE::STY_ZPX => {
-                let addr = self.get_addr(memory, AddressingMode::ZEROPAGEX);
-                memory.write_byte(&addr, &self.y.value);
-            }
-            OPCODE::STY_A => {
-                let addr = self.get_addr(memory, AddressingMode::ABSOLUTE);
-                memory.write_byte(&addr, &self.y.value);
-            }
-
-            OPCODE::LDA_I => {
-                let value = memory.read_byte(&first_opr);
-                self.a.value = *value;
-            }
-            OPCODE::LDA_ZP => {
-                let addr = self.get_addr(memory, AddressingMode::ZEROPAGE);
-                let value = memory.read_byte(&addr);
-                self.a.value = *value;
-            }
-            OPCODE::LDA_ZPX => {
-                let addr = self.get_addr(memory, AddressingMode::ZEROPAGEX);
-                let value: u8 = *memory.read_byte(&addr);
-                self.a.value = value;
-            }
-            OPCODE::LDA_A => {
-                let addr = self.get_addr(memory, AddressingMode::ABSOLUTE);
-                let value = *memory.read_byte(&addr);
-                self.a.value = value;
-            }
-            /* TODO: consider page crosses and add a extra cpu cycle to the execution */
-            OPCODE::LDA_AX => {
-                let addr = self.get_addr(memory, AddressingMode::ABSOLUTEX);
-                let value = *memory.read_byte(&addr);
-                self.a.value = value;
-            }
-            /* TODO: consider page crosses and add a extra cpu cycle to the execution */
-            OPCODE::LDA_AY => {
-                let addr = self.get_addr(memory, AddressingMode::ABSOLUTEY);
-                let value = *memory.read_byte(&addr);
-                self.a.value = value;
-            }
-            OPCODE::LDA_IX => {
-                let addr = self.get_addr(memory, AddressingMode::INDIRECTX);
-                let value = memory.read_byte(&addr);
-                self.a.value = *value;
-            }
-            /* TODO: consider page crosses and add a extra cpu cycle to the execution (not totally sure why this only occurs if y reg is used)*/
-            OPCODE::LDA_IY => {
-                let addr = self.get_addr(memory, AddressingMode::INDIRECTY);
-                let value = memory.read_byte(&addr);
-                self.a.value = *value;
-            }
-
-            OPCODE::LDX_I => {
-                let value = memory.read_byte(&first_opr);
-                self.x.value = *value;
-            }
-            OPCODE::LDX_A => {
-                let addr = self.get_addr(memory, AddressingMode::ABSOLUTE);
-                let value = *memory.read_byte(&addr);
-                self.x.value = value;
-            }
-            /* TODO: consider page crosses and add a extra cpu cycle to the execution */
-            OPCODE::LDX_AY => {
-                let addr = self.get_addr(memory, AddressingMode::ABSOLUTEY);
-                let value = *memory.read_byte(&addr);
-                self.x.value = value;
-            }
-            OPCODE::LDX_ZP => {
-                let addr = self.get_addr(memory, AddressingMode::ZEROPAGE);
-                let value = memory.read_byte(&addr);
-                self.x.value = *value;
-            }
-            OPCODE::LDX_ZPY => {
-                let addr = self.get_addr(memory, AddressingMode::ZEROPAGEY);
-                let value: u8 = *memory.read_byte(&addr);
-                self.x.value = value;
-            }
-
-            OPCODE::LDY_I => {
-                let value = memory.read_byte(&first_opr);
-                self.y.value = *value;
-            }
-            OPCODE::LDY_A => {
-                let addr = self.get_addr(memory, AddressingMode::ABSOLUTE);
-                let value = *memory.read_byte(&addr);
-                self.y.value = value;
-            }
-            /* TODO: consider page crosses and add a extra cpu cycle to the execution */
-            OPCODE::LDY_AX => {
-                let addr = self.get_addr(memory, AddressingMode::ABSOLUTEX);
-                let value = *memory.read_byte(&addr);
-                self.y.value = value;
-            }
-            OPCODE::LDY_ZP => {
-                let addr = self.get_addr(memory, AddressingMode::ZEROPAGE);
-                let value = memory.read_byte(&addr);
-                self.y.value = *value;
-            }
-            OPCODE::LDY_ZPX => {
-                let addr = self.get_addr(memory, AddressingMode::ZEROPAGEX);
-                let value: u8 = *memory.read_byte(&addr);
-                self.y.value = value;
-            }
-
-            OPCODE::CPX_I => {
-                self.cmp_op(self.x.value, *memory.read_byte(&first_opr));
-            }
-            OPCODE::CPX_A => {
-                let addr = self.get_addr(memory, AddressingMode::ABSOLUTE);
-                self.cmp_op(self.x.value, *memory.read_byte(&addr));
-            }
-            OPCODE::CPX_ZP => {
-                let addr = self.get_addr(memory, AddressingMode::ZEROPAGE);
-                self.cmp_op(self.x.value, *memory.read_byte(&addr));
-            }
-
-            OPCODE::CPY_I => {
-                self.cmp_op(self.y.value, *memory.read_byte(&first_opr));
-            }
-            OPCODE::CPY_A => {
-                let addr = self.get_addr(memory, AddressingMode::ABSOLUTE);
-                self.cmp_op(self.y.value, *memory.read_byte(&addr));
-            }
-            OPCODE::CPY_ZP => {
-                let addr = self.get_addr(memory, AddressingMode::ZEROPAGE);
-                self.cmp_op(self.y.value, *memory.read_byte(&addr));
+        let table = self.table;
+        let operation = &table[opcode as usize];
+        if self.trap_undocumented && operation.opc.undocumented() {
+            let error = CpuError::IllegalOpcode {
+                opcode,
+                addr: opcode_addr,
+            };
+            self.halted = Some(error);
+            return Err(error);
+        }
+        operation.handler.call(self, bus, operation.mode);
+        self.check_bus(bus);
+        match operation.opc {
+            /* these instructions load the program counter themselves */
+            OPCODE::JMP_A
+            | OPCODE::JMP_I
+            | OPCODE::JMP_IAX
+            | OPCODE::JSR
+            | OPCODE::RTS
+            | OPCODE::BRK
+            | OPCODE::RTI => {}
+            /* a jammed or stopped cpu does not move on */
+            _ if self.halted.is_some() || self.stopped => {}
+            _ => {
+                self.program_counter.value = self.program_counter.value.wrapping_add(operation.size)
             }
+        }
+        self.clock_cycles_elapsed += operation.cycles as u64;
+        if self.page_crossed && operation.page_cross_penalty {
+            self.clock_cycles_elapsed += 1;
+        }
+        match self.halted {
+            Some(error) => Err(error),
+            None => Ok(StepOutcome::Executed { opcode }),
+        }
+    }
 
-            OPCODE::CMP_I => {
-                self.cmp_op(self.a.value, *memory.read_byte(&first_opr));
-            }
-            OPCODE::CMP_ZP => {
-                let addr = self.get_addr(memory, AddressingMode::ZEROPAGE);
-                self.cmp_op(self.a.value, *memory.read_byte(&addr));
-            }
-            OPCODE::CMP_ZPX => {
-                let addr = self.get_addr(memory, AddressingMode::ZEROPAGEX);
-                let value = memory.read_byte(&addr);
-                self.cmp_op(self.a.value, *value);
-            }
-            OPCODE::CMP_A => {
-                let addr = self.get_addr(memory, AddressingMode::ABSOLUTE);
-                self.cmp_op(self.a.value, *memory.read_byte(&addr));
-            }
-            OPCODE::CMP_AX => {
-                let addr = self.get_addr(memory, AddressingMode::ABSOLUTEX);
-                self.cmp_op(self.a.value, *memory.read_byte(&addr));
-            }
-            OPCODE::CMP_AY => {
-                let addr = self.get_addr(memory, AddressingMode::ABSOLUTEY);
-                self.cmp_op(self.a.value, *memory.read_byte(&addr));
-            }
-            OPCODE::CMP_IX => {
-                let addr = self.get_addr(memory, AddressingMode::INDIRECTX);
-                let value = memory.read_byte(&addr);
-                self.cmp_op(self.a.value, *value);
-            }
-            OPCODE::CMP_IY => {
-                let addr = self.get_addr(memory, AddressingMode::INDIRECTY);
-                let value = memory.read_byte(&addr);
-                self.cmp_op(self.a.value, *value);
-            }
+    /* a write the bus refused halts the cpu after the instruction that did it */
+    fn check_bus<B: Bus + ?Sized>(&mut self, bus: &mut B) {
+        if let Some(addr) = bus.take_fault() {
+            self.halted = Some(CpuError::BusFault { addr });
+        }
+    }
 
-            OPCODE::ADC_I => {
-                let value = *memory.read_byte(&first_opr);
-                let prev_value = self.a.value;
-                self.a.value = u8::wrapping_add(self.a.value, value);
-                self.adc_calc_flags(&prev_value)
-            }
-            OPCODE::ADC_ZP => {
-                let addr = self.get_addr(memory, AddressingMode::ZEROPAGE);
-                let value = *memory.read_byte(&addr);
-                let prev_value = self.a.value;
-                self.a.value = u8::wrapping_add(self.a.value, value);
-                self.adc_calc_flags(&prev_value)
-            }
-            OPCODE::ADC_ZPX => {
-                let addr = self.get_addr(memory, AddressingMode::ZEROPAGEX);
-                let value = *memory.read_byte(&addr);
-                let prev_value = self.a.value;
-                self.a.value = u8::wrapping_add(self.a.value, value);
-                self.adc_calc_flags(&prev_value)
-            }
-            OPCODE::ADC_A => {
-                let addr = self.get_addr(memory, AddressingMode::ABSOLUTE);
-                let value = *memory.read_byte(&addr);
-                let prev_value = self.a.value;
-                self.a.value = u8::wrapping_add(self.a.value, value);
-                self.adc_calc_flags(&prev_value)
-            }
-            OPCODE::ADC_AX => {
-                let addr = self.get_addr(memory, AddressingMode::ABSOLUTEX);
-                let value = *memory.read_byte(&addr);
-                let prev_value = self.a.value;
-                self.a.value = u8::wrapping_add(self.a.value, value);
-                self.adc_calc_flags(&prev_value)
-            }
-            OPCODE::ADC_AY => {
-                let addr = self.get_addr(memory, AddressingMode::ABSOLUTEY);
-                let value = *memory.read_byte(&addr);
-                let prev_value = self.a.value;
-                self.a.value = u8::wrapping_add(self.a.value, value);
-                self.adc_calc_flags(&prev_value)
-            }
-            OPCODE::ADC_IX => {
-                let addr = self.get_addr(memory, AddressingMode::INDIRECTX);
-                let value = *memory.read_byte(&addr);
-                let prev_value = self.a.value;
-                self.a.value = u8::wrapping_add(self.a.value, value);
-                self.adc_calc_flags(&prev_value)
-            }
-            OPCODE::ADC_IY => {
-                let addr = self.get_addr(memory, AddressingMode::INDIRECTY);
-                let value = *memory.read_byte(&addr);
-                let prev_value = self.a.value;
-                self.a.value = u8::wrapping_add(self.a.value, value);
-                self.adc_calc_flags(&prev_value)
-            }
+    pub fn set_variant(&mut self, variant: Variant) {
+        self.variant = variant;
+        self.table = dispatch::table(variant);
+    }
+    pub fn variant(&self) -> Variant {
+        self.variant
+    }
+    pub fn set_trap_undocumented(&mut self, enabled: bool) {
+        self.trap_undocumented = enabled;
+    }
+    pub fn trap_undocumented(&self) -> bool {
+        self.trap_undocumented
+    }
+    /* installs or removes the tracer, the previous one is returned so its lines can be read */
+    pub fn set_tracer(&mut self, tracer: Option<Tracer>) -> Option<Tracer> {
+        std::mem::replace(&mut self.tracer, tracer)
+    }
+    pub fn tracer(&self) -> Option<&Tracer> {
+        self.tracer.as_ref()
+    }
+    pub fn tracer_mut(&mut self) -> Option<&mut Tracer> {
+        self.tracer.as_mut()
+    }
+    /* number of clock cycles the cpu has spent since it was created */
+    pub fn clock_cycles_elapsed(&self) -> u64 {
+        self.clock_cycles_elapsed
+    }
+    pub fn state(&self) -> CpuState {
+        CpuState {
+            a: self.a.value,
+            x: self.x.value,
+            y: self.y.value,
+            ins: self.ins.value,
+            s: self.s.value,
+            p: self.p,
+            program_counter: self.program_counter.value,
+            clock_cycles_elapsed: self.clock_cycles_elapsed,
+            irq_line: self.irq_line,
+            nmi_line: self.nmi_line,
+            nmi_pending: self.nmi_pending,
+            halted: self.halted,
+            waiting: self.waiting,
+            stopped: self.stopped,
+        }
+    }
+    /* the variant, the tracer and the trap setting are configuration and stay as they are */
+    pub fn restore_state(&mut self, state: &CpuState) {
+        self.a.value = state.a;
+        self.x.value = state.x;
+        self.y.value = state.y;
+        self.ins.value = state.ins;
+        self.s.value = state.s;
+        self.p = state.p;
+        self.program_counter.value = state.program_counter;
+        self.clock_cycles_elapsed = state.clock_cycles_elapsed;
+        self.irq_line = state.irq_line;
+        self.nmi_line = state.nmi_line;
+        self.nmi_pending = state.nmi_pending;
+        self.halted = state.halted;
+        self.waiting = state.waiting;
+        self.stopped = state.stopped;
+    }
+    /* the error (JAM or a trapped undocumented opcode) that stopped the cpu */
+    pub fn halted(&self) -> Option<CpuError> {
+        self.halted
+    }
+    pub fn assert_irq(&mut self) {
+        self.irq_line = true;
+    }
+    pub fn deassert_irq(&mut self) {
+        self.irq_line = false;
+    }
+    pub fn assert_nmi(&mut self) {
+        if !self.nmi_line {
+            self.nmi_pending = true;
+        }
+        self.nmi_line = true;
+    }
+    pub fn deassert_nmi(&mut self) {
+        self.nmi_line = false;
+    }
 
-            OPCODE::AND_I => {
-                let value = *memory.read_byte(&first_opr);
-                self.a.value &= value;
-                self.and_calc_flags();
-            }
-            OPCODE::AND_ZP => {
-                let addr = self.get_addr(memory, AddressingMode::ZEROPAGE);
-                let value = *memory.read_byte(&addr);
-                self.a.value &= value;
-                self.and_calc_flags();
-            }
-            OPCODE::AND_ZPX => {
-                let addr = self.get_addr(memory, AddressingMode::ZEROPAGEX);
-                let value = *memory.read_byte(&addr);
-                self.a.value &= value;
-                self.and_calc_flags();
-            }
-            OPCODE::AND_A => {
-                let addr = self.get_addr(memory, AddressingMode::ABSOLUTE);
-                let value = *memory.read_byte(&addr);
-                self.a.value &= value;
-                self.and_calc_flags();
-            }
-            OPCODE::AND_AX => {
-                let addr = self.get_addr(memory, AddressingMode::ABSOLUTEX);
-                let value = *memory.read_byte(&addr);
-                self.a.value &= value;
-                self.and_calc_flags();
-            }
-            OPCODE::AND_AY => {
-                let addr = self.get_addr(memory, AddressingMode::ABSOLUTEY);
-                let value = *memory.read_byte(&addr);
-                self.a.value &= value;
-                self.and_calc_flags();
-            }
-            OPCODE::AND_IX => {
-                let addr = self.get_addr(memory, AddressingMode::INDIRECTX);
-                let value = *memory.read_byte(&addr);
-                self.a.value &= value;
-                self.and_calc_flags();
-            }
-            OPCODE::AND_IY => {
-                let addr = self.get_addr(memory, AddressingMode::INDIRECTY);
-                let value = *memory.read_byte(&addr);
-                self.a.value &= value;
-                self.and_calc_flags();
-            }
+    /*
+        Read access to the registers.
+        The unit tests and the predicates of the run loop use them to look at the state of the cpu.
+    */
+    pub fn a(&self) -> &Register<u8> {
+        &self.a
+    }
+    pub fn x(&self) -> &Register<u8> {
+        &self.x
+    }
+    pub fn y(&self) -> &Register<u8> {
+        &self.y
+    }
+    pub fn ins(&self) -> &Register<u8> {
+        &self.ins
+    }
+    pub fn s(&self) -> &Register<u8> {
+        &self.s
+    }
+    pub fn p(&self) -> &StatusRegister {
+        &self.p
+    }
+    pub fn program_counter(&self) -> &Register<u16> {
+        &self.program_counter
+    }
+    pub fn n_flag(&self) -> bool {
+        self.p.get(Flag::N)
+    }
+    pub fn c_flag(&self) -> bool {
+        self.p.get(Flag::C)
+    }
+    pub fn i_flag(&self) -> bool {
+        self.p.get(Flag::I)
+    }
+    pub fn z_flag(&self) -> bool {
+        self.p.get(Flag::Z)
+    }
+    pub fn d_flag(&self) -> bool {
+        self.p.get(Flag::D)
+    }
+    pub fn v_flag(&self) -> bool {
+        self.p.get(Flag::V)
+    }
+}
 
-            OPCODE::ASL_ACC => {
-                let prev_value = self.a.value;
-                self.a.value <<= 1;
-                self.asl_calc_flags(&prev_value);
-            }
-            OPCODE::ASL_ZP => {
-                let addr = self.get_addr(memory, AddressingMode::ZEROPAGE);
-                let mut value = *memory.read_byte(&addr);
-                let prev_value = value;
-                value <<= 1;
-                memory.write_byte(&addr, &value);
-                self.asl_calc_flags(&prev_value);
-            }
-            OPCODE::ASL_ZPX => {
-                let addr = self.get_addr(memory, AddressingMode::ZEROPAGEX);
-                let mut value = *memory.read_byte(&addr);
-                let prev_value = value;
-                value <<= 1;
-                memory.write_byte(&addr, &value);
-                self.asl_calc_flags(&prev_value);
-            }
-            OPCODE::ASL_A => {
-                let addr = self.get_addr(memory, AddressingMode::ABSOLUTE);
-                let mut value = *memory.read_byte(&addr);
-                let prev_value = value;
-                value <<= 1;
-                memory.write_byte(&addr, &value);
-                self.asl_calc_flags(&prev_value);
-            }
-            OPCODE::ASL_AX => {
-                let addr = self.get_addr(memory, AddressingMode::ABSOLUTEX);
-                let mut value = *memory.read_byte(&addr);
-                let prev_value = value;
-                value <<= 1;
-                memory.write_byte(&addr, &value);
-                self.asl_calc_flags(&prev_value);
-            }
+/*
+    Instruction handlers, they are called through the dispatch table with the addressing mode of the opcode.
+    The program counter still points to the opcode, it is advanced after the handler returned.
+*/
+impl CPU {
+    /* an immediate operand is part of the instruction, it is fetched rather than read */
+    fn read_operand<B: Bus + ?Sized>(&mut self, bus: &mut B, mode: AddressingMode) -> u8 {
+        let addr = self.get_addr(bus, mode);
+        if mode == AddressingMode::IMMEDIATE {
+            bus.fetch(addr)
+        } else {
+            bus.read(addr)
+        }
+    }
+    /* shifts and rotates work on A or on memory */
+    fn shift<B: Bus + ?Sized>(
+        &mut self,
+        bus: &mut B,
+        mode: AddressingMode,
+        op: fn(&mut StatusRegister, u8) -> u8,
+    ) {
+        if mode == AddressingMode::ACCUMULATOR {
+            self.a.value = op(&mut self.p, self.a.value);
+        } else {
+            self.modify(bus, mode, op);
+        }
+    }
 
-            OPCODE::BIT_ZP => {
-                let addr = self.get_addr(memory, AddressingMode::ZEROPAGE);
-                let value = *memory.read_byte(&addr);
-                let added_value = value & self.a.value;
-                if added_value == 0 {
-                    self.z_flag = true;
-                }
-                self.n_flag = (value & (0x1 << 7)) != 0;
-                self.v_flag = (value & (0x1 << 6)) != 0;
-            }
-            OPCODE::BIT_A => {
-                let addr = self.get_addr(memory, AddressingMode::ABSOLUTE);
-                let value = *memory.read_byte(&addr);
-                let added_value = value & self.a.value;
-                if added_value == 0 {
-                    self.z_flag = true;
-                }
-                self.n_flag = (value & (0x1 << 7)) != 0;
-                self.v_flag = (value & (0x1 << 6)) != 0;
-            }
+    pub(crate) fn lda<B: Bus + ?Sized>(&mut self, bus: &mut B, mode: AddressingMode) {
+        self.a.value = self.read_operand(bus, mode);
+        self.p.set_nz(self.a.value);
+    }
+    pub(crate) fn ldx<B: Bus + ?Sized>(&mut self, bus: &mut B, mode: AddressingMode) {
+        self.x.value = self.read_operand(bus, mode);
+        self.p.set_nz(self.x.value);
+    }
+    pub(crate) fn ldy<B: Bus + ?Sized>(&mut self, bus: &mut B, mode: AddressingMode) {
+        self.y.value = self.read_operand(bus, mode);
+        self.p.set_nz(self.y.value);
+    }
+    pub(crate) fn sta<B: Bus + ?Sized>(&mut self, bus: &mut B, mode: AddressingMode) {
+        let addr = self.get_addr(bus, mode);
+        bus.write(addr, self.a.value);
+    }
+    pub(crate) fn stx<B: Bus + ?Sized>(&mut self, bus: &mut B, mode: AddressingMode) {
+        let addr = self.get_addr(bus, mode);
+        bus.write(addr, self.x.value);
+    }
+    pub(crate) fn sty<B: Bus + ?Sized>(&mut self, bus: &mut B, mode: AddressingMode) {
+        let addr = self.get_addr(bus, mode);
+        bus.write(addr, self.y.value);
+    }
+    pub(crate) fn stz<B: Bus + ?Sized>(&mut self, bus: &mut B, mode: AddressingMode) {
+        let addr = self.get_addr(bus, mode);
+        bus.write(addr, 0);
+    }
 
-            OPCODE::BPL => {
-                let pc_offset = *memory.read_byte(&first_opr);
-                // Flag combination for a positiv previous compare operation
-                if self.z_flag == false && self.c_flag == false {
-                    self.program_counter.value += pc_offset as u16;
-                }
-            }
-            OPCODE::BMI => {
-                let pc_offset = *memory.read_byte(&first_opr);
-                if self.z_flag == false && self.c_flag == true {
-                    self.program_counter.value += pc_offset as u16;
-                }
-            }
-            OPCODE::BVC => {
-                let pc_offset = *memory.read_byte(&first_opr);
-                if self.v_flag == false {
-                    self.program_counter.value += pc_offset as u16;
-                }
-            }
-            OPCODE::BVS => {
-                let pc_offset = *memory.read_byte(&first_opr);
-                if self.v_flag == true {
-                    self.program_counter.value += pc_offset as u16;
-                }
-            }
-            OPCODE::BCC => {
-                let pc_offset = *memory.read_byte(&first_opr);
-                if self.c_flag == false {
-                    self.program_counter.value += pc_offset as u16;
-                }
-            }
-            OPCODE::BCS => {
-                let pc_offset = *memory.read_byte(&first_opr);
-                if self.c_flag == true {
-                    self.program_counter.value += pc_offset as u16;
-                }
-            }
-            OPCODE::BNE => {
-                let pc_offset = *memory.read_byte(&first_opr);
-                if self.z_flag == false {
-                    self.program_counter.value += pc_offset as u16;
-                }
-            }
-            OPCODE::BEQ => {
-                let pc_offset = *memory.read_byte(&first_opr);
-                if self.z_flag == true {
-                    self.program_counter.value += pc_offset as u16;
-                }
-            }
+    pub(crate) fn cmp<B: Bus + ?Sized>(&mut self, bus: &mut B, mode: AddressingMode) {
+        let value = self.read_operand(bus, mode);
+        self.cmp_op(self.a.value, value);
+    }
+    pub(crate) fn cpx<B: Bus + ?Sized>(&mut self, bus: &mut B, mode: AddressingMode) {
+        let value = self.read_operand(bus, mode);
+        self.cmp_op(self.x.value, value);
+    }
+    pub(crate) fn cpy<B: Bus + ?Sized>(&mut self, bus: &mut B, mode: AddressingMode) {
+        let value = self.read_operand(bus, mode);
+        self.cmp_op(self.y.value, value);
+    }
+    pub(crate) fn adc<B: Bus + ?Sized>(&mut self, bus: &mut B, mode: AddressingMode) {
+        let value = self.read_operand(bus, mode);
+        self.adc_op(value);
+    }
+    pub(crate) fn sbc<B: Bus + ?Sized>(&mut self, bus: &mut B, mode: AddressingMode) {
+        let value = self.read_operand(bus, mode);
+        self.sbc_op(value);
+    }
+    pub(crate) fn and<B: Bus + ?Sized>(&mut self, bus: &mut B, mode: AddressingMode) {
+        self.a.value &= self.read_operand(bus, mode);
+        self.p.set_nz(self.a.value);
+    }
+    pub(crate) fn ora<B: Bus + ?Sized>(&mut self, bus: &mut B, mode: AddressingMode) {
+        self.a.value |= self.read_operand(bus, mode);
+        self.p.set_nz(self.a.value);
+    }
+    pub(crate) fn eor<B: Bus + ?Sized>(&mut self, bus: &mut B, mode: AddressingMode) {
+        self.a.value ^= self.read_operand(bus, mode);
+        self.p.set_nz(self.a.value);
+    }
+    /* BIT #imm (65C02) only affects Z */
+    pub(crate) fn bit<B: Bus + ?Sized>(&mut self, bus: &mut B, mode: AddressingMode) {
+        let value = self.read_operand(bus, mode);
+        if mode == AddressingMode::IMMEDIATE {
+            self.p.update(Flag::Z, self.a.value & value == 0);
+        } else {
+            alu::bit(&mut self.p, self.a.value, value);
+        }
+    }
 
-            OPCODE::INX => {
-                self.x.value += 1;
-            }
-            OPCODE::DEX => {
-                self.x.value -= 1;
-            }
-            OPCODE::INY => {
-                self.y.value += 1;
-            }
-            OPCODE::DEY => {
-                self.y.value -= 1;
-            }
+    pub(crate) fn asl<B: Bus + ?Sized>(&mut self, bus: &mut B, mode: AddressingMode) {
+        self.shift(bus, mode, alu::asl);
+    }
+    pub(crate) fn lsr<B: Bus + ?Sized>(&mut self, bus: &mut B, mode: AddressingMode) {
+        self.shift(bus, mode, alu::lsr);
+    }
+    pub(crate) fn rol<B: Bus + ?Sized>(&mut self, bus: &mut B, mode: AddressingMode) {
+        self.shift(bus, mode, alu::rol);
+    }
+    pub(crate) fn ror<B: Bus + ?Sized>(&mut self, bus: &mut B, mode: AddressingMode) {
+        self.shift(bus, mode, alu::ror);
+    }
+    pub(crate) fn inc<B: Bus + ?Sized>(&mut self, bus: &mut B, mode: AddressingMode) {
+        let value = if mode == AddressingMode::ACCUMULATOR {
+            self.a.value = self.a.value.wrapping_add(1);
+            self.a.value
+        } else {
+            self.modify(bus, mode, |_, value| value.wrapping_add(1))
+        };
+        self.p.set_nz(value);
+    }
+    pub(crate) fn dec<B: Bus + ?Sized>(&mut self, bus: &mut B, mode: AddressingMode) {
+        let value = if mode == AddressingMode::ACCUMULATOR {
+            self.a.value = self.a.value.wrapping_sub(1);
+            self.a.value
+        } else {
+            self.modify(bus, mode, |_, value| value.wrapping_sub(1))
+        };
+        self.p.set_nz(value);
+    }
+    pub(crate) fn inx<B: Bus + ?Sized>(&mut self, _bus: &mut B, _mode: AddressingMode) {
+        self.x.value = self.x.value.wrapping_add(1);
+        self.p.set_nz(self.x.value);
+    }
+    pub(crate) fn dex<B: Bus + ?Sized>(&mut self, _bus: &mut B, _mode: AddressingMode) {
+        self.x.value = self.x.value.wrapping_sub(1);
+        self.p.set_nz(self.x.value);
+    }
+    pub(crate) fn iny<B: Bus + ?Sized>(&mut self, _bus: &mut B, _mode: AddressingMode) {
+        self.y.value = self.y.value.wrapping_add(1);
+        self.p.set_nz(self.y.value);
+    }
+    pub(crate) fn dey<B: Bus + ?Sized>(&mut self, _bus: &mut B, _mode: AddressingMode) {
+        self.y.value = self.y.value.wrapping_sub(1);
+        self.p.set_nz(self.y.value);
+    }
 
-            OPCODE::DEC_ZP => {
-                let addr = self.get_addr(memory, AddressingMode::ZEROPAGE);
-                let value = *memory.read_byte(&addr) - 1;
-                memory.write_byte(&addr, &value);
-            }
-            OPCODE::DEC_ZPX => {
-                let addr = self.get_addr(memory, AddressingMode::ZEROPAGEX);
-                let value = *memory.read_byte(&addr) - 1;
-                memory.write_byte(&addr, &value);
-            }
-            OPCODE::DEC_A => {
-                let addr = self.get_addr(memory, AddressingMode::ABSOLUTE);
-                let value = *memory.read_byte(&addr) - 1;
-                memory.write_byte(&addr, &value);
-            }
-            OPCODE::DEC_AX => {
-                let addr = self.get_addr(memory, AddressingMode::ABSOLUTEX);
-                let value = *memory.read_byte(&addr) - 1;
-                memory.write_byte(&addr, &value);
-            }
+    pub(crate) fn bpl<B: Bus + ?Sized>(&mut self, bus: &mut B, _mode: AddressingMode) {
+        self.branch(bus, !self.p.get(Flag::N));
+    }
+    pub(crate) fn bmi<B: Bus + ?Sized>(&mut self, bus: &mut B, _mode: AddressingMode) {
+        self.branch(bus, self.p.get(Flag::N));
+    }
+    pub(crate) fn bvc<B: Bus + ?Sized>(&mut self, bus: &mut B, _mode: AddressingMode) {
+        self.branch(bus, !self.p.get(Flag::V));
+    }
+    pub(crate) fn bvs<B: Bus + ?Sized>(&mut self, bus: &mut B, _mode: AddressingMode) {
+        self.branch(bus, self.p.get(Flag::V));
+    }
+    pub(crate) fn bcc<B: Bus + ?Sized>(&mut self, bus: &mut B, _mode: AddressingMode) {
+        self.branch(bus, !self.p.get(Flag::C));
+    }
+    pub(crate) fn bcs<B: Bus + ?Sized>(&mut self, bus: &mut B, _mode: AddressingMode) {
+        self.branch(bus, self.p.get(Flag::C));
+    }
+    pub(crate) fn bne<B: Bus + ?Sized>(&mut self, bus: &mut B, _mode: AddressingMode) {
+        self.branch(bus, !self.p.get(Flag::Z));
+    }
+    pub(crate) fn beq<B: Bus + ?Sized>(&mut self, bus: &mut B, _mode: AddressingMode) {
+        self.branch(bus, self.p.get(Flag::Z));
+    }
+    pub(crate) fn bra<B: Bus + ?Sized>(&mut self, bus: &mut B, _mode: AddressingMode) {
+        self.branch(bus, true);
+    }
 
-            OPCODE::INC_ZP => {
-                let addr = self.get_addr(memory, AddressingMode::ZEROPAGE);
-                let value = *memory.read_byte(&addr) + 1;
-                memory.write_byte(&addr, &value);
-            }
-            OPCODE::INC_ZPX => {
-                let addr = self.get_addr(memory, AddressingMode::ZEROPAGEX);
-                let value = *memory.read_byte(&addr) + 1;
-                memory.write_byte(&addr, &value);
-            }
-            OPCODE::INC_A => {
-                let addr = self.get_addr(memory, AddressingMode::ABSOLUTE);
-                let value = *memory.read_byte(&addr) + 1;
-                memory.write_byte(&addr, &value);
-            }
-            OPCODE::INC_AX => {
-                let addr = self.get_addr(memory, AddressingMode::ABSOLUTEX);
-                let value = *memory.read_byte(&addr) + 1;
-                memory.write_byte(&addr, &value);
-            }
+    pub(crate) fn clc<B: Bus + ?Sized>(&mut self, _bus: &mut B, _mode: AddressingMode) {
+        self.p.clear(Flag::C);
+    }
+    pub(crate) fn sec<B: Bus + ?Sized>(&mut self, _bus: &mut B, _mode: AddressingMode) {
+        self.p.set(Flag::C);
+    }
+    pub(crate) fn cli<B: Bus + ?Sized>(&mut self, _bus: &mut B, _mode: AddressingMode) {
+        self.p.clear(Flag::I);
+    }
+    pub(crate) fn sei<B: Bus + ?Sized>(&mut self, _bus: &mut B, _mode: AddressingMode) {
+        self.p.set(Flag::I);
+    }
+    pub(crate) fn clv<B: Bus + ?Sized>(&mut self, _bus: &mut B, _mode: AddressingMode) {
+        self.p.clear(Flag::V);
+    }
+    pub(crate) fn cld<B: Bus + ?Sized>(&mut self, _bus: &mut B, _mode: AddressingMode) {
+        self.p.clear(Flag::D);
+    }
+    pub(crate) fn sed<B: Bus + ?Sized>(&mut self, _bus: &mut B, _mode: AddressingMode) {
+        self.p.set(Flag::D);
+    }
 
-            OPCODE::JMP_A => {
-                let addr = self.get_addr(memory, AddressingMode::ABSOLUTE);
-                self.program_counter.value = addr;
-            }
+    pub(crate) fn tax<B: Bus + ?Sized>(&mut self, _bus: &mut B, _mode: AddressingMode) {
+        self.x.value = self.a.value;
+        self.p.set_nz(self.x.value);
+    }
+    pub(crate) fn tay<B: Bus + ?Sized>(&mut self, _bus: &mut B, _mode: AddressingMode) {
+        self.y.value = self.a.value;
+        self.p.set_nz(self.y.value);
+    }
+    pub(crate) fn txa<B: Bus + ?Sized>(&mut self, _bus: &mut B, _mode: AddressingMode) {
+        self.a.value = self.x.value;
+        self.p.set_nz(self.a.value);
+    }
+    pub(crate) fn tya<B: Bus + ?Sized>(&mut self, _bus: &mut B, _mode: AddressingMode) {
+        self.a.value = self.y.value;
+        self.p.set_nz(self.a.value);
+    }
+    pub(crate) fn txs<B: Bus + ?Sized>(&mut self, _bus: &mut B, _mode: AddressingMode) {
+        self.s.value = self.x.value;
+    }
+    pub(crate) fn tsx<B: Bus + ?Sized>(&mut self, _bus: &mut B, _mode: AddressingMode) {
+        self.x.value = self.s.value;
+        self.p.set_nz(self.x.value);
+    }
 
-            OPCODE::JMP_I => {
-                let addr = self.get_addr(memory, AddressingMode::INDIRECT);
-                self.program_counter.value = addr;
-            }
+    pub(crate) fn pha<B: Bus + ?Sized>(&mut self, bus: &mut B, _mode: AddressingMode) {
+        self.push_stack(bus, self.a.value);
+    }
+    pub(crate) fn pla<B: Bus + ?Sized>(&mut self, bus: &mut B, _mode: AddressingMode) {
+        self.a.value = self.pull_stack(bus);
+        self.p.set_nz(self.a.value);
+    }
+    /* PHP always pushes the status with the B flag set */
+    pub(crate) fn php<B: Bus + ?Sized>(&mut self, bus: &mut B, _mode: AddressingMode) {
+        let status = self.p.to_byte(true);
+        self.push_stack(bus, status);
+    }
+    pub(crate) fn plp<B: Bus + ?Sized>(&mut self, bus: &mut B, _mode: AddressingMode) {
+        let status = self.pull_stack(bus);
+        self.p.load(status);
+    }
+    pub(crate) fn phx<B: Bus + ?Sized>(&mut self, bus: &mut B, _mode: AddressingMode) {
+        self.push_stack(bus, self.x.value);
+    }
+    pub(crate) fn plx<B: Bus + ?Sized>(&mut self, bus: &mut B, _mode: AddressingMode) {
+        self.x.value = self.pull_stack(bus);
+        self.p.set_nz(self.x.value);
+    }
+    pub(crate) fn phy<B: Bus + ?Sized>(&mut self, bus: &mut B, _mode: AddressingMode) {
+        self.push_stack(bus, self.y.value);
+    }
+    pub(crate) fn ply<B: Bus + ?Sized>(&mut self, bus: &mut B, _mode: AddressingMode) {
+        self.y.value = self.pull_stack(bus);
+        self.p.set_nz(self.y.value);
+    }
 
-            OPCODE::LSR_ACC => {
-                self.c_flag = if self.a.value & 0x01 == 0x01 {
-                    true
-                } else {
-                    false
-                };
-                self.a.value >>= 1;
-                self.z_flag = if self.a.value == 0 { true } else { false };
-                self.n_flag = false;
-            }
-            OPCODE::LSR_ZP => {
-                self.c_flag = if self.a.value & 0x01 == 0x01 {
-                    true
-                } else {
-                    false
-                };
-                let addr = self.get_addr(memory, AddressingMode::ZEROPAGE);
-                let value = *memory.read_byte(&addr);
-                memory.write_byte(&addr, &(value >> 1));
-                self.z_flag = if self.a.value == 0 { true } else { false };
-                self.n_flag = false;
-            }
-            OPCODE::LSR_ZPX => {
-                self.c_flag = if self.a.value & 0x01 == 0x01 {
-                    true
-                } else {
-                    false
-                };
-                let addr = self.get_addr(memory, AddressingMode::ZEROPAGEX);
-                let value = *memory.read_byte(&addr);
-                memory.write_byte(&addr, &(value >> 1));
-                self.z_flag = if self.a.value == 0 { true } else { false };
-                self.n_flag = false;
-            }
-            OPCODE::LSR_A => {
-                self.c_flag = if self.a.value & 0x01 == 0x01 {
-                    true
-                } else {
-                    false
-                };
-                let addr = self.get_addr(memory, AddressingMode::ABSOLUTE);
-                let value = *memory.read_byte(&addr);
-                memory.write_byte(&addr, &(value >> 1));
-                self.z_flag = if self.a.value == 0 { true } else { false };
-                self.n_flag = false;
-            }
-            OPCODE::LSR_AX => {
-                self.c_flag = if self.a.value & 0x01 == 0x01 {
-                    true
-                } else {
-                    false
-                };
-                let addr = self.get_addr(memory, AddressingMode::ABSOLUTEX);
-                let value = *memory.read_byte(&addr);
-                memory.write_byte(&addr, &(value >> 1));
-                self.z_flag = if self.a.value == 0 { true } else { false };
-                self.n_flag = false;
-            }
-            OPCODE::NOP => { /*Does nothing*/ }
-            OPCODE::ORA_I => {
-                let value = *memory.read_byte(&first_opr);
-                self.a.value |= value;
-                self.ora_calc_flags();
-            }
-            OPCODE::ORA_ZP => {
-                let addr = self.get_addr(memory, AddressingMode::ZEROPAGE);
-                let value = *memory.read_byte(&addr);
-                self.a.value |= value;
-                self.ora_calc_flags();
-            }
-            OPCODE::ORA_ZPX => {
-                let addr = self.get_addr(memory, AddressingMode::ZEROPAGEX);
-                let value = *memory.read_byte(&addr);
-                self.a.value |= value;
-                self.ora_calc_flags();
-            }
-            OPCODE::ORA_A => {
-                let addr = self.get_addr(memory, AddressingMode::ABSOLUTE);
-                let value = *memory.read_byte(&addr);
-                self.a.value |= value;
-                self.ora_calc_flags();
-            }
-            OPCODE::ORA_AX => {
-                let addr = self.get_addr(memory, AddressingMode::ABSOLUTEX);
-                let value = *memory.read_byte(&addr);
-                self.a.value |= value;
-                self.ora_calc_flags();
-            }
-            OPCODE::ORA_AY => {
-                let addr = self.get_addr(memory, AddressingMode::ABSOLUTEY);
-                let value = *memory.read_byte(&addr);
-                self.a.value |= value;
-                self.ora_calc_flags();
-            }
-            OPCODE::ORA_IX => {
-                let addr = self.get_addr(memory, AddressingMode::INDIRECTX);
-                let value = *memory.read_byte(&addr);
-                self.a.value |= value;
-                self.ora_calc_flags();
-            }
-            OPCODE::ORA_IY => {
-                let addr = self.get_addr(memory, AddressingMode::INDIRECTY);
-                let value = *memory.read_byte(&addr);
-                self.a.value |= value;
-                self.ora_calc_flags();
-            }
-            _ => {
-                let opcode: u8 = (*instruction.opc()).into();
-                panic!("Instruction not handled by simulation. OPCODE: {}", opcode);
-            }
-        }
-        if *instruction.opc() != OPCODE::JMP_I && *instruction.opc() != OPCODE::JMP_A {
-            self.program_counter.value += instruction.size();
+    pub(crate) fn jmp<B: Bus + ?Sized>(&mut self, bus: &mut B, mode: AddressingMode) {
+        self.program_counter.value = self.get_addr(bus, mode);
+    }
+    /* pushes the address of the last byte of the JSR instruction (PC + 2) */
+    pub(crate) fn jsr<B: Bus + ?Sized>(&mut self, bus: &mut B, mode: AddressingMode) {
+        let addr = self.get_addr(bus, mode);
+        self.push_stack_word(bus, self.program_counter.value.wrapping_add(2));
+        self.program_counter.value = addr;
+    }
+    pub(crate) fn rts<B: Bus + ?Sized>(&mut self, bus: &mut B, _mode: AddressingMode) {
+        self.program_counter.value = self.pull_stack_word(bus).wrapping_add(1);
+    }
+    /* BRK skips a padding byte, so the return address is PC + 2 */
+    pub(crate) fn brk<B: Bus + ?Sized>(&mut self, bus: &mut B, _mode: AddressingMode) {
+        self.interrupt(
+            bus,
+            self.program_counter.value.wrapping_add(2),
+            true,
+            VECTOR_ADDR_IRQ_BRK_LOW,
+            VECTOR_ADDR_IRQ_BRK_HIGH,
+        );
+    }
+    pub(crate) fn rti<B: Bus + ?Sized>(&mut self, bus: &mut B, _mode: AddressingMode) {
+        let status = self.pull_stack(bus);
+        self.p.load(status);
+        self.program_counter.value = self.pull_stack_word(bus);
+    }
+    /* NOPs with an operand read it, the indexed ones may take the page cross penalty */
+    pub(crate) fn nop<B: Bus + ?Sized>(&mut self, bus: &mut B, mode: AddressingMode) {
+        if mode != AddressingMode::IMPLIED {
+            self.get_addr(bus, mode);
         }
-        self.inc_clock_cycles_via_ins(&instruction);
     }
 
-    /*
-        This methods are only supposed for unit tests.
-        They are needed to set up the register inorder to effectively test the cpu executing instructions correctly.
-    */
-    pub fn a(&mut self) -> &Register<u8> {
-        &self.a
+    /* undocumented NMOS instructions */
+    pub(crate) fn sax<B: Bus + ?Sized>(&mut self, bus: &mut B, mode: AddressingMode) {
+        let addr = self.get_addr(bus, mode);
+        bus.write(addr, self.a.value & self.x.value);
     }
-    pub fn x(&mut self) -> &Register<u8> {
-        &self.x
+    pub(crate) fn anc<B: Bus + ?Sized>(&mut self, bus: &mut B, mode: AddressingMode) {
+        self.a.value &= self.read_operand(bus, mode);
+        self.p.set_nz(self.a.value);
+        self.p.update(Flag::C, self.a.value & 0x80 != 0);
     }
-    pub fn y(&mut self) -> &Register<u8> {
-        &self.y
+    pub(crate) fn alr<B: Bus + ?Sized>(&mut self, bus: &mut B, mode: AddressingMode) {
+        let value = self.a.value & self.read_operand(bus, mode);
+        self.a.value = alu::lsr(&mut self.p, value);
     }
-    pub fn ins(&mut self) -> &Register<u8> {
-        &self.ins
+    pub(crate) fn arr<B: Bus + ?Sized>(&mut self, bus: &mut B, mode: AddressingMode) {
+        let value = self.read_operand(bus, mode);
+        self.a.value = alu::arr(&mut self.p, self.a.value, value);
+    }
+    pub(crate) fn sbx<B: Bus + ?Sized>(&mut self, bus: &mut B, mode: AddressingMode) {
+        let value = self.read_operand(bus, mode);
+        let and = self.a.value & self.x.value;
+        self.p.update(Flag::C, and >= value);
+        self.x.value = and.wrapping_sub(value);
+        self.p.set_nz(self.x.value);
+    }
+    pub(crate) fn las<B: Bus + ?Sized>(&mut self, bus: &mut B, mode: AddressingMode) {
+        let value = self.read_operand(bus, mode) & self.s.value;
+        self.a.value = value;
+        self.x.value = value;
+        self.s.value = value;
+        self.p.set_nz(value);
+    }
+    /* the unstable opcodes use the magic constant $EE most chips show */
+    pub(crate) fn ane<B: Bus + ?Sized>(&mut self, bus: &mut B, mode: AddressingMode) {
+        let value = self.read_operand(bus, mode);
+        self.a.value = (self.a.value | 0xEE) & self.x.value & value;
+        self.p.set_nz(self.a.value);
+    }
+    pub(crate) fn lxa<B: Bus + ?Sized>(&mut self, bus: &mut B, mode: AddressingMode) {
+        let value = self.read_operand(bus, mode);
+        self.a.value = (self.a.value | 0xEE) & value;
+        self.x.value = self.a.value;
+        self.p.set_nz(self.a.value);
+    }
+    pub(crate) fn sha<B: Bus + ?Sized>(&mut self, bus: &mut B, mode: AddressingMode) {
+        let value = self.a.value & self.x.value;
+        self.store_and_high(bus, mode, self.y.value, value);
+    }
+    pub(crate) fn shx<B: Bus + ?Sized>(&mut self, bus: &mut B, mode: AddressingMode) {
+        self.store_and_high(bus, mode, self.y.value, self.x.value);
+    }
+    pub(crate) fn shy<B: Bus + ?Sized>(&mut self, bus: &mut B, mode: AddressingMode) {
+        self.store_and_high(bus, mode, self.x.value, self.y.value);
+    }
+    pub(crate) fn tas<B: Bus + ?Sized>(&mut self, bus: &mut B, mode: AddressingMode) {
+        self.s.value = self.a.value & self.x.value;
+        self.store_and_high(bus, mode, self.y.value, self.s.value);
+    }
+    pub(crate) fn jam<B: Bus + ?Sized>(&mut self, _bus: &mut B, _mode: AddressingMode) {
+        self.halted = Some(CpuError::Jammed {
+            opcode: self.ins.value,
+            addr: self.program_counter.value,
+        });
+    }
+
+    /* 65C02 instructions, the bit number of RMB, SMB, BBR and BBS is encoded in bit 4-6 of the opcode */
+    pub(crate) fn trb<B: Bus + ?Sized>(&mut self, bus: &mut B, mode: AddressingMode) {
+        self.test_bits(bus, mode, false);
+    }
+    pub(crate) fn tsb<B: Bus + ?Sized>(&mut self, bus: &mut B, mode: AddressingMode) {
+        self.test_bits(bus, mode, true);
     }
-    pub fn n_flag(&mut self) -> &bool {
-        &self.n_flag
+    pub(crate) fn rmb<B: Bus + ?Sized>(&mut self, bus: &mut B, _mode: AddressingMode) {
+        self.set_zp_bit(bus, (self.ins.value >> 4) & 0x07, false);
     }
-    pub fn c_flag(&mut self) -> &bool {
-        &self.c_flag
+    pub(crate) fn smb<B: Bus + ?Sized>(&mut self, bus: &mut B, _mode: AddressingMode) {
+        self.set_zp_bit(bus, (self.ins.value >> 4) & 0x07, true);
     }
-    pub fn i_flag(&mut self) -> &bool {
-        &self.i_flag
+    pub(crate) fn bbr<B: Bus + ?Sized>(&mut self, bus: &mut B, _mode: AddressingMode) {
+        self.branch_on_bit(bus, (self.ins.value >> 4) & 0x07, false);
     }
-    pub fn z_flag(&mut self) -> &bool {
-        &self.z_flag
+    pub(crate) fn bbs<B: Bus + ?Sized>(&mut self, bus: &mut B, _mode: AddressingMode) {
+        self.branch_on_bit(bus, (self.ins.value >> 4) & 0x07, true);
     }
-    pub fn d_flag(&mut self) -> &bool {
-        &self.d_flag
+    pub(crate) fn wai<B: Bus + ?Sized>(&mut self, _bus: &mut B, _mode: AddressingMode) {
+        self.waiting = true;
     }
-    pub fn v_flag(&mut self) -> &bool {
-        &self.v_flag
+    pub(crate) fn stp<B: Bus + ?Sized>(&mut self, _bus: &mut B, _mode: AddressingMode) {
+        self.stopped = true;
     }
 }
@@ -0,0 +1,334 @@
+use std::cell::Cell;
+
+use crate::bus::Bus;
+use crate::cpu::CPU;
+use crate::history::History;
+use crate::memory::Memory;
+use crate::register::Flag;
+use crate::run::{RunLimits, RunSummary, StopReason};
+
+/* the kind of bus access a watchpoint reacts to, a hit is always reported as READ or WRITE */
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum Access {
+    READ,
+    WRITE,
+    ACCESS,
+}
+
+impl Access {
+    fn matches(self, access: Access) -> bool {
+        self == Access::ACCESS || self == access
+    }
+}
+
+/* the registers a condition can look at */
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum CpuRegister {
+    A,
+    X,
+    Y,
+    S,
+    /* the status byte as PHP would push it without B */
+    P,
+    PC,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum Compare {
+    EQ,
+    NE,
+    LT,
+    LE,
+    GT,
+    GE,
+}
+
+/* a test over the cpu state, evaluated in front of the instruction a breakpoint stops at */
+#[derive(Clone, Debug, PartialEq)]
+pub enum Condition {
+    Register {
+        register: CpuRegister,
+        compare: Compare,
+        value: u16,
+    },
+    Flag {
+        flag: Flag,
+        set: bool,
+    },
+    All(Vec<Condition>),
+    Any(Vec<Condition>),
+}
+
+impl Condition {
+    pub fn holds(&self, cpu: &CPU) -> bool {
+        match self {
+            Condition::Register {
+                register,
+                compare,
+                value,
+            } => {
+                let current = match register {
+                    CpuRegister::A => cpu.a().value as u16,
+                    CpuRegister::X => cpu.x().value as u16,
+                    CpuRegister::Y => cpu.y().value as u16,
+                    CpuRegister::S => cpu.s().value as u16,
+                    CpuRegister::P => cpu.p().to_byte(false) as u16,
+                    CpuRegister::PC => cpu.program_counter().value,
+                };
+                match compare {
+                    Compare::EQ => current == *value,
+                    Compare::NE => current != *value,
+                    Compare::LT => current < *value,
+                    Compare::LE => current <= *value,
+                    Compare::GT => current > *value,
+                    Compare::GE => current >= *value,
+                }
+            }
+            Condition::Flag { flag, set } => cpu.p().get(*flag) == *set,
+            Condition::All(conditions) => conditions.iter().all(|condition| condition.holds(cpu)),
+            Condition::Any(conditions) => conditions.iter().any(|condition| condition.holds(cpu)),
+        }
+    }
+}
+
+/*
+    Stops in front of the instruction at addr, or in front of any instruction if addr is None.
+    hits counts every time the address and the condition matched, the first ignore hits do not stop.
+*/
+#[derive(Clone, Debug, PartialEq)]
+pub struct Breakpoint {
+    pub id: usize,
+    pub addr: Option<u16>,
+    pub condition: Option<Condition>,
+    pub enabled: bool,
+    pub ignore: u64,
+    pub hits: u64,
+}
+
+/*
+    Stops after the instruction that accessed start..=end, the instruction is finished first.
+    Fetching the opcode and the operand bytes of an instruction (Bus::fetch) is not a READ,
+    only the data accesses count, even when they read the bytes of the instruction itself.
+*/
+#[derive(Clone, Debug, PartialEq)]
+pub struct Watchpoint {
+    pub id: usize,
+    pub start: u16,
+    pub end: u16,
+    pub access: Access,
+    pub enabled: bool,
+    pub ignore: u64,
+    pub hits: u64,
+}
+
+/*
+    The breakpoints and watchpoints of a debugging session.
+    Ids are handed out once and stay valid until the point is removed.
+*/
+#[derive(Clone, Debug, Default)]
+pub struct Debugger {
+    breakpoints: Vec<Breakpoint>,
+    watchpoints: Vec<Watchpoint>,
+    next_id: usize,
+}
+
+impl Debugger {
+    pub fn new() -> Debugger {
+        Debugger::default()
+    }
+
+    fn id(&mut self) -> usize {
+        self.next_id += 1;
+        self.next_id
+    }
+
+    pub fn add_breakpoint(&mut self, addr: u16) -> usize {
+        self.add_conditional(Some(addr), None)
+    }
+
+    /* a breakpoint that also needs the condition, with addr None it is checked after every instruction */
+    pub fn add_conditional(&mut self, addr: Option<u16>, condition: Option<Condition>) -> usize {
+        let id = self.id();
+        self.breakpoints.push(Breakpoint {
+            id,
+            addr,
+            condition,
+            enabled: true,
+            ignore: 0,
+            hits: 0,
+        });
+        id
+    }
+
+    pub fn add_watchpoint(&mut self, start: u16, end: u16, access: Access) -> usize {
+        let id = self.id();
+        self.watchpoints.push(Watchpoint {
+            id,
+            start,
+            end,
+            access,
+            enabled: true,
+            ignore: 0,
+            hits: 0,
+        });
+        id
+    }
+
+    /* false if there is no breakpoint or watchpoint with the id */
+    pub fn remove(&mut self, id: usize) -> bool {
+        let count = self.breakpoints.len() + self.watchpoints.len();
+        self.breakpoints.retain(|breakpoint| breakpoint.id != id);
+        self.watchpoints.retain(|watchpoint| watchpoint.id != id);
+        count != self.breakpoints.len() + self.watchpoints.len()
+    }
+
+    pub fn breakpoint(&self, id: usize) -> Option<&Breakpoint> {
+        self.breakpoints
+            .iter()
+            .find(|breakpoint| breakpoint.id == id)
+    }
+    pub fn breakpoint_mut(&mut self, id: usize) -> Option<&mut Breakpoint> {
+        self.breakpoints
+            .iter_mut()
+            .find(|breakpoint| breakpoint.id == id)
+    }
+    pub fn watchpoint(&self, id: usize) -> Option<&Watchpoint> {
+        self.watchpoints
+            .iter()
+            .find(|watchpoint| watchpoint.id == id)
+    }
+    pub fn watchpoint_mut(&mut self, id: usize) -> Option<&mut Watchpoint> {
+        self.watchpoints
+            .iter_mut()
+            .find(|watchpoint| watchpoint.id == id)
+    }
+
+    /*
+        CPU::run with the breakpoints and watchpoints armed.
+        The instruction at the program counter is always executed first, so continuing from a breakpoint
+        does not stop at it again. A hit is reported even if the same instruction also reached the
+        pc limit or halted the cpu, the cpu still shows those.
+    */
+    pub fn run<B: Bus + ?Sized>(
+        &mut self,
+        cpu: &mut CPU,
+        bus: &mut B,
+        limits: &RunLimits,
+    ) -> RunSummary {
+        self.run_until(cpu, bus, limits, |_| false)
+    }
+
+    /* like run, but also stops once the predicate holds, it is asked after the breakpoints */
+    pub fn run_until<B: Bus + ?Sized>(
+        &mut self,
+        cpu: &mut CPU,
+        bus: &mut B,
+        limits: &RunLimits,
+        mut predicate: impl FnMut(&CPU) -> bool,
+    ) -> RunSummary {
+        let triggered: Cell<Option<StopReason>> = Cell::new(None);
+        let breakpoints = &mut self.breakpoints;
+        let mut watched = WatchedBus {
+            bus,
+            watchpoints: &mut self.watchpoints,
+            triggered: &triggered,
+        };
+        let mut summary = cpu.run_until(&mut watched, limits, |cpu| {
+            if triggered.get().is_some() {
+                return true;
+            }
+            let pc = cpu.program_counter().value;
+            for breakpoint in breakpoints.iter_mut() {
+                if !breakpoint.enabled
+                    || breakpoint.addr.is_some_and(|addr| addr != pc)
+                    || breakpoint
+                        .condition
+                        .as_ref()
+                        .is_some_and(|condition| !condition.holds(cpu))
+                {
+                    continue;
+                }
+                breakpoint.hits += 1;
+                if breakpoint.hits > breakpoint.ignore {
+                    triggered.set(Some(StopReason::Breakpoint {
+                        id: breakpoint.id,
+                        addr: pc,
+                    }));
+                    return true;
+                }
+            }
+            predicate(cpu)
+        });
+        if let Some(reason) = triggered.get() {
+            summary.reason = reason;
+        }
+        summary
+    }
+
+    /*
+        run that records every step into the history, so an overshoot past a breakpoint or
+        watchpoint can be taken back with History::step_back.
+    */
+    pub fn run_recorded(
+        &mut self,
+        cpu: &mut CPU,
+        memory: &mut Memory,
+        history: &mut History,
+        limits: &RunLimits,
+    ) -> RunSummary {
+        history.record(cpu, memory, limits, |cpu, memory, limits| {
+            self.run_until(cpu, memory, limits, |_| true)
+        })
+    }
+}
+
+/* forwards to the bus and records the first watchpoint an access hits */
+struct WatchedBus<'a, B: Bus + ?Sized> {
+    bus: &'a mut B,
+    watchpoints: &'a mut Vec<Watchpoint>,
+    triggered: &'a Cell<Option<StopReason>>,
+}
+
+impl<B: Bus + ?Sized> WatchedBus<'_, B> {
+    fn check(&mut self, addr: u16, access: Access, value: u8) {
+        for watchpoint in self.watchpoints.iter_mut() {
+            if !watchpoint.enabled
+                || !watchpoint.access.matches(access)
+                || addr < watchpoint.start
+                || addr > watchpoint.end
+            {
+                continue;
+            }
+            watchpoint.hits += 1;
+            if watchpoint.hits > watchpoint.ignore && self.triggered.get().is_none() {
+                self.triggered.set(Some(StopReason::Watchpoint {
+                    id: watchpoint.id,
+                    addr,
+                    access,
+                    value,
+                }));
+            }
+        }
+    }
+}
+
+impl<B: Bus + ?Sized> Bus for WatchedBus<'_, B> {
+    fn read(&mut self, addr: u16) -> u8 {
+        let value = self.bus.read(addr);
+        self.check(addr, Access::READ, value);
+        value
+    }
+    fn fetch(&mut self, addr: u16) -> u8 {
+        self.bus.fetch(addr)
+    }
+    fn write(&mut self, addr: u16, value: u8) {
+        self.bus.write(addr, value);
+        self.check(addr, Access::WRITE, value);
+    }
+    fn peek(&self, addr: u16) -> u8 {
+        self.bus.peek(addr)
+    }
+    fn take_fault(&mut self) -> Option<u16> {
+        self.bus.take_fault()
+    }
+}
@@ -0,0 +1,156 @@
+use std::collections::BTreeMap;
+use std::fmt;
+
+use crate::bus::Bus;
+use crate::cpu::{AddressingMode, Variant};
+use crate::dispatch::{self, Operation};
+
+/* one instruction or one .byte of a listing */
+#[derive(Clone, Debug, PartialEq)]
+pub struct Line {
+    pub addr: u16,
+    pub bytes: Vec<u8>,
+    /* the symbol defined at addr */
+    pub label: Option<String>,
+    /* canonical syntax, e.g. LDA $1234,X */
+    pub text: String,
+}
+
+impl fmt::Display for Line {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let bytes: Vec<String> = self
+            .bytes
+            .iter()
+            .map(|byte| format!("{:02X}", byte))
+            .collect();
+        write!(
+            f,
+            "{:04X}  {:<8}  {}",
+            self.addr,
+            bytes.join(" "),
+            self.text
+        )
+    }
+}
+
+/* the lines with a "label:" line in front of every labelled address */
+pub fn listing(lines: &[Line]) -> String {
+    let mut listing = String::new();
+    for line in lines {
+        if let Some(label) = &line.label {
+            listing.push_str(&format!("{}:\n", label));
+        }
+        listing.push_str(&format!("{}\n", line));
+    }
+    listing
+}
+
+/*
+    Turns memory back into assembler syntax, reading it with Bus::peek so devices are not disturbed.
+    Opcodes that are not documented for the variant are shown as .byte unless undocumented is set.
+*/
+pub struct Disassembler {
+    table: &'static [Operation; 256],
+    undocumented: bool,
+    symbols: BTreeMap<u16, String>,
+}
+
+impl Disassembler {
+    pub fn new(variant: Variant) -> Disassembler {
+        Disassembler {
+            table: dispatch::table(variant),
+            undocumented: false,
+            symbols: BTreeMap::new(),
+        }
+    }
+    pub fn undocumented(mut self, undocumented: bool) -> Disassembler {
+        self.undocumented = undocumented;
+        self
+    }
+    /* names addresses with the symbols of e.g. an assembled Program, the first name of an address wins */
+    pub fn symbols(mut self, symbols: &BTreeMap<String, u16>) -> Disassembler {
+        for (name, addr) in symbols {
+            self.symbols.entry(*addr).or_insert_with(|| name.clone());
+        }
+        self
+    }
+
+    /* disassembles start..=end, an instruction that does not fit into the range is shown as .byte */
+    pub fn disassemble(&self, bus: &dyn Bus, start: u16, end: u16) -> Vec<Line> {
+        let mut lines = Vec::new();
+        let mut addr = start as u32;
+        while addr <= end as u32 {
+            let line = self.line(bus, addr as u16, end);
+            addr += line.bytes.len() as u32;
+            lines.push(line);
+        }
+        lines
+    }
+
+    /* the instruction at addr */
+    pub fn line(&self, bus: &dyn Bus, addr: u16, end: u16) -> Line {
+        let opcode = bus.peek(addr);
+        let operation = &self.table[opcode as usize];
+        let fits = addr as u32 + operation.size as u32 - 1 <= end as u32;
+        if !fits || (operation.opc.undocumented() && !self.undocumented) {
+            return Line {
+                addr,
+                bytes: vec![opcode],
+                label: self.symbols.get(&addr).cloned(),
+                text: format!(".byte ${:02X}", opcode),
+            };
+        }
+        let bytes: Vec<u8> = (0..operation.size)
+            .map(|i| bus.peek(addr.wrapping_add(i)))
+            .collect();
+        let operand = self.operand(operation, addr, &bytes);
+        Line {
+            addr,
+            label: self.symbols.get(&addr).cloned(),
+            text: if operand.is_empty() {
+                operation.mnemonic.to_string()
+            } else {
+                format!("{} {}", operation.mnemonic, operand)
+            },
+            bytes,
+        }
+    }
+
+    fn name(&self, addr: u16, digits: usize) -> String {
+        match self.symbols.get(&addr) {
+            Some(name) => name.clone(),
+            None => format!("${:0digits$X}", addr, digits = digits),
+        }
+    }
+
+    fn operand(&self, operation: &Operation, addr: u16, bytes: &[u8]) -> String {
+        let zero_page = || self.name(bytes[1] as u16, 2);
+        let absolute = || self.name(bytes[1] as u16 | ((bytes[2] as u16) << 8), 4);
+        /* branch offsets count from the end of the instruction */
+        let target = |offset: u8| {
+            let next = addr.wrapping_add(operation.size);
+            self.name(next.wrapping_add(offset as i8 as u16), 4)
+        };
+        match operation.mode {
+            AddressingMode::IMPLIED => String::new(),
+            AddressingMode::ACCUMULATOR => String::from("A"),
+            AddressingMode::IMMEDIATE => format!("#${:02X}", bytes[1]),
+            AddressingMode::RELATIVE => target(bytes[1]),
+            /* BBR and BBS: a zero page address and a branch target */
+            AddressingMode::ZEROPAGE if operation.size == 3 => {
+                format!("{},{}", zero_page(), target(bytes[2]))
+            }
+            AddressingMode::ZEROPAGE => zero_page(),
+            AddressingMode::ZEROPAGEX => format!("{},X", zero_page()),
+            AddressingMode::ZEROPAGEY => format!("{},Y", zero_page()),
+            AddressingMode::ABSOLUTE => absolute(),
+            AddressingMode::ABSOLUTEX => format!("{},X", absolute()),
+            AddressingMode::ABSOLUTEY => format!("{},Y", absolute()),
+            AddressingMode::INDIRECT => format!("({})", absolute()),
+            AddressingMode::INDIRECTX => format!("({},X)", zero_page()),
+            AddressingMode::INDIRECTY => format!("({}),Y", zero_page()),
+            AddressingMode::INDIRECTZP => format!("({})", zero_page()),
+            AddressingMode::INDIRECTABSX => format!("({},X)", absolute()),
+        }
+    }
+}
@@ -0,0 +1,644 @@
+use std::sync::OnceLock;
+
+use crate::bus::Bus;
+use crate::cpu::{AddressingMode, Variant, CPU};
+use crate::{Instruction, OPCODE};
+
+/*
+    Precomputed decoding of all 256 opcodes for every cpu variant.
+    CPU::execute only indexes the table of its variant and calls the handler, nothing is allocated per step.
+*/
+
+/* the instruction an opcode runs, call matches it to the CPU method so the bus is not called through a vtable */
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum Handler {
+    ADC,
+    ALR,
+    ANC,
+    AND,
+    ANE,
+    ARR,
+    ASL,
+    BBR,
+    BBS,
+    BCC,
+    BCS,
+    BEQ,
+    BIT,
+    BMI,
+    BNE,
+    BPL,
+    BRA,
+    BRK,
+    BVC,
+    BVS,
+    CLC,
+    CLD,
+    CLI,
+    CLV,
+    CMP,
+    CPX,
+    CPY,
+    DCP,
+    DEC,
+    DEX,
+    DEY,
+    EOR,
+    INC,
+    INX,
+    INY,
+    ISC,
+    JAM,
+    JMP,
+    JSR,
+    LAS,
+    LAX,
+    LDA,
+    LDX,
+    LDY,
+    LSR,
+    LXA,
+    NOP,
+    ORA,
+    PHA,
+    PHP,
+    PHX,
+    PHY,
+    PLA,
+    PLP,
+    PLX,
+    PLY,
+    RLA,
+    RMB,
+    ROL,
+    ROR,
+    RRA,
+    RTI,
+    RTS,
+    SAX,
+    SBC,
+    SBX,
+    SEC,
+    SED,
+    SEI,
+    SHA,
+    SHX,
+    SHY,
+    SLO,
+    SMB,
+    SRE,
+    STA,
+    STP,
+    STX,
+    STY,
+    STZ,
+    TAS,
+    TAX,
+    TAY,
+    TRB,
+    TSB,
+    TSX,
+    TXA,
+    TXS,
+    TYA,
+    WAI,
+}
+
+impl Handler {
+    pub(crate) fn call<B: Bus + ?Sized>(self, cpu: &mut CPU, bus: &mut B, mode: AddressingMode) {
+        match self {
+            Handler::ADC => cpu.adc(bus, mode),
+            Handler::ALR => cpu.alr(bus, mode),
+            Handler::ANC => cpu.anc(bus, mode),
+            Handler::AND => cpu.and(bus, mode),
+            Handler::ANE => cpu.ane(bus, mode),
+            Handler::ARR => cpu.arr(bus, mode),
+            Handler::ASL => cpu.asl(bus, mode),
+            Handler::BBR => cpu.bbr(bus, mode),
+            Handler::BBS => cpu.bbs(bus, mode),
+            Handler::BCC => cpu.bcc(bus, mode),
+            Handler::BCS => cpu.bcs(bus, mode),
+            Handler::BEQ => cpu.beq(bus, mode),
+            Handler::BIT => cpu.bit(bus, mode),
+            Handler::BMI => cpu.bmi(bus, mode),
+            Handler::BNE => cpu.bne(bus, mode),
+            Handler::BPL => cpu.bpl(bus, mode),
+            Handler::BRA => cpu.bra(bus, mode),
+            Handler::BRK => cpu.brk(bus, mode),
+            Handler::BVC => cpu.bvc(bus, mode),
+            Handler::BVS => cpu.bvs(bus, mode),
+            Handler::CLC => cpu.clc(bus, mode),
+            Handler::CLD => cpu.cld(bus, mode),
+            Handler::CLI => cpu.cli(bus, mode),
+            Handler::CLV => cpu.clv(bus, mode),
+            Handler::CMP => cpu.cmp(bus, mode),
+            Handler::CPX => cpu.cpx(bus, mode),
+            Handler::CPY => cpu.cpy(bus, mode),
+            Handler::DCP => cpu.dcp(bus, mode),
+            Handler::DEC => cpu.dec(bus, mode),
+            Handler::DEX => cpu.dex(bus, mode),
+            Handler::DEY => cpu.dey(bus, mode),
+            Handler::EOR => cpu.eor(bus, mode),
+            Handler::INC => cpu.inc(bus, mode),
+            Handler::INX => cpu.inx(bus, mode),
+            Handler::INY => cpu.iny(bus, mode),
+            Handler::ISC => cpu.isc(bus, mode),
+            Handler::JAM => cpu.jam(bus, mode),
+            Handler::JMP => cpu.jmp(bus, mode),
+            Handler::JSR => cpu.jsr(bus, mode),
+            Handler::LAS => cpu.las(bus, mode),
+            Handler::LAX => cpu.lax(bus, mode),
+            Handler::LDA => cpu.lda(bus, mode),
+            Handler::LDX => cpu.ldx(bus, mode),
+            Handler::LDY => cpu.ldy(bus, mode),
+            Handler::LSR => cpu.lsr(bus, mode),
+            Handler::LXA => cpu.lxa(bus, mode),
+            Handler::NOP => cpu.nop(bus, mode),
+            Handler::ORA => cpu.ora(bus, mode),
+            Handler::PHA => cpu.pha(bus, mode),
+            Handler::PHP => cpu.php(bus, mode),
+            Handler::PHX => cpu.phx(bus, mode),
+            Handler::PHY => cpu.phy(bus, mode),
+            Handler::PLA => cpu.pla(bus, mode),
+            Handler::PLP => cpu.plp(bus, mode),
+            Handler::PLX => cpu.plx(bus, mode),
+            Handler::PLY => cpu.ply(bus, mode),
+            Handler::RLA => cpu.rla(bus, mode),
+            Handler::RMB => cpu.rmb(bus, mode),
+            Handler::ROL => cpu.rol(bus, mode),
+            Handler::ROR => cpu.ror(bus, mode),
+            Handler::RRA => cpu.rra(bus, mode),
+            Handler::RTI => cpu.rti(bus, mode),
+            Handler::RTS => cpu.rts(bus, mode),
+            Handler::SAX => cpu.sax(bus, mode),
+            Handler::SBC => cpu.sbc(bus, mode),
+            Handler::SBX => cpu.sbx(bus, mode),
+            Handler::SEC => cpu.sec(bus, mode),
+            Handler::SED => cpu.sed(bus, mode),
+            Handler::SEI => cpu.sei(bus, mode),
+            Handler::SHA => cpu.sha(bus, mode),
+            Handler::SHX => cpu.shx(bus, mode),
+            Handler::SHY => cpu.shy(bus, mode),
+            Handler::SLO => cpu.slo(bus, mode),
+            Handler::SMB => cpu.smb(bus, mode),
+            Handler::SRE => cpu.sre(bus, mode),
+            Handler::STA => cpu.sta(bus, mode),
+            Handler::STP => cpu.stp(bus, mode),
+            Handler::STX => cpu.stx(bus, mode),
+            Handler::STY => cpu.sty(bus, mode),
+            Handler::STZ => cpu.stz(bus, mode),
+            Handler::TAS => cpu.tas(bus, mode),
+            Handler::TAX => cpu.tax(bus, mode),
+            Handler::TAY => cpu.tay(bus, mode),
+            Handler::TRB => cpu.trb(bus, mode),
+            Handler::TSB => cpu.tsb(bus, mode),
+            Handler::TSX => cpu.tsx(bus, mode),
+            Handler::TXA => cpu.txa(bus, mode),
+            Handler::TXS => cpu.txs(bus, mode),
+            Handler::TYA => cpu.tya(bus, mode),
+            Handler::WAI => cpu.wai(bus, mode),
+        }
+    }
+}
+
+#[derive(Copy, Clone)]
+pub struct Operation {
+    pub opc: OPCODE,
+    pub mnemonic: &'static str,
+    pub mode: AddressingMode,
+    pub size: u16,
+    /* base cycles, page crosses and taken branches are added during the execution */
+    pub cycles: u8,
+    pub page_cross_penalty: bool,
+    pub handler: Handler,
+}
+
+static NMOS6502: OnceLock<[Operation; 256]> = OnceLock::new();
+static CMOS65C02: OnceLock<[Operation; 256]> = OnceLock::new();
+static ROCKWELL65C02: OnceLock<[Operation; 256]> = OnceLock::new();
+static WDC65C02: OnceLock<[Operation; 256]> = OnceLock::new();
+
+pub fn table(variant: Variant) -> &'static [Operation; 256] {
+    let table = match variant {
+        Variant::NMOS6502 => &NMOS6502,
+        Variant::CMOS65C02 => &CMOS65C02,
+        Variant::ROCKWELL65C02 => &ROCKWELL65C02,
+        Variant::WDC65C02 => &WDC65C02,
+    };
+    table.get_or_init(|| build(variant))
+}
+
+fn build(variant: Variant) -> [Operation; 256] {
+    std::array::from_fn(|code| {
+        let ins = Instruction::decode(code as u8, variant);
+        let (mnemonic, handler, mode) = operation(*ins.opc());
+        /* the 65C02 indexed shifts only take the long path if they cross a page */
+        let cmos_shift = variant != Variant::NMOS6502
+            && matches!(
+                ins.opc(),
+                OPCODE::ASL_AX | OPCODE::LSR_AX | OPCODE::ROL_AX | OPCODE::ROR_AX
+            );
+        Operation {
+            opc: *ins.opc(),
+            mnemonic,
+            mode,
+            size: *ins.size(),
+            cycles: *ins.cycles(),
+            page_cross_penalty: ins.page_cross_penalty() || cmos_shift,
+            handler,
+        }
+    })
+}
+
+fn operation(opc: OPCODE) -> (&'static str, Handler, AddressingMode) {
+    match opc {
+        OPCODE::STA_ZP => ("STA", Handler::STA, AddressingMode::ZEROPAGE),
+        OPCODE::STA_ZPX => ("STA", Handler::STA, AddressingMode::ZEROPAGEX),
+        OPCODE::STA_A => ("STA", Handler::STA, AddressingMode::ABSOLUTE),
+        OPCODE::STA_AX => ("STA", Handler::STA, AddressingMode::ABSOLUTEX),
+        OPCODE::STA_AY => ("STA", Handler::STA, AddressingMode::ABSOLUTEY),
+        OPCODE::STA_IX => ("STA", Handler::STA, AddressingMode::INDIRECTX),
+        OPCODE::STA_IY => ("STA", Handler::STA, AddressingMode::INDIRECTY),
+        OPCODE::STX_ZP => ("STX", Handler::STX, AddressingMode::ZEROPAGE),
+        OPCODE::STX_ZPY => ("STX", Handler::STX, AddressingMode::ZEROPAGEY),
+        OPCODE::STX_A => ("STX", Handler::STX, AddressingMode::ABSOLUTE),
+        OPCODE::STY_ZP => ("STY", Handler::STY, AddressingMode::ZEROPAGE),
+        OPCODE::STY_ZPX => ("STY", Handler::STY, AddressingMode::ZEROPAGEX),
+        OPCODE::STY_A => ("STY", Handler::STY, AddressingMode::ABSOLUTE),
+        OPCODE::LDA_I => ("LDA", Handler::LDA, AddressingMode::IMMEDIATE),
+        OPCODE::LDA_ZP => ("LDA", Handler::LDA, AddressingMode::ZEROPAGE),
+        OPCODE::LDA_ZPX => ("LDA", Handler::LDA, AddressingMode::ZEROPAGEX),
+        OPCODE::LDA_A => ("LDA", Handler::LDA, AddressingMode::ABSOLUTE),
+        OPCODE::LDA_AX => ("LDA", Handler::LDA, AddressingMode::ABSOLUTEX),
+        OPCODE::LDA_AY => ("LDA", Handler::LDA, AddressingMode::ABSOLUTEY),
+        OPCODE::LDA_IX => ("LDA", Handler::LDA, AddressingMode::INDIRECTX),
+        OPCODE::LDA_IY => ("LDA", Handler::LDA, AddressingMode::INDIRECTY),
+        OPCODE::LDX_I => ("LDX", Handler::LDX, AddressingMode::IMMEDIATE),
+        OPCODE::LDX_A => ("LDX", Handler::LDX, AddressingMode::ABSOLUTE),
+        OPCODE::LDX_AY => ("LDX", Handler::LDX, AddressingMode::ABSOLUTEY),
+        OPCODE::LDX_ZP => ("LDX", Handler::LDX, AddressingMode::ZEROPAGE),
+        OPCODE::LDX_ZPY => ("LDX", Handler::LDX, AddressingMode::ZEROPAGEY),
+        OPCODE::LDY_I => ("LDY", Handler::LDY, AddressingMode::IMMEDIATE),
+        OPCODE::LDY_A => ("LDY", Handler::LDY, AddressingMode::ABSOLUTE),
+        OPCODE::LDY_AX => ("LDY", Handler::LDY, AddressingMode::ABSOLUTEX),
+        OPCODE::LDY_ZP => ("LDY", Handler::LDY, AddressingMode::ZEROPAGE),
+        OPCODE::LDY_ZPX => ("LDY", Handler::LDY, AddressingMode::ZEROPAGEX),
+        OPCODE::CMP_I => ("CMP", Handler::CMP, AddressingMode::IMMEDIATE),
+        OPCODE::CMP_ZP => ("CMP", Handler::CMP, AddressingMode::ZEROPAGE),
+        OPCODE::CMP_ZPX => ("CMP", Handler::CMP, AddressingMode::ZEROPAGEX),
+        OPCODE::CMP_A => ("CMP", Handler::CMP, AddressingMode::ABSOLUTE),
+        OPCODE::CMP_AX => ("CMP", Handler::CMP, AddressingMode::ABSOLUTEX),
+        OPCODE::CMP_AY => ("CMP", Handler::CMP, AddressingMode::ABSOLUTEY),
+        OPCODE::CMP_IX => ("CMP", Handler::CMP, AddressingMode::INDIRECTX),
+        OPCODE::CMP_IY => ("CMP", Handler::CMP, AddressingMode::INDIRECTY),
+        OPCODE::CPX_I => ("CPX", Handler::CPX, AddressingMode::IMMEDIATE),
+        OPCODE::CPX_A => ("CPX", Handler::CPX, AddressingMode::ABSOLUTE),
+        OPCODE::CPX_ZP => ("CPX", Handler::CPX, AddressingMode::ZEROPAGE),
+        OPCODE::CPY_I => ("CPY", Handler::CPY, AddressingMode::IMMEDIATE),
+        OPCODE::CPY_A => ("CPY", Handler::CPY, AddressingMode::ABSOLUTE),
+        OPCODE::CPY_ZP => ("CPY", Handler::CPY, AddressingMode::ZEROPAGE),
+        OPCODE::ADC_I => ("ADC", Handler::ADC, AddressingMode::IMMEDIATE),
+        OPCODE::ADC_ZP => ("ADC", Handler::ADC, AddressingMode::ZEROPAGE),
+        OPCODE::ADC_ZPX => ("ADC", Handler::ADC, AddressingMode::ZEROPAGEX),
+        OPCODE::ADC_A => ("ADC", Handler::ADC, AddressingMode::ABSOLUTE),
+        OPCODE::ADC_AX => ("ADC", Handler::ADC, AddressingMode::ABSOLUTEX),
+        OPCODE::ADC_AY => ("ADC", Handler::ADC, AddressingMode::ABSOLUTEY),
+        OPCODE::ADC_IX => ("ADC", Handler::ADC, AddressingMode::INDIRECTX),
+        OPCODE::ADC_IY => ("ADC", Handler::ADC, AddressingMode::INDIRECTY),
+        OPCODE::AND_I => ("AND", Handler::AND, AddressingMode::IMMEDIATE),
+        OPCODE::AND_ZP => ("AND", Handler::AND, AddressingMode::ZEROPAGE),
+        OPCODE::AND_ZPX => ("AND", Handler::AND, AddressingMode::ZEROPAGEX),
+        OPCODE::AND_A => ("AND", Handler::AND, AddressingMode::ABSOLUTE),
+        OPCODE::AND_AX => ("AND", Handler::AND, AddressingMode::ABSOLUTEX),
+        OPCODE::AND_AY => ("AND", Handler::AND, AddressingMode::ABSOLUTEY),
+        OPCODE::AND_IX => ("AND", Handler::AND, AddressingMode::INDIRECTX),
+        OPCODE::AND_IY => ("AND", Handler::AND, AddressingMode::INDIRECTY),
+        OPCODE::ASL_ACC => ("ASL", Handler::ASL, AddressingMode::ACCUMULATOR),
+        OPCODE::ASL_ZP => ("ASL", Handler::ASL, AddressingMode::ZEROPAGE),
+        OPCODE::ASL_ZPX => ("ASL", Handler::ASL, AddressingMode::ZEROPAGEX),
+        OPCODE::ASL_A => ("ASL", Handler::ASL, AddressingMode::ABSOLUTE),
+        OPCODE::ASL_AX => ("ASL", Handler::ASL, AddressingMode::ABSOLUTEX),
+        OPCODE::BIT_ZP => ("BIT", Handler::BIT, AddressingMode::ZEROPAGE),
+        OPCODE::BIT_A => ("BIT", Handler::BIT, AddressingMode::ABSOLUTE),
+        OPCODE::BPL => ("BPL", Handler::BPL, AddressingMode::RELATIVE),
+        OPCODE::BMI => ("BMI", Handler::BMI, AddressingMode::RELATIVE),
+        OPCODE::BVC => ("BVC", Handler::BVC, AddressingMode::RELATIVE),
+        OPCODE::BVS => ("BVS", Handler::BVS, AddressingMode::RELATIVE),
+        OPCODE::BCC => ("BCC", Handler::BCC, AddressingMode::RELATIVE),
+        OPCODE::BCS => ("BCS", Handler::BCS, AddressingMode::RELATIVE),
+        OPCODE::BNE => ("BNE", Handler::BNE, AddressingMode::RELATIVE),
+        OPCODE::BEQ => ("BEQ", Handler::BEQ, AddressingMode::RELATIVE),
+        OPCODE::BRK => ("BRK", Handler::BRK, AddressingMode::IMPLIED),
+        OPCODE::DEC_ZP => ("DEC", Handler::DEC, AddressingMode::ZEROPAGE),
+        OPCODE::DEC_ZPX => ("DEC", Handler::DEC, AddressingMode::ZEROPAGEX),
+        OPCODE::DEC_A => ("DEC", Handler::DEC, AddressingMode::ABSOLUTE),
+        OPCODE::DEC_AX => ("DEC", Handler::DEC, AddressingMode::ABSOLUTEX),
+        OPCODE::EOR_I => ("EOR", Handler::EOR, AddressingMode::IMMEDIATE),
+        OPCODE::EOR_ZP => ("EOR", Handler::EOR, AddressingMode::ZEROPAGE),
+        OPCODE::EOR_ZPX => ("EOR", Handler::EOR, AddressingMode::ZEROPAGEX),
+        OPCODE::EOR_A => ("EOR", Handler::EOR, AddressingMode::ABSOLUTE),
+        OPCODE::EOR_AX => ("EOR", Handler::EOR, AddressingMode::ABSOLUTEX),
+        OPCODE::EOR_AY => ("EOR", Handler::EOR, AddressingMode::ABSOLUTEY),
+        OPCODE::EOR_IX => ("EOR", Handler::EOR, AddressingMode::INDIRECTX),
+        OPCODE::EOR_IY => ("EOR", Handler::EOR, AddressingMode::INDIRECTY),
+        OPCODE::CLC => ("CLC", Handler::CLC, AddressingMode::IMPLIED),
+        OPCODE::SEC => ("SEC", Handler::SEC, AddressingMode::IMPLIED),
+        OPCODE::CLI => ("CLI", Handler::CLI, AddressingMode::IMPLIED),
+        OPCODE::SEI => ("SEI", Handler::SEI, AddressingMode::IMPLIED),
+        OPCODE::CLV => ("CLV", Handler::CLV, AddressingMode::IMPLIED),
+        OPCODE::CLD => ("CLD", Handler::CLD, AddressingMode::IMPLIED),
+        OPCODE::SED => ("SED", Handler::SED, AddressingMode::IMPLIED),
+        OPCODE::INC_ZP => ("INC", Handler::INC, AddressingMode::ZEROPAGE),
+        OPCODE::INC_ZPX => ("INC", Handler::INC, AddressingMode::ZEROPAGEX),
+        OPCODE::INC_A => ("INC", Handler::INC, AddressingMode::ABSOLUTE),
+        OPCODE::INC_AX => ("INC", Handler::INC, AddressingMode::ABSOLUTEX),
+        OPCODE::JMP_A => ("JMP", Handler::JMP, AddressingMode::ABSOLUTE),
+        OPCODE::JMP_I => ("JMP", Handler::JMP, AddressingMode::INDIRECT),
+        OPCODE::JSR => ("JSR", Handler::JSR, AddressingMode::ABSOLUTE),
+        OPCODE::LSR_ACC => ("LSR", Handler::LSR, AddressingMode::ACCUMULATOR),
+        OPCODE::LSR_ZP => ("LSR", Handler::LSR, AddressingMode::ZEROPAGE),
+        OPCODE::LSR_ZPX => ("LSR", Handler::LSR, AddressingMode::ZEROPAGEX),
+        OPCODE::LSR_A => ("LSR", Handler::LSR, AddressingMode::ABSOLUTE),
+        OPCODE::LSR_AX => ("LSR", Handler::LSR, AddressingMode::ABSOLUTEX),
+        OPCODE::NOP => ("NOP", Handler::NOP, AddressingMode::IMPLIED),
+        OPCODE::ORA_I => ("ORA", Handler::ORA, AddressingMode::IMMEDIATE),
+        OPCODE::ORA_ZP => ("ORA", Handler::ORA, AddressingMode::ZEROPAGE),
+        OPCODE::ORA_ZPX => ("ORA", Handler::ORA, AddressingMode::ZEROPAGEX),
+        OPCODE::ORA_A => ("ORA", Handler::ORA, AddressingMode::ABSOLUTE),
+        OPCODE::ORA_AX => ("ORA", Handler::ORA, AddressingMode::ABSOLUTEX),
+        OPCODE::ORA_AY => ("ORA", Handler::ORA, AddressingMode::ABSOLUTEY),
+        OPCODE::ORA_IX => ("ORA", Handler::ORA, AddressingMode::INDIRECTX),
+        OPCODE::ORA_IY => ("ORA", Handler::ORA, AddressingMode::INDIRECTY),
+        OPCODE::TAX => ("TAX", Handler::TAX, AddressingMode::IMPLIED),
+        OPCODE::TXA => ("TXA", Handler::TXA, AddressingMode::IMPLIED),
+        OPCODE::DEX => ("DEX", Handler::DEX, AddressingMode::IMPLIED),
+        OPCODE::INX => ("INX", Handler::INX, AddressingMode::IMPLIED),
+        OPCODE::TAY => ("TAY", Handler::TAY, AddressingMode::IMPLIED),
+        OPCODE::TYA => ("TYA", Handler::TYA, AddressingMode::IMPLIED),
+        OPCODE::DEY => ("DEY", Handler::DEY, AddressingMode::IMPLIED),
+        OPCODE::INY => ("INY", Handler::INY, AddressingMode::IMPLIED),
+        OPCODE::ROL_ACC => ("ROL", Handler::ROL, AddressingMode::ACCUMULATOR),
+        OPCODE::ROL_ZP => ("ROL", Handler::ROL, AddressingMode::ZEROPAGE),
+        OPCODE::ROL_ZPX => ("ROL", Handler::ROL, AddressingMode::ZEROPAGEX),
+        OPCODE::ROL_A => ("ROL", Handler::ROL, AddressingMode::ABSOLUTE),
+        OPCODE::ROL_AX => ("ROL", Handler::ROL, AddressingMode::ABSOLUTEX),
+        OPCODE::ROR_ACC => ("ROR", Handler::ROR, AddressingMode::ACCUMULATOR),
+        OPCODE::ROR_ZP => ("ROR", Handler::ROR, AddressingMode::ZEROPAGE),
+        OPCODE::ROR_ZPX => ("ROR", Handler::ROR, AddressingMode::ZEROPAGEX),
+        OPCODE::ROR_A => ("ROR", Handler::ROR, AddressingMode::ABSOLUTE),
+        OPCODE::ROR_AX => ("ROR", Handler::ROR, AddressingMode::ABSOLUTEX),
+        OPCODE::RTI => ("RTI", Handler::RTI, AddressingMode::IMPLIED),
+        OPCODE::RTS => ("RTS", Handler::RTS, AddressingMode::IMPLIED),
+        OPCODE::SBC_I => ("SBC", Handler::SBC, AddressingMode::IMMEDIATE),
+        OPCODE::SBC_ZP => ("SBC", Handler::SBC, AddressingMode::ZEROPAGE),
+        OPCODE::SBC_ZPX => ("SBC", Handler::SBC, AddressingMode::ZEROPAGEX),
+        OPCODE::SBC_A => ("SBC", Handler::SBC, AddressingMode::ABSOLUTE),
+        OPCODE::SBC_AX => ("SBC", Handler::SBC, AddressingMode::ABSOLUTEX),
+        OPCODE::SBC_AY => ("SBC", Handler::SBC, AddressingMode::ABSOLUTEY),
+        OPCODE::SBC_IX => ("SBC", Handler::SBC, AddressingMode::INDIRECTX),
+        OPCODE::SBC_IY => ("SBC", Handler::SBC, AddressingMode::INDIRECTY),
+        OPCODE::TXS => ("TXS", Handler::TXS, AddressingMode::IMPLIED),
+        OPCODE::TSX => ("TSX", Handler::TSX, AddressingMode::IMPLIED),
+        OPCODE::PHA => ("PHA", Handler::PHA, AddressingMode::IMPLIED),
+        OPCODE::PLA => ("PLA", Handler::PLA, AddressingMode::IMPLIED),
+        OPCODE::PHP => ("PHP", Handler::PHP, AddressingMode::IMPLIED),
+        OPCODE::PLP => ("PLP", Handler::PLP, AddressingMode::IMPLIED),
+        OPCODE::SLO_ZP => ("SLO", Handler::SLO, AddressingMode::ZEROPAGE),
+        OPCODE::SLO_ZPX => ("SLO", Handler::SLO, AddressingMode::ZEROPAGEX),
+        OPCODE::SLO_A => ("SLO", Handler::SLO, AddressingMode::ABSOLUTE),
+        OPCODE::SLO_AX => ("SLO", Handler::SLO, AddressingMode::ABSOLUTEX),
+        OPCODE::SLO_AY => ("SLO", Handler::SLO, AddressingMode::ABSOLUTEY),
+        OPCODE::SLO_IX => ("SLO", Handler::SLO, AddressingMode::INDIRECTX),
+        OPCODE::SLO_IY => ("SLO", Handler::SLO, AddressingMode::INDIRECTY),
+        OPCODE::RLA_ZP => ("RLA", Handler::RLA, AddressingMode::ZEROPAGE),
+        OPCODE::RLA_ZPX => ("RLA", Handler::RLA, AddressingMode::ZEROPAGEX),
+        OPCODE::RLA_A => ("RLA", Handler::RLA, AddressingMode::ABSOLUTE),
+        OPCODE::RLA_AX => ("RLA", Handler::RLA, AddressingMode::ABSOLUTEX),
+        OPCODE::RLA_AY => ("RLA", Handler::RLA, AddressingMode::ABSOLUTEY),
+        OPCODE::RLA_IX => ("RLA", Handler::RLA, AddressingMode::INDIRECTX),
+        OPCODE::RLA_IY => ("RLA", Handler::RLA, AddressingMode::INDIRECTY),
+        OPCODE::SRE_ZP => ("SRE", Handler::SRE, AddressingMode::ZEROPAGE),
+        OPCODE::SRE_ZPX => ("SRE", Handler::SRE, AddressingMode::ZEROPAGEX),
+        OPCODE::SRE_A => ("SRE", Handler::SRE, AddressingMode::ABSOLUTE),
+        OPCODE::SRE_AX => ("SRE", Handler::SRE, AddressingMode::ABSOLUTEX),
+        OPCODE::SRE_AY => ("SRE", Handler::SRE, AddressingMode::ABSOLUTEY),
+        OPCODE::SRE_IX => ("SRE", Handler::SRE, AddressingMode::INDIRECTX),
+        OPCODE::SRE_IY => ("SRE", Handler::SRE, AddressingMode::INDIRECTY),
+        OPCODE::RRA_ZP => ("RRA", Handler::RRA, AddressingMode::ZEROPAGE),
+        OPCODE::RRA_ZPX => ("RRA", Handler::RRA, AddressingMode::ZEROPAGEX),
+        OPCODE::RRA_A => ("RRA", Handler::RRA, AddressingMode::ABSOLUTE),
+        OPCODE::RRA_AX => ("RRA", Handler::RRA, AddressingMode::ABSOLUTEX),
+        OPCODE::RRA_AY => ("RRA", Handler::RRA, AddressingMode::ABSOLUTEY),
+        OPCODE::RRA_IX => ("RRA", Handler::RRA, AddressingMode::INDIRECTX),
+        OPCODE::RRA_IY => ("RRA", Handler::RRA, AddressingMode::INDIRECTY),
+        OPCODE::DCP_ZP => ("DCP", Handler::DCP, AddressingMode::ZEROPAGE),
+        OPCODE::DCP_ZPX => ("DCP", Handler::DCP, AddressingMode::ZEROPAGEX),
+        OPCODE::DCP_A => ("DCP", Handler::DCP, AddressingMode::ABSOLUTE),
+        OPCODE::DCP_AX => ("DCP", Handler::DCP, AddressingMode::ABSOLUTEX),
+        OPCODE::DCP_AY => ("DCP", Handler::DCP, AddressingMode::ABSOLUTEY),
+        OPCODE::DCP_IX => ("DCP", Handler::DCP, AddressingMode::INDIRECTX),
+        OPCODE::DCP_IY => ("DCP", Handler::DCP, AddressingMode::INDIRECTY),
+        OPCODE::ISC_ZP => ("ISC", Handler::ISC, AddressingMode::ZEROPAGE),
+        OPCODE::ISC_ZPX => ("ISC", Handler::ISC, AddressingMode::ZEROPAGEX),
+        OPCODE::ISC_A => ("ISC", Handler::ISC, AddressingMode::ABSOLUTE),
+        OPCODE::ISC_AX => ("ISC", Handler::ISC, AddressingMode::ABSOLUTEX),
+        OPCODE::ISC_AY => ("ISC", Handler::ISC, AddressingMode::ABSOLUTEY),
+        OPCODE::ISC_IX => ("ISC", Handler::ISC, AddressingMode::INDIRECTX),
+        OPCODE::ISC_IY => ("ISC", Handler::ISC, AddressingMode::INDIRECTY),
+        OPCODE::SAX_ZP => ("SAX", Handler::SAX, AddressingMode::ZEROPAGE),
+        OPCODE::SAX_ZPY => ("SAX", Handler::SAX, AddressingMode::ZEROPAGEY),
+        OPCODE::SAX_A => ("SAX", Handler::SAX, AddressingMode::ABSOLUTE),
+        OPCODE::SAX_IX => ("SAX", Handler::SAX, AddressingMode::INDIRECTX),
+        OPCODE::LAX_ZP => ("LAX", Handler::LAX, AddressingMode::ZEROPAGE),
+        OPCODE::LAX_ZPY => ("LAX", Handler::LAX, AddressingMode::ZEROPAGEY),
+        OPCODE::LAX_A => ("LAX", Handler::LAX, AddressingMode::ABSOLUTE),
+        OPCODE::LAX_AY => ("LAX", Handler::LAX, AddressingMode::ABSOLUTEY),
+        OPCODE::LAX_IX => ("LAX", Handler::LAX, AddressingMode::INDIRECTX),
+        OPCODE::LAX_IY => ("LAX", Handler::LAX, AddressingMode::INDIRECTY),
+        OPCODE::ANC_I => ("ANC", Handler::ANC, AddressingMode::IMMEDIATE),
+        OPCODE::ANC_I_2B => ("ANC", Handler::ANC, AddressingMode::IMMEDIATE),
+        OPCODE::ALR_I => ("ALR", Handler::ALR, AddressingMode::IMMEDIATE),
+        OPCODE::ARR_I => ("ARR", Handler::ARR, AddressingMode::IMMEDIATE),
+        OPCODE::SBX_I => ("SBX", Handler::SBX, AddressingMode::IMMEDIATE),
+        OPCODE::SBC_I_EB => ("SBC", Handler::SBC, AddressingMode::IMMEDIATE),
+        OPCODE::LAS_AY => ("LAS", Handler::LAS, AddressingMode::ABSOLUTEY),
+        OPCODE::ANE_I => ("ANE", Handler::ANE, AddressingMode::IMMEDIATE),
+        OPCODE::LXA_I => ("LXA", Handler::LXA, AddressingMode::IMMEDIATE),
+        OPCODE::SHA_AY => ("SHA", Handler::SHA, AddressingMode::ABSOLUTEY),
+        OPCODE::SHA_IY => ("SHA", Handler::SHA, AddressingMode::INDIRECTY),
+        OPCODE::SHX_AY => ("SHX", Handler::SHX, AddressingMode::ABSOLUTEY),
+        OPCODE::SHY_AX => ("SHY", Handler::SHY, AddressingMode::ABSOLUTEX),
+        OPCODE::TAS_AY => ("TAS", Handler::TAS, AddressingMode::ABSOLUTEY),
+        OPCODE::NOP_1A => ("NOP", Handler::NOP, AddressingMode::IMPLIED),
+        OPCODE::NOP_3A => ("NOP", Handler::NOP, AddressingMode::IMPLIED),
+        OPCODE::NOP_5A => ("NOP", Handler::NOP, AddressingMode::IMPLIED),
+        OPCODE::NOP_7A => ("NOP", Handler::NOP, AddressingMode::IMPLIED),
+        OPCODE::NOP_DA => ("NOP", Handler::NOP, AddressingMode::IMPLIED),
+        OPCODE::NOP_FA => ("NOP", Handler::NOP, AddressingMode::IMPLIED),
+        OPCODE::NOP_I_80 => ("NOP", Handler::NOP, AddressingMode::IMMEDIATE),
+        OPCODE::NOP_I_82 => ("NOP", Handler::NOP, AddressingMode::IMMEDIATE),
+        OPCODE::NOP_I_89 => ("NOP", Handler::NOP, AddressingMode::IMMEDIATE),
+        OPCODE::NOP_I_C2 => ("NOP", Handler::NOP, AddressingMode::IMMEDIATE),
+        OPCODE::NOP_I_E2 => ("NOP", Handler::NOP, AddressingMode::IMMEDIATE),
+        OPCODE::NOP_ZP_04 => ("NOP", Handler::NOP, AddressingMode::ZEROPAGE),
+        OPCODE::NOP_ZP_44 => ("NOP", Handler::NOP, AddressingMode::ZEROPAGE),
+        OPCODE::NOP_ZP_64 => ("NOP", Handler::NOP, AddressingMode::ZEROPAGE),
+        OPCODE::NOP_ZPX_14 => ("NOP", Handler::NOP, AddressingMode::ZEROPAGEX),
+        OPCODE::NOP_ZPX_34 => ("NOP", Handler::NOP, AddressingMode::ZEROPAGEX),
+        OPCODE::NOP_ZPX_54 => ("NOP", Handler::NOP, AddressingMode::ZEROPAGEX),
+        OPCODE::NOP_ZPX_74 => ("NOP", Handler::NOP, AddressingMode::ZEROPAGEX),
+        OPCODE::NOP_ZPX_D4 => ("NOP", Handler::NOP, AddressingMode::ZEROPAGEX),
+        OPCODE::NOP_ZPX_F4 => ("NOP", Handler::NOP, AddressingMode::ZEROPAGEX),
+        OPCODE::NOP_A_0C => ("NOP", Handler::NOP, AddressingMode::ABSOLUTE),
+        OPCODE::NOP_AX_1C => ("NOP", Handler::NOP, AddressingMode::ABSOLUTEX),
+        OPCODE::NOP_AX_3C => ("NOP", Handler::NOP, AddressingMode::ABSOLUTEX),
+        OPCODE::NOP_AX_5C => ("NOP", Handler::NOP, AddressingMode::ABSOLUTEX),
+        OPCODE::NOP_AX_7C => ("NOP", Handler::NOP, AddressingMode::ABSOLUTEX),
+        OPCODE::NOP_AX_DC => ("NOP", Handler::NOP, AddressingMode::ABSOLUTEX),
+        OPCODE::NOP_AX_FC => ("NOP", Handler::NOP, AddressingMode::ABSOLUTEX),
+        OPCODE::JAM_02 => ("JAM", Handler::JAM, AddressingMode::IMPLIED),
+        OPCODE::JAM_12 => ("JAM", Handler::JAM, AddressingMode::IMPLIED),
+        OPCODE::JAM_22 => ("JAM", Handler::JAM, AddressingMode::IMPLIED),
+        OPCODE::JAM_32 => ("JAM", Handler::JAM, AddressingMode::IMPLIED),
+        OPCODE::JAM_42 => ("JAM", Handler::JAM, AddressingMode::IMPLIED),
+        OPCODE::JAM_52 => ("JAM", Handler::JAM, AddressingMode::IMPLIED),
+        OPCODE::JAM_62 => ("JAM", Handler::JAM, AddressingMode::IMPLIED),
+        OPCODE::JAM_72 => ("JAM", Handler::JAM, AddressingMode::IMPLIED),
+        OPCODE::JAM_92 => ("JAM", Handler::JAM, AddressingMode::IMPLIED),
+        OPCODE::JAM_B2 => ("JAM", Handler::JAM, AddressingMode::IMPLIED),
+        OPCODE::JAM_D2 => ("JAM", Handler::JAM, AddressingMode::IMPLIED),
+        OPCODE::JAM_F2 => ("JAM", Handler::JAM, AddressingMode::IMPLIED),
+        OPCODE::ORA_IZP => ("ORA", Handler::ORA, AddressingMode::INDIRECTZP),
+        OPCODE::AND_IZP => ("AND", Handler::AND, AddressingMode::INDIRECTZP),
+        OPCODE::EOR_IZP => ("EOR", Handler::EOR, AddressingMode::INDIRECTZP),
+        OPCODE::ADC_IZP => ("ADC", Handler::ADC, AddressingMode::INDIRECTZP),
+        OPCODE::STA_IZP => ("STA", Handler::STA, AddressingMode::INDIRECTZP),
+        OPCODE::LDA_IZP => ("LDA", Handler::LDA, AddressingMode::INDIRECTZP),
+        OPCODE::CMP_IZP => ("CMP", Handler::CMP, AddressingMode::INDIRECTZP),
+        OPCODE::SBC_IZP => ("SBC", Handler::SBC, AddressingMode::INDIRECTZP),
+        OPCODE::BIT_I => ("BIT", Handler::BIT, AddressingMode::IMMEDIATE),
+        OPCODE::BIT_ZPX => ("BIT", Handler::BIT, AddressingMode::ZEROPAGEX),
+        OPCODE::BIT_AX => ("BIT", Handler::BIT, AddressingMode::ABSOLUTEX),
+        OPCODE::INC_ACC => ("INC", Handler::INC, AddressingMode::ACCUMULATOR),
+        OPCODE::DEC_ACC => ("DEC", Handler::DEC, AddressingMode::ACCUMULATOR),
+        OPCODE::JMP_IAX => ("JMP", Handler::JMP, AddressingMode::INDIRECTABSX),
+        OPCODE::BRA => ("BRA", Handler::BRA, AddressingMode::RELATIVE),
+        OPCODE::PHX => ("PHX", Handler::PHX, AddressingMode::IMPLIED),
+        OPCODE::PLX => ("PLX", Handler::PLX, AddressingMode::IMPLIED),
+        OPCODE::PHY => ("PHY", Handler::PHY, AddressingMode::IMPLIED),
+        OPCODE::PLY => ("PLY", Handler::PLY, AddressingMode::IMPLIED),
+        OPCODE::STZ_ZP => ("STZ", Handler::STZ, AddressingMode::ZEROPAGE),
+        OPCODE::STZ_ZPX => ("STZ", Handler::STZ, AddressingMode::ZEROPAGEX),
+        OPCODE::STZ_A => ("STZ", Handler::STZ, AddressingMode::ABSOLUTE),
+        OPCODE::STZ_AX => ("STZ", Handler::STZ, AddressingMode::ABSOLUTEX),
+        OPCODE::TRB_ZP => ("TRB", Handler::TRB, AddressingMode::ZEROPAGE),
+        OPCODE::TRB_A => ("TRB", Handler::TRB, AddressingMode::ABSOLUTE),
+        OPCODE::TSB_ZP => ("TSB", Handler::TSB, AddressingMode::ZEROPAGE),
+        OPCODE::TSB_A => ("TSB", Handler::TSB, AddressingMode::ABSOLUTE),
+        OPCODE::RMB0 => ("RMB0", Handler::RMB, AddressingMode::ZEROPAGE),
+        OPCODE::RMB1 => ("RMB1", Handler::RMB, AddressingMode::ZEROPAGE),
+        OPCODE::RMB2 => ("RMB2", Handler::RMB, AddressingMode::ZEROPAGE),
+        OPCODE::RMB3 => ("RMB3", Handler::RMB, AddressingMode::ZEROPAGE),
+        OPCODE::RMB4 => ("RMB4", Handler::RMB, AddressingMode::ZEROPAGE),
+        OPCODE::RMB5 => ("RMB5", Handler::RMB, AddressingMode::ZEROPAGE),
+        OPCODE::RMB6 => ("RMB6", Handler::RMB, AddressingMode::ZEROPAGE),
+        OPCODE::RMB7 => ("RMB7", Handler::RMB, AddressingMode::ZEROPAGE),
+        OPCODE::SMB0 => ("SMB0", Handler::SMB, AddressingMode::ZEROPAGE),
+        OPCODE::SMB1 => ("SMB1", Handler::SMB, AddressingMode::ZEROPAGE),
+        OPCODE::SMB2 => ("SMB2", Handler::SMB, AddressingMode::ZEROPAGE),
+        OPCODE::SMB3 => ("SMB3", Handler::SMB, AddressingMode::ZEROPAGE),
+        OPCODE::SMB4 => ("SMB4", Handler::SMB, AddressingMode::ZEROPAGE),
+        OPCODE::SMB5 => ("SMB5", Handler::SMB, AddressingMode::ZEROPAGE),
+        OPCODE::SMB6 => ("SMB6", Handler::SMB, AddressingMode::ZEROPAGE),
+        OPCODE::SMB7 => ("SMB7", Handler::SMB, AddressingMode::ZEROPAGE),
+        OPCODE::BBR0 => ("BBR0", Handler::BBR, AddressingMode::ZEROPAGE),
+        OPCODE::BBR1 => ("BBR1", Handler::BBR, AddressingMode::ZEROPAGE),
+        OPCODE::BBR2 => ("BBR2", Handler::BBR, AddressingMode::ZEROPAGE),
+        OPCODE::BBR3 => ("BBR3", Handler::BBR, AddressingMode::ZEROPAGE),
+        OPCODE::BBR4 => ("BBR4", Handler::BBR, AddressingMode::ZEROPAGE),
+        OPCODE::BBR5 => ("BBR5", Handler::BBR, AddressingMode::ZEROPAGE),
+        OPCODE::BBR6 => ("BBR6", Handler::BBR, AddressingMode::ZEROPAGE),
+        OPCODE::BBR7 => ("BBR7", Handler::BBR, AddressingMode::ZEROPAGE),
+        OPCODE::BBS0 => ("BBS0", Handler::BBS, AddressingMode::ZEROPAGE),
+        OPCODE::BBS1 => ("BBS1", Handler::BBS, AddressingMode::ZEROPAGE),
+        OPCODE::BBS2 => ("BBS2", Handler::BBS, AddressingMode::ZEROPAGE),
+        OPCODE::BBS3 => ("BBS3", Handler::BBS, AddressingMode::ZEROPAGE),
+        OPCODE::BBS4 => ("BBS4", Handler::BBS, AddressingMode::ZEROPAGE),
+        OPCODE::BBS5 => ("BBS5", Handler::BBS, AddressingMode::ZEROPAGE),
+        OPCODE::BBS6 => ("BBS6", Handler::BBS, AddressingMode::ZEROPAGE),
+        OPCODE::BBS7 => ("BBS7", Handler::BBS, AddressingMode::ZEROPAGE),
+        OPCODE::WAI => ("WAI", Handler::WAI, AddressingMode::IMPLIED),
+        OPCODE::STP => ("STP", Handler::STP, AddressingMode::IMPLIED),
+        OPCODE::NOP_C_03 => ("NOP", Handler::NOP, AddressingMode::IMPLIED),
+        OPCODE::NOP_C_07 => ("NOP", Handler::NOP, AddressingMode::IMPLIED),
+        OPCODE::NOP_C_0B => ("NOP", Handler::NOP, AddressingMode::IMPLIED),
+        OPCODE::NOP_C_0F => ("NOP", Handler::NOP, AddressingMode::IMPLIED),
+        OPCODE::NOP_C_13 => ("NOP", Handler::NOP, AddressingMode::IMPLIED),
+        OPCODE::NOP_C_17 => ("NOP", Handler::NOP, AddressingMode::IMPLIED),
+        OPCODE::NOP_C_1B => ("NOP", Handler::NOP, AddressingMode::IMPLIED),
+        OPCODE::NOP_C_1F => ("NOP", Handler::NOP, AddressingMode::IMPLIED),
+        OPCODE::NOP_C_23 => ("NOP", Handler::NOP, AddressingMode::IMPLIED),
+        OPCODE::NOP_C_27 => ("NOP", Handler::NOP, AddressingMode::IMPLIED),
+        OPCODE::NOP_C_2B => ("NOP", Handler::NOP, AddressingMode::IMPLIED),
+        OPCODE::NOP_C_2F => ("NOP", Handler::NOP, AddressingMode::IMPLIED),
+        OPCODE::NOP_C_33 => ("NOP", Handler::NOP, AddressingMode::IMPLIED),
+        OPCODE::NOP_C_37 => ("NOP", Handler::NOP, AddressingMode::IMPLIED),
+        OPCODE::NOP_C_3B => ("NOP", Handler::NOP, AddressingMode::IMPLIED),
+        OPCODE::NOP_C_3F => ("NOP", Handler::NOP, AddressingMode::IMPLIED),
+        OPCODE::NOP_C_43 => ("NOP", Handler::NOP, AddressingMode::IMPLIED),
+        OPCODE::NOP_C_47 => ("NOP", Handler::NOP, AddressingMode::IMPLIED),
+        OPCODE::NOP_C_4B => ("NOP", Handler::NOP, AddressingMode::IMPLIED),
+        OPCODE::NOP_C_4F => ("NOP", Handler::NOP, AddressingMode::IMPLIED),
+        OPCODE::NOP_C_53 => ("NOP", Handler::NOP, AddressingMode::IMPLIED),
+        OPCODE::NOP_C_57 => ("NOP", Handler::NOP, AddressingMode::IMPLIED),
+        OPCODE::NOP_C_5B => ("NOP", Handler::NOP, AddressingMode::IMPLIED),
+        OPCODE::NOP_C_5F => ("NOP", Handler::NOP, AddressingMode::IMPLIED),
+        OPCODE::NOP_C_63 => ("NOP", Handler::NOP, AddressingMode::IMPLIED),
+        OPCODE::NOP_C_67 => ("NOP", Handler::NOP, AddressingMode::IMPLIED),
+        OPCODE::NOP_C_6B => ("NOP", Handler::NOP, AddressingMode::IMPLIED),
+        OPCODE::NOP_C_6F => ("NOP", Handler::NOP, AddressingMode::IMPLIED),
+        OPCODE::NOP_C_73 => ("NOP", Handler::NOP, AddressingMode::IMPLIED),
+        OPCODE::NOP_C_77 => ("NOP", Handler::NOP, AddressingMode::IMPLIED),
+        OPCODE::NOP_C_7B => ("NOP", Handler::NOP, AddressingMode::IMPLIED),
+        OPCODE::NOP_C_7F => ("NOP", Handler::NOP, AddressingMode::IMPLIED),
+        OPCODE::NOP_C_83 => ("NOP", Handler::NOP, AddressingMode::IMPLIED),
+        OPCODE::NOP_C_87 => ("NOP", Handler::NOP, AddressingMode::IMPLIED),
+        OPCODE::NOP_C_8B => ("NOP", Handler::NOP, AddressingMode::IMPLIED),
+        OPCODE::NOP_C_8F => ("NOP", Handler::NOP, AddressingMode::IMPLIED),
+        OPCODE::NOP_C_93 => ("NOP", Handler::NOP, AddressingMode::IMPLIED),
+        OPCODE::NOP_C_97 => ("NOP", Handler::NOP, AddressingMode::IMPLIED),
+        OPCODE::NOP_C_9B => ("NOP", Handler::NOP, AddressingMode::IMPLIED),
+        OPCODE::NOP_C_9F => ("NOP", Handler::NOP, AddressingMode::IMPLIED),
+        OPCODE::NOP_C_A3 => ("NOP", Handler::NOP, AddressingMode::IMPLIED),
+        OPCODE::NOP_C_A7 => ("NOP", Handler::NOP, AddressingMode::IMPLIED),
+        OPCODE::NOP_C_AB => ("NOP", Handler::NOP, AddressingMode::IMPLIED),
+        OPCODE::NOP_C_AF => ("NOP", Handler::NOP, AddressingMode::IMPLIED),
+        OPCODE::NOP_C_B3 => ("NOP", Handler::NOP, AddressingMode::IMPLIED),
+        OPCODE::NOP_C_B7 => ("NOP", Handler::NOP, AddressingMode::IMPLIED),
+        OPCODE::NOP_C_BB => ("NOP", Handler::NOP, AddressingMode::IMPLIED),
+        OPCODE::NOP_C_BF => ("NOP", Handler::NOP, AddressingMode::IMPLIED),
+        OPCODE::NOP_C_C3 => ("NOP", Handler::NOP, AddressingMode::IMPLIED),
+        OPCODE::NOP_C_C7 => ("NOP", Handler::NOP, AddressingMode::IMPLIED),
+        OPCODE::NOP_C_CB => ("NOP", Handler::NOP, AddressingMode::IMPLIED),
+        OPCODE::NOP_C_CF => ("NOP", Handler::NOP, AddressingMode::IMPLIED),
+        OPCODE::NOP_C_D3 => ("NOP", Handler::NOP, AddressingMode::IMPLIED),
+        OPCODE::NOP_C_D7 => ("NOP", Handler::NOP, AddressingMode::IMPLIED),
+        OPCODE::NOP_C_DB => ("NOP", Handler::NOP, AddressingMode::IMPLIED),
+        OPCODE::NOP_C_DF => ("NOP", Handler::NOP, AddressingMode::IMPLIED),
+        OPCODE::NOP_C_E3 => ("NOP", Handler::NOP, AddressingMode::IMPLIED),
+        OPCODE::NOP_C_E7 => ("NOP", Handler::NOP, AddressingMode::IMPLIED),
+        OPCODE::NOP_C_EB => ("NOP", Handler::NOP, AddressingMode::IMPLIED),
+        OPCODE::NOP_C_EF => ("NOP", Handler::NOP, AddressingMode::IMPLIED),
+        OPCODE::NOP_C_F3 => ("NOP", Handler::NOP, AddressingMode::IMPLIED),
+        OPCODE::NOP_C_F7 => ("NOP", Handler::NOP, AddressingMode::IMPLIED),
+        OPCODE::NOP_C_FB => ("NOP", Handler::NOP, AddressingMode::IMPLIED),
+        OPCODE::NOP_C_FF => ("NOP", Handler::NOP, AddressingMode::IMPLIED),
+        OPCODE::NOP_C_I_02 => ("NOP", Handler::NOP, AddressingMode::IMMEDIATE),
+        OPCODE::NOP_C_I_22 => ("NOP", Handler::NOP, AddressingMode::IMMEDIATE),
+        OPCODE::NOP_C_I_42 => ("NOP", Handler::NOP, AddressingMode::IMMEDIATE),
+        OPCODE::NOP_C_I_62 => ("NOP", Handler::NOP, AddressingMode::IMMEDIATE),
+        OPCODE::NOP_C_A_5C => ("NOP", Handler::NOP, AddressingMode::ABSOLUTE),
+        OPCODE::NOP_C_A_DC => ("NOP", Handler::NOP, AddressingMode::ABSOLUTE),
+        OPCODE::NOP_C_A_FC => ("NOP", Handler::NOP, AddressingMode::ABSOLUTE),
+    }
+}
@@ -0,0 +1,203 @@
+use std::fmt;
+
+/* reasons for the cpu to stop executing, the state of the cpu is left as it was when the error occurred */
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum CpuError {
+    /* undocumented opcode fetched while trapping on them is enabled */
+    IllegalOpcode { opcode: u8, addr: u16 },
+    /* JAM (also known as KIL) locked up the cpu, only a reset gets it running again */
+    Jammed { opcode: u8, addr: u16 },
+    /* the bus could not serve an access to the address */
+    BusFault { addr: u16 },
+}
+
+impl fmt::Display for CpuError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CpuError::IllegalOpcode { opcode, addr } => {
+                write!(f, "illegal opcode ${:02X} at ${:04X}", opcode, addr)
+            }
+            CpuError::Jammed { opcode, addr } => {
+                write!(f, "cpu jammed by opcode ${:02X} at ${:04X}", opcode, addr)
+            }
+            CpuError::BusFault { addr } => write!(f, "bus fault at ${:04X}", addr),
+        }
+    }
+}
+
+impl std::error::Error for CpuError {}
+
+/* Instruction::new got a parameter list that does not fit the opcode */
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct InstructionError {
+    pub opcode: u8,
+    pub expected: u16,
+    pub got: usize,
+}
+
+impl fmt::Display for InstructionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "instruction with opcode ${:02X} expects {} params, got {}",
+            self.opcode, self.expected, self.got
+        )
+    }
+}
+
+impl std::error::Error for InstructionError {}
+
+/* a snapshot that does not fit the state it is restored into */
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct SnapshotError {
+    pub expected: usize,
+    pub got: usize,
+}
+
+impl fmt::Display for SnapshotError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "snapshot of {} bytes does not fit, expected {} bytes",
+            self.got, self.expected
+        )
+    }
+}
+
+impl std::error::Error for SnapshotError {}
+
+/* a program file that cannot be loaded */
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum LoadError {
+    /* a line of an Intel HEX or S-record file that is not a valid record */
+    Malformed { line: usize },
+    Checksum { line: usize, expected: u8, got: u8 },
+    /* a binary file that ends in the middle of its header or a segment */
+    Truncated,
+    BadHeader,
+    Unsupported { feature: &'static str },
+    /* a segment that does not fit below $10000 */
+    OutOfRange { addr: u32, len: usize },
+    /* the start of a segment that overlaps the one before it */
+    Overlap { addr: u16 },
+}
+
+impl fmt::Display for LoadError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LoadError::Malformed { line } => write!(f, "malformed record in line {}", line),
+            LoadError::Checksum {
+                line,
+                expected,
+                got,
+            } => write!(
+                f,
+                "checksum mismatch in line {}: expected ${:02X}, got ${:02X}",
+                line, expected, got
+            ),
+            LoadError::Truncated => write!(f, "file ends unexpectedly"),
+            LoadError::BadHeader => write!(f, "unknown file header"),
+            LoadError::Unsupported { feature } => write!(f, "unsupported {}", feature),
+            LoadError::OutOfRange { addr, len } => write!(
+                f,
+                "segment of {} bytes at ${:X} does not fit into the address space",
+                len, addr
+            ),
+            LoadError::Overlap { addr } => {
+                write!(f, "segment at ${:04X} overlaps another one", addr)
+            }
+        }
+    }
+}
+
+impl std::error::Error for LoadError {}
+
+/* what is wrong with a line of assembler source */
+#[derive(Clone, Debug, PartialEq)]
+pub enum AsmErrorKind {
+    Syntax(&'static str),
+    UnknownMnemonic(String),
+    UnknownDirective(String),
+    /* the instruction does not have the addressing mode the operand is written in */
+    AddressingMode,
+    UndefinedSymbol(String),
+    DuplicateSymbol(String),
+    ValueOutOfRange(i64),
+    /* the distance to the branch target */
+    BranchOutOfRange(i64),
+    DivisionByZero,
+    /* the address was already assembled to */
+    Overlap(u16),
+    Load(LoadError),
+}
+
+/* an assembler diagnostic, line and column start at 1 */
+#[derive(Clone, Debug, PartialEq)]
+pub struct AsmError {
+    pub line: usize,
+    pub column: usize,
+    pub kind: AsmErrorKind,
+}
+
+impl fmt::Display for AsmError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:{}: ", self.line, self.column)?;
+        match &self.kind {
+            AsmErrorKind::Syntax(reason) => write!(f, "{}", reason),
+            AsmErrorKind::UnknownMnemonic(name) => write!(f, "unknown instruction {}", name),
+            AsmErrorKind::UnknownDirective(name) => write!(f, "unknown directive .{}", name),
+            AsmErrorKind::AddressingMode => {
+                write!(f, "addressing mode not supported by the instruction")
+            }
+            AsmErrorKind::UndefinedSymbol(name) => write!(f, "undefined symbol {}", name),
+            AsmErrorKind::DuplicateSymbol(name) => write!(f, "symbol {} is already defined", name),
+            AsmErrorKind::ValueOutOfRange(value) => write!(f, "value {} is out of range", value),
+            AsmErrorKind::BranchOutOfRange(offset) => {
+                write!(f, "branch target is {} bytes away", offset)
+            }
+            AsmErrorKind::DivisionByZero => write!(f, "division by zero"),
+            AsmErrorKind::Overlap(addr) => write!(f, "${:04X} is already assembled to", addr),
+            AsmErrorKind::Load(error) => write!(f, "{}", error),
+        }
+    }
+}
+
+impl std::error::Error for AsmError {}
+
+/* a save state that cannot be loaded */
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum SaveStateError {
+    BadMagic,
+    UnsupportedVersion(u16),
+    Truncated,
+    /* a field with a value no version of the format writes */
+    Invalid(&'static str),
+    /* the memory does not have the mapper or the devices the state was saved with */
+    Mismatch(&'static str),
+    Snapshot(SnapshotError),
+}
+
+impl fmt::Display for SaveStateError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SaveStateError::BadMagic => write!(f, "not a save state"),
+            SaveStateError::UnsupportedVersion(version) => {
+                write!(f, "save state version {} is not supported", version)
+            }
+            SaveStateError::Truncated => write!(f, "save state ends too early"),
+            SaveStateError::Invalid(field) => write!(f, "invalid {} in the save state", field),
+            SaveStateError::Mismatch(part) => {
+                write!(f, "{} of the save state does not match the memory", part)
+            }
+            SaveStateError::Snapshot(error) => write!(f, "{}", error),
+        }
+    }
+}
+
+impl std::error::Error for SaveStateError {}
+
+impl From<SnapshotError> for SaveStateError {
+    fn from(error: SnapshotError) -> SaveStateError {
+        SaveStateError::Snapshot(error)
+    }
+}
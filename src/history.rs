@@ -0,0 +1,178 @@
+use std::collections::VecDeque;
+
+use crate::cpu::{CpuState, CPU};
+use crate::memory::{Memory, UndoWrite};
+use crate::run::{RunLimits, RunSummary, StopReason};
+
+/* what is needed to take back one instruction or interrupt sequence */
+struct UndoRecord {
+    cpu: CpuState,
+    writes: Vec<UndoWrite>,
+    /* the bank state in front of the step, None if the mapper took no write */
+    mapper: Option<Vec<u8>>,
+}
+
+/*
+    Undo records of the last capacity steps of a CPU and its Memory, the oldest is dropped when it is full.
+    The registers, the memory written through write_byte and the bank state of the mapper are rewound,
+    devices keep what they have. The bank state is snapshotted when a run starts and after
+    every step the mapper took a write in.
+    Only the steps taken through the History are recorded. If the cpu is moved in between
+    (CPU::run, a reset, a loaded save state) the history no longer fits and is cleared,
+    memory written in between with the cpu left alone cannot be noticed and is not undone.
+*/
+pub struct History {
+    records: VecDeque<UndoRecord>,
+    capacity: usize,
+    /* where the last recorded step left the cpu */
+    end: Option<CpuState>,
+}
+
+impl History {
+    pub fn new(capacity: usize) -> History {
+        History {
+            records: VecDeque::with_capacity(capacity),
+            capacity,
+            end: None,
+        }
+    }
+    /* the number of steps that can be taken back */
+    pub fn len(&self) -> usize {
+        self.records.len()
+    }
+    pub fn is_empty(&self) -> bool {
+        self.records.is_empty()
+    }
+    pub fn clear(&mut self) {
+        self.records.clear();
+        self.end = None;
+    }
+
+    /* drops the records if the cpu is not where the last recorded step left it */
+    fn check_position(&mut self, cpu: &CPU) {
+        if self.end.is_some_and(|end| end != cpu.state()) {
+            self.clear();
+        }
+    }
+
+    fn push(&mut self, record: UndoRecord) {
+        if self.capacity == 0 {
+            return;
+        }
+        if self.records.len() == self.capacity {
+            self.records.pop_front();
+        }
+        self.records.push_back(record);
+    }
+
+    /* CPU::run that records every step it takes, Debugger::run_recorded does the same for a debugger */
+    pub fn run(&mut self, cpu: &mut CPU, memory: &mut Memory, limits: &RunLimits) -> RunSummary {
+        self.record(cpu, memory, limits, |cpu, memory, limits| {
+            cpu.run_until(memory, limits, |_| true)
+        })
+    }
+
+    /*
+        Runs with step until the limits or another stop, recording every step.
+        step runs the cpu for at most one instruction or interrupt sequence and reports Predicate
+        when it did not stop for another reason.
+        The journal of the memory is handed back the way it was found, with the writes of the run
+        appended if it was recording.
+    */
+    pub(crate) fn record(
+        &mut self,
+        cpu: &mut CPU,
+        memory: &mut Memory,
+        limits: &RunLimits,
+        mut step: impl FnMut(&mut CPU, &mut Memory, &RunLimits) -> RunSummary,
+    ) -> RunSummary {
+        self.check_position(cpu);
+        let mut outer = memory.replace_journal(Some(Vec::new()));
+        let mut mapper = memory.mapper().map(|mapper| mapper.snapshot());
+        let mut total = RunSummary {
+            reason: StopReason::Predicate,
+            instructions: 0,
+            cycles: 0,
+        };
+        loop {
+            let step_limits = RunLimits {
+                cycles: limits
+                    .cycles
+                    .map(|cycles| cycles.saturating_sub(total.cycles)),
+                instructions: limits
+                    .instructions
+                    .map(|count| count.saturating_sub(total.instructions)),
+                ..*limits
+            };
+            let before = cpu.state();
+            let mapper_writes = memory.mapper_writes();
+            let summary = step(cpu, memory, &step_limits);
+            let writes = memory.take_journal();
+            if let Some(outer) = &mut outer {
+                outer.extend_from_slice(&writes);
+            }
+            let banks = if memory.mapper_writes() != mapper_writes {
+                let after = memory.mapper().map(|mapper| mapper.snapshot());
+                std::mem::replace(&mut mapper, after)
+            } else {
+                None
+            };
+            if cpu.state() != before || !writes.is_empty() || banks.is_some() {
+                self.push(UndoRecord {
+                    cpu: before,
+                    writes,
+                    mapper: banks,
+                });
+            }
+            total.instructions += summary.instructions;
+            total.cycles += summary.cycles;
+            if summary.reason != StopReason::Predicate {
+                total.reason = summary.reason;
+                break;
+            }
+        }
+        memory.replace_journal(outer);
+        self.end = Some(cpu.state());
+        total
+    }
+
+    /* one instruction forward, an interrupt sequence in front of it is a step of its own */
+    pub fn step(&mut self, cpu: &mut CPU, memory: &mut Memory) -> RunSummary {
+        self.run(cpu, memory, &RunLimits::new().instructions(1))
+    }
+
+    /* takes back up to count steps, returns how many there were to take back */
+    pub fn step_back(&mut self, cpu: &mut CPU, memory: &mut Memory, count: usize) -> usize {
+        self.check_position(cpu);
+        let mut undone = 0;
+        while undone < count {
+            match self.records.pop_back() {
+                Some(record) => {
+                    memory.undo(&record.writes);
+                    /* the snapshot came from this mapper, it is only refused if the mapper was swapped */
+                    if let (Some(snapshot), Some(mapper)) = (&record.mapper, memory.mapper_mut()) {
+                        let _ = mapper.restore(snapshot);
+                    }
+                    cpu.restore_state(&record.cpu);
+                    self.end = Some(record.cpu);
+                    undone += 1;
+                }
+                None => break,
+            }
+        }
+        undone
+    }
+
+    /*
+        Takes back steps until the cycle counter is at or before cycle, which leaves the cpu at the
+        last instruction boundary not after it. False if the history does not reach back that far.
+    */
+    pub fn rewind_to_cycle(&mut self, cpu: &mut CPU, memory: &mut Memory, cycle: u64) -> bool {
+        while cpu.clock_cycles_elapsed() > cycle {
+            if self.step_back(cpu, memory, 1) == 0 {
+                return false;
+            }
+        }
+        true
+    }
+}
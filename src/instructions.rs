@@ -1,9 +1,16 @@
 use std::convert::From;
 use num_enum::IntoPrimitive;
-
+use crate::cpu::Variant;
+use crate::error::InstructionError;
+
+/*
+    The low byte of every opcode is its value in memory (OPCODE::X as u8).
+    65C02 opcodes reuse bytes of undocumented NMOS opcodes, they are kept apart by the high byte:
+    0x1XX for the 65C02 instructions and 0x2XX for the NOPs the 65C02 executes for reserved opcodes.
+*/
 #[allow(non_camel_case_types)]
 #[derive(Copy, Clone, IntoPrimitive, Debug, PartialEq)]
-#[repr(u8)]
+#[repr(u16)]
 pub enum OPCODE {
     STA_ZP = 0x85,
     STA_ZPX = 0x95,
@@ -59,10 +66,7 @@ pub enum OPCODE {
     CPY_A = 0xCC,
     CPY_ZP = 0xC4,
 
-    /*
-        TODO: ONLY non-BDC addition is implemented
-        https://www.electrical4u.com/bcd-or-binary-coded-decimal-bcd-conversion-addition-subtraction/ 
-    */
+    /* BCD arithmetic is selected by the D flag, see alu::adc */
     ADC_I = 0x69,
     ADC_ZP = 0x65,
     ADC_ZPX = 0x75,
@@ -190,7 +194,344 @@ pub enum OPCODE {
     PHA = 0x48,
     PLA = 0x68,
     PHP = 0x08,
-    PLP = 0x28
+    PLP = 0x28,
+
+    /*
+        Undocumented NMOS opcodes, see http://www.oxyron.de/html/opcodes02.html
+        The duplicates of an opcode are suffixed with their value.
+    */
+
+    /* ASL + ORA */
+    SLO_ZP = 0x07,
+    SLO_ZPX = 0x17,
+    SLO_A = 0x0F,
+    SLO_AX = 0x1F,
+    SLO_AY = 0x1B,
+    SLO_IX = 0x03,
+    SLO_IY = 0x13,
+
+    /* ROL + AND */
+    RLA_ZP = 0x27,
+    RLA_ZPX = 0x37,
+    RLA_A = 0x2F,
+    RLA_AX = 0x3F,
+    RLA_AY = 0x3B,
+    RLA_IX = 0x23,
+    RLA_IY = 0x33,
+
+    /* LSR + EOR */
+    SRE_ZP = 0x47,
+    SRE_ZPX = 0x57,
+    SRE_A = 0x4F,
+    SRE_AX = 0x5F,
+    SRE_AY = 0x5B,
+    SRE_IX = 0x43,
+    SRE_IY = 0x53,
+
+    /* ROR + ADC */
+    RRA_ZP = 0x67,
+    RRA_ZPX = 0x77,
+    RRA_A = 0x6F,
+    RRA_AX = 0x7F,
+    RRA_AY = 0x7B,
+    RRA_IX = 0x63,
+    RRA_IY = 0x73,
+
+    /* DEC + CMP */
+    DCP_ZP = 0xC7,
+    DCP_ZPX = 0xD7,
+    DCP_A = 0xCF,
+    DCP_AX = 0xDF,
+    DCP_AY = 0xDB,
+    DCP_IX = 0xC3,
+    DCP_IY = 0xD3,
+
+    /* INC + SBC */
+    ISC_ZP = 0xE7,
+    ISC_ZPX = 0xF7,
+    ISC_A = 0xEF,
+    ISC_AX = 0xFF,
+    ISC_AY = 0xFB,
+    ISC_IX = 0xE3,
+    ISC_IY = 0xF3,
+
+    /* stores A & X */
+    SAX_ZP = 0x87,
+    SAX_ZPY = 0x97,
+    SAX_A = 0x8F,
+    SAX_IX = 0x83,
+
+    /* LDA + LDX */
+    LAX_ZP = 0xA7,
+    LAX_ZPY = 0xB7,
+    LAX_A = 0xAF,
+    LAX_AY = 0xBF,
+    LAX_IX = 0xA3,
+    LAX_IY = 0xB3,
+
+    /* AND, C is copied from N */
+    ANC_I = 0x0B,
+    ANC_I_2B = 0x2B,
+    /* AND + LSR */
+    ALR_I = 0x4B,
+    /* AND + ROR with odd flags */
+    ARR_I = 0x6B,
+    /* X = A & X - M without borrow */
+    SBX_I = 0xCB,
+    /* same as SBC_I */
+    SBC_I_EB = 0xEB,
+    /* A, X, S = M & S */
+    LAS_AY = 0xBB,
+
+    /* unstable, A = (A | $EE) & X & M */
+    ANE_I = 0x8B,
+    /* unstable, A, X = (A | $EE) & M */
+    LXA_I = 0xAB,
+    /* unstable, stores A & X & (high byte of the address + 1) */
+    SHA_AY = 0x9F,
+    SHA_IY = 0x93,
+    /* unstable, stores X & (high byte of the address + 1) */
+    SHX_AY = 0x9E,
+    /* unstable, stores Y & (high byte of the address + 1) */
+    SHY_AX = 0x9C,
+    /* unstable, S = A & X, then stores S & (high byte of the address + 1) */
+    TAS_AY = 0x9B,
+
+    /* NOPs, the ones with an operand read it and ignore the value */
+    NOP_1A = 0x1A,
+    NOP_3A = 0x3A,
+    NOP_5A = 0x5A,
+    NOP_7A = 0x7A,
+    NOP_DA = 0xDA,
+    NOP_FA = 0xFA,
+    NOP_I_80 = 0x80,
+    NOP_I_82 = 0x82,
+    NOP_I_89 = 0x89,
+    NOP_I_C2 = 0xC2,
+    NOP_I_E2 = 0xE2,
+    NOP_ZP_04 = 0x04,
+    NOP_ZP_44 = 0x44,
+    NOP_ZP_64 = 0x64,
+    NOP_ZPX_14 = 0x14,
+    NOP_ZPX_34 = 0x34,
+    NOP_ZPX_54 = 0x54,
+    NOP_ZPX_74 = 0x74,
+    NOP_ZPX_D4 = 0xD4,
+    NOP_ZPX_F4 = 0xF4,
+    NOP_A_0C = 0x0C,
+    NOP_AX_1C = 0x1C,
+    NOP_AX_3C = 0x3C,
+    NOP_AX_5C = 0x5C,
+    NOP_AX_7C = 0x7C,
+    NOP_AX_DC = 0xDC,
+    NOP_AX_FC = 0xFC,
+
+    /* halts the cpu until it is reset */
+    JAM_02 = 0x02,
+    JAM_12 = 0x12,
+    JAM_22 = 0x22,
+    JAM_32 = 0x32,
+    JAM_42 = 0x42,
+    JAM_52 = 0x52,
+    JAM_62 = 0x62,
+    JAM_72 = 0x72,
+    JAM_92 = 0x92,
+    JAM_B2 = 0xB2,
+    JAM_D2 = 0xD2,
+    JAM_F2 = 0xF2,
+
+    /* 65C02 instructions, see http://www.6502.org/tutorials/65c02opcodes.html */
+
+    /* (zp) addressing */
+    ORA_IZP = 0x112,
+    AND_IZP = 0x132,
+    EOR_IZP = 0x152,
+    ADC_IZP = 0x172,
+    STA_IZP = 0x192,
+    LDA_IZP = 0x1B2,
+    CMP_IZP = 0x1D2,
+    SBC_IZP = 0x1F2,
+
+    BIT_I = 0x189,
+    BIT_ZPX = 0x134,
+    BIT_AX = 0x13C,
+
+    INC_ACC = 0x11A,
+    DEC_ACC = 0x13A,
+    JMP_IAX = 0x17C,
+
+    BRA = 0x180,
+    PHX = 0x1DA,
+    PLX = 0x1FA,
+    PHY = 0x15A,
+    PLY = 0x17A,
+
+    /* store zero */
+    STZ_ZP = 0x164,
+    STZ_ZPX = 0x174,
+    STZ_A = 0x19C,
+    STZ_AX = 0x19E,
+
+    /* test and reset / set bits, Z = (A & M) == 0 */
+    TRB_ZP = 0x114,
+    TRB_A = 0x11C,
+    TSB_ZP = 0x104,
+    TSB_A = 0x10C,
+
+    /* Rockwell and WDC: reset / set a bit of a zero page byte and branch on bit reset / set */
+    RMB0 = 0x107,
+    RMB1 = 0x117,
+    RMB2 = 0x127,
+    RMB3 = 0x137,
+    RMB4 = 0x147,
+    RMB5 = 0x157,
+    RMB6 = 0x167,
+    RMB7 = 0x177,
+
+    SMB0 = 0x187,
+    SMB1 = 0x197,
+    SMB2 = 0x1A7,
+    SMB3 = 0x1B7,
+    SMB4 = 0x1C7,
+    SMB5 = 0x1D7,
+    SMB6 = 0x1E7,
+    SMB7 = 0x1F7,
+
+    BBR0 = 0x10F,
+    BBR1 = 0x11F,
+    BBR2 = 0x12F,
+    BBR3 = 0x13F,
+    BBR4 = 0x14F,
+    BBR5 = 0x15F,
+    BBR6 = 0x16F,
+    BBR7 = 0x17F,
+
+    BBS0 = 0x18F,
+    BBS1 = 0x19F,
+    BBS2 = 0x1AF,
+    BBS3 = 0x1BF,
+    BBS4 = 0x1CF,
+    BBS5 = 0x1DF,
+    BBS6 = 0x1EF,
+    BBS7 = 0x1FF,
+
+    /* WDC: wait for an interrupt and stop the clock until a reset */
+    WAI = 0x1CB,
+    STP = 0x1DB,
+
+    /* the 65C02 executes every reserved opcode as NOP */
+    NOP_C_03 = 0x203,
+    NOP_C_07 = 0x207,
+    NOP_C_0B = 0x20B,
+    NOP_C_0F = 0x20F,
+    NOP_C_13 = 0x213,
+    NOP_C_17 = 0x217,
+    NOP_C_1B = 0x21B,
+    NOP_C_1F = 0x21F,
+    NOP_C_23 = 0x223,
+    NOP_C_27 = 0x227,
+    NOP_C_2B = 0x22B,
+    NOP_C_2F = 0x22F,
+    NOP_C_33 = 0x233,
+    NOP_C_37 = 0x237,
+    NOP_C_3B = 0x23B,
+    NOP_C_3F = 0x23F,
+    NOP_C_43 = 0x243,
+    NOP_C_47 = 0x247,
+    NOP_C_4B = 0x24B,
+    NOP_C_4F = 0x24F,
+    NOP_C_53 = 0x253,
+    NOP_C_57 = 0x257,
+    NOP_C_5B = 0x25B,
+    NOP_C_5F = 0x25F,
+    NOP_C_63 = 0x263,
+    NOP_C_67 = 0x267,
+    NOP_C_6B = 0x26B,
+    NOP_C_6F = 0x26F,
+    NOP_C_73 = 0x273,
+    NOP_C_77 = 0x277,
+    NOP_C_7B = 0x27B,
+    NOP_C_7F = 0x27F,
+    NOP_C_83 = 0x283,
+    NOP_C_87 = 0x287,
+    NOP_C_8B = 0x28B,
+    NOP_C_8F = 0x28F,
+    NOP_C_93 = 0x293,
+    NOP_C_97 = 0x297,
+    NOP_C_9B = 0x29B,
+    NOP_C_9F = 0x29F,
+    NOP_C_A3 = 0x2A3,
+    NOP_C_A7 = 0x2A7,
+    NOP_C_AB = 0x2AB,
+    NOP_C_AF = 0x2AF,
+    NOP_C_B3 = 0x2B3,
+    NOP_C_B7 = 0x2B7,
+    NOP_C_BB = 0x2BB,
+    NOP_C_BF = 0x2BF,
+    NOP_C_C3 = 0x2C3,
+    NOP_C_C7 = 0x2C7,
+    NOP_C_CB = 0x2CB,
+    NOP_C_CF = 0x2CF,
+    NOP_C_D3 = 0x2D3,
+    NOP_C_D7 = 0x2D7,
+    NOP_C_DB = 0x2DB,
+    NOP_C_DF = 0x2DF,
+    NOP_C_E3 = 0x2E3,
+    NOP_C_E7 = 0x2E7,
+    NOP_C_EB = 0x2EB,
+    NOP_C_EF = 0x2EF,
+    NOP_C_F3 = 0x2F3,
+    NOP_C_F7 = 0x2F7,
+    NOP_C_FB = 0x2FB,
+    NOP_C_FF = 0x2FF,
+    NOP_C_I_02 = 0x202,
+    NOP_C_I_22 = 0x222,
+    NOP_C_I_42 = 0x242,
+    NOP_C_I_62 = 0x262,
+    NOP_C_A_5C = 0x25C,
+    NOP_C_A_DC = 0x2DC,
+    NOP_C_A_FC = 0x2FC
+}
+
+impl OPCODE {
+    /* value of the opcode in memory */
+    pub fn byte(self) -> u8 {
+        (self as u16 & 0x00FF) as u8
+    }
+    /* opcodes that are not part of the official NMOS instruction set */
+    pub fn undocumented(&self) -> bool {
+        matches!(self,
+            OPCODE::SLO_ZP | OPCODE::SLO_ZPX | OPCODE::SLO_A | OPCODE::SLO_AX | OPCODE::SLO_AY | OPCODE::SLO_IX |
+            OPCODE::SLO_IY | OPCODE::RLA_ZP | OPCODE::RLA_ZPX | OPCODE::RLA_A | OPCODE::RLA_AX | OPCODE::RLA_AY |
+            OPCODE::RLA_IX | OPCODE::RLA_IY | OPCODE::SRE_ZP | OPCODE::SRE_ZPX | OPCODE::SRE_A | OPCODE::SRE_AX |
+            OPCODE::SRE_AY | OPCODE::SRE_IX | OPCODE::SRE_IY | OPCODE::RRA_ZP | OPCODE::RRA_ZPX | OPCODE::RRA_A |
+            OPCODE::RRA_AX | OPCODE::RRA_AY | OPCODE::RRA_IX | OPCODE::RRA_IY | OPCODE::DCP_ZP | OPCODE::DCP_ZPX |
+            OPCODE::DCP_A | OPCODE::DCP_AX | OPCODE::DCP_AY | OPCODE::DCP_IX | OPCODE::DCP_IY | OPCODE::ISC_ZP |
+            OPCODE::ISC_ZPX | OPCODE::ISC_A | OPCODE::ISC_AX | OPCODE::ISC_AY | OPCODE::ISC_IX | OPCODE::ISC_IY |
+            OPCODE::SAX_ZP | OPCODE::SAX_ZPY | OPCODE::SAX_A | OPCODE::SAX_IX | OPCODE::LAX_ZP | OPCODE::LAX_ZPY |
+            OPCODE::LAX_A | OPCODE::LAX_AY | OPCODE::LAX_IX | OPCODE::LAX_IY | OPCODE::ANC_I | OPCODE::ANC_I_2B |
+            OPCODE::ALR_I | OPCODE::ARR_I | OPCODE::SBX_I | OPCODE::SBC_I_EB | OPCODE::LAS_AY | OPCODE::ANE_I |
+            OPCODE::LXA_I | OPCODE::SHA_AY | OPCODE::SHA_IY | OPCODE::SHX_AY | OPCODE::SHY_AX | OPCODE::TAS_AY |
+            OPCODE::NOP_1A | OPCODE::NOP_3A | OPCODE::NOP_5A | OPCODE::NOP_7A | OPCODE::NOP_DA | OPCODE::NOP_FA |
+            OPCODE::NOP_I_80 | OPCODE::NOP_I_82 | OPCODE::NOP_I_89 | OPCODE::NOP_I_C2 | OPCODE::NOP_I_E2 | OPCODE::NOP_ZP_04 |
+            OPCODE::NOP_ZP_44 | OPCODE::NOP_ZP_64 | OPCODE::NOP_ZPX_14 | OPCODE::NOP_ZPX_34 | OPCODE::NOP_ZPX_54 | OPCODE::NOP_ZPX_74 |
+            OPCODE::NOP_ZPX_D4 | OPCODE::NOP_ZPX_F4 | OPCODE::NOP_A_0C | OPCODE::NOP_AX_1C | OPCODE::NOP_AX_3C | OPCODE::NOP_AX_5C |
+            OPCODE::NOP_AX_7C | OPCODE::NOP_AX_DC | OPCODE::NOP_AX_FC | OPCODE::JAM_02 | OPCODE::JAM_12 | OPCODE::JAM_22 |
+            OPCODE::JAM_32 | OPCODE::JAM_42 | OPCODE::JAM_52 | OPCODE::JAM_62 | OPCODE::JAM_72 | OPCODE::JAM_92 |
+            OPCODE::JAM_B2 | OPCODE::JAM_D2 | OPCODE::JAM_F2 |
+            OPCODE::NOP_C_03 | OPCODE::NOP_C_07 | OPCODE::NOP_C_0B | OPCODE::NOP_C_0F | OPCODE::NOP_C_13 | OPCODE::NOP_C_17 |
+            OPCODE::NOP_C_1B | OPCODE::NOP_C_1F | OPCODE::NOP_C_23 | OPCODE::NOP_C_27 | OPCODE::NOP_C_2B | OPCODE::NOP_C_2F |
+            OPCODE::NOP_C_33 | OPCODE::NOP_C_37 | OPCODE::NOP_C_3B | OPCODE::NOP_C_3F | OPCODE::NOP_C_43 | OPCODE::NOP_C_47 |
+            OPCODE::NOP_C_4B | OPCODE::NOP_C_4F | OPCODE::NOP_C_53 | OPCODE::NOP_C_57 | OPCODE::NOP_C_5B | OPCODE::NOP_C_5F |
+            OPCODE::NOP_C_63 | OPCODE::NOP_C_67 | OPCODE::NOP_C_6B | OPCODE::NOP_C_6F | OPCODE::NOP_C_73 | OPCODE::NOP_C_77 |
+            OPCODE::NOP_C_7B | OPCODE::NOP_C_7F | OPCODE::NOP_C_83 | OPCODE::NOP_C_87 | OPCODE::NOP_C_8B | OPCODE::NOP_C_8F |
+            OPCODE::NOP_C_93 | OPCODE::NOP_C_97 | OPCODE::NOP_C_9B | OPCODE::NOP_C_9F | OPCODE::NOP_C_A3 | OPCODE::NOP_C_A7 |
+            OPCODE::NOP_C_AB | OPCODE::NOP_C_AF | OPCODE::NOP_C_B3 | OPCODE::NOP_C_B7 | OPCODE::NOP_C_BB | OPCODE::NOP_C_BF |
+            OPCODE::NOP_C_C3 | OPCODE::NOP_C_C7 | OPCODE::NOP_C_CB | OPCODE::NOP_C_CF | OPCODE::NOP_C_D3 | OPCODE::NOP_C_D7 |
+            OPCODE::NOP_C_DB | OPCODE::NOP_C_DF | OPCODE::NOP_C_E3 | OPCODE::NOP_C_E7 | OPCODE::NOP_C_EB | OPCODE::NOP_C_EF |
+            OPCODE::NOP_C_F3 | OPCODE::NOP_C_F7 | OPCODE::NOP_C_FB | OPCODE::NOP_C_FF | OPCODE::NOP_C_I_02 | OPCODE::NOP_C_I_22 |
+            OPCODE::NOP_C_I_42 | OPCODE::NOP_C_I_62 | OPCODE::NOP_C_A_5C | OPCODE::NOP_C_A_DC | OPCODE::NOP_C_A_FC)
+    }
 }
 
 #[derive(Debug)]
@@ -214,30 +555,199 @@ impl Instruction {
     pub fn cycles(&self) -> &u8 {
         &self.cycles
     }
+    /* true for indexed reads, they take one extra cycle if the indexing crosses a page (stores and read-modify-write instructions always take the long path) */
+    pub fn page_cross_penalty(&self) -> bool {
+        matches!(self.opc,
+            OPCODE::LDA_AX | OPCODE::LDA_AY | OPCODE::LDA_IY |
+            OPCODE::LDX_AY | OPCODE::LDY_AX |
+            OPCODE::ADC_AX | OPCODE::ADC_AY | OPCODE::ADC_IY |
+            OPCODE::SBC_AX | OPCODE::SBC_AY | OPCODE::SBC_IY |
+            OPCODE::AND_AX | OPCODE::AND_AY | OPCODE::AND_IY |
+            OPCODE::ORA_AX | OPCODE::ORA_AY | OPCODE::ORA_IY |
+            OPCODE::EOR_AX | OPCODE::EOR_AY | OPCODE::EOR_IY |
+            OPCODE::CMP_AX | OPCODE::CMP_AY | OPCODE::CMP_IY |
+            OPCODE::LAX_AY | OPCODE::LAX_IY | OPCODE::LAS_AY |
+            OPCODE::NOP_AX_1C | OPCODE::NOP_AX_3C | OPCODE::NOP_AX_5C |
+            OPCODE::NOP_AX_7C | OPCODE::NOP_AX_DC | OPCODE::NOP_AX_FC |
+            OPCODE::BIT_AX)
+    }
     pub fn convert_to_mem_layout(&self) -> Vec<u8> {
-        let mut layout = vec![self.opc.into()];
+        let mut layout = vec![self.opc.byte()];
         for param in &self.param {
-            layout.push(param.clone());
+            layout.push(*param);
         }
         layout
     }
-    pub fn new(opcode: OPCODE, param: &Vec<u8>) -> Instruction {
+    /* decodes an opcode byte the way the given cpu variant does, every byte is an opcode since the undocumented ones decode too */
+    pub fn decode(code: u8, variant: Variant) -> Instruction {
+        let ins = Instruction::try_from(code).expect("every opcode byte decodes");
+        if variant == Variant::NMOS6502 {
+            return ins;
+        }
+        let mut ins = match cmos_opcode(code, variant) {
+            Some(opc) => Instruction::from(opc),
+            None => ins,
+        };
+        /* the 65C02 fixed the JMP ($xxFF) bug at the cost of a cycle and shortened the indexed shifts */
+        match ins.opc {
+            OPCODE::JMP_I => ins.cycles = 6,
+            OPCODE::ASL_AX | OPCODE::LSR_AX | OPCODE::ROL_AX | OPCODE::ROR_AX => ins.cycles = 6,
+            _ => {}
+        }
+        ins
+    }
+    /* takes &Vec like it always did, the programs and tests build their operands with vec! */
+    #[allow(clippy::ptr_arg)]
+    pub fn new(opcode: OPCODE, param: &Vec<u8>) -> Result<Instruction, InstructionError> {
         let mut ins = Instruction::from(opcode);
 
         /* if - is the right amount of params supplied */
-        if ins.size - 1 > param.len() as u16 {
-            let name: u8 = ins.opc.into();
-            panic!("Too few params for instruction with opcode {}. Expected {} params got {}.",
-                   name, ins.size - 1, param.len());
-        } else if ins.size - 1 < param.len() as u16 {
-            let name: u8 = ins.opc.into();
-            panic!("Too many params for instruction with opcode {}. Expected {} params got {}.",
-                   name, ins.size - 1, param.len());
+        if (ins.size - 1) as usize != param.len() {
+            return Err(InstructionError { opcode: ins.opc.byte(), expected: ins.size - 1, got: param.len() });
         }
         /* end if - is the right amount of params supplied */
 
         ins.param = param.clone();
-        ins
+        Ok(ins)
+    }
+}
+/* 65C02 opcodes that replace the NMOS one of the same byte, None if the NMOS opcode is kept */
+fn cmos_opcode(code: u8, variant: Variant) -> Option<OPCODE> {
+    let bit_instructions = variant == Variant::ROCKWELL65C02 || variant == Variant::WDC65C02;
+    match code {
+        x if x == OPCODE::ORA_IZP.byte() => Some(OPCODE::ORA_IZP),
+        x if x == OPCODE::AND_IZP.byte() => Some(OPCODE::AND_IZP),
+        x if x == OPCODE::EOR_IZP.byte() => Some(OPCODE::EOR_IZP),
+        x if x == OPCODE::ADC_IZP.byte() => Some(OPCODE::ADC_IZP),
+        x if x == OPCODE::STA_IZP.byte() => Some(OPCODE::STA_IZP),
+        x if x == OPCODE::LDA_IZP.byte() => Some(OPCODE::LDA_IZP),
+        x if x == OPCODE::CMP_IZP.byte() => Some(OPCODE::CMP_IZP),
+        x if x == OPCODE::SBC_IZP.byte() => Some(OPCODE::SBC_IZP),
+        x if x == OPCODE::BIT_I.byte() => Some(OPCODE::BIT_I),
+        x if x == OPCODE::BIT_ZPX.byte() => Some(OPCODE::BIT_ZPX),
+        x if x == OPCODE::BIT_AX.byte() => Some(OPCODE::BIT_AX),
+        x if x == OPCODE::INC_ACC.byte() => Some(OPCODE::INC_ACC),
+        x if x == OPCODE::DEC_ACC.byte() => Some(OPCODE::DEC_ACC),
+        x if x == OPCODE::JMP_IAX.byte() => Some(OPCODE::JMP_IAX),
+        x if x == OPCODE::BRA.byte() => Some(OPCODE::BRA),
+        x if x == OPCODE::PHX.byte() => Some(OPCODE::PHX),
+        x if x == OPCODE::PLX.byte() => Some(OPCODE::PLX),
+        x if x == OPCODE::PHY.byte() => Some(OPCODE::PHY),
+        x if x == OPCODE::PLY.byte() => Some(OPCODE::PLY),
+        x if x == OPCODE::STZ_ZP.byte() => Some(OPCODE::STZ_ZP),
+        x if x == OPCODE::STZ_ZPX.byte() => Some(OPCODE::STZ_ZPX),
+        x if x == OPCODE::STZ_A.byte() => Some(OPCODE::STZ_A),
+        x if x == OPCODE::STZ_AX.byte() => Some(OPCODE::STZ_AX),
+        x if x == OPCODE::TRB_ZP.byte() => Some(OPCODE::TRB_ZP),
+        x if x == OPCODE::TRB_A.byte() => Some(OPCODE::TRB_A),
+        x if x == OPCODE::TSB_ZP.byte() => Some(OPCODE::TSB_ZP),
+        x if x == OPCODE::TSB_A.byte() => Some(OPCODE::TSB_A),
+        x if x == OPCODE::RMB0.byte() && bit_instructions => Some(OPCODE::RMB0),
+        x if x == OPCODE::RMB1.byte() && bit_instructions => Some(OPCODE::RMB1),
+        x if x == OPCODE::RMB2.byte() && bit_instructions => Some(OPCODE::RMB2),
+        x if x == OPCODE::RMB3.byte() && bit_instructions => Some(OPCODE::RMB3),
+        x if x == OPCODE::RMB4.byte() && bit_instructions => Some(OPCODE::RMB4),
+        x if x == OPCODE::RMB5.byte() && bit_instructions => Some(OPCODE::RMB5),
+        x if x == OPCODE::RMB6.byte() && bit_instructions => Some(OPCODE::RMB6),
+        x if x == OPCODE::RMB7.byte() && bit_instructions => Some(OPCODE::RMB7),
+        x if x == OPCODE::SMB0.byte() && bit_instructions => Some(OPCODE::SMB0),
+        x if x == OPCODE::SMB1.byte() && bit_instructions => Some(OPCODE::SMB1),
+        x if x == OPCODE::SMB2.byte() && bit_instructions => Some(OPCODE::SMB2),
+        x if x == OPCODE::SMB3.byte() && bit_instructions => Some(OPCODE::SMB3),
+        x if x == OPCODE::SMB4.byte() && bit_instructions => Some(OPCODE::SMB4),
+        x if x == OPCODE::SMB5.byte() && bit_instructions => Some(OPCODE::SMB5),
+        x if x == OPCODE::SMB6.byte() && bit_instructions => Some(OPCODE::SMB6),
+        x if x == OPCODE::SMB7.byte() && bit_instructions => Some(OPCODE::SMB7),
+        x if x == OPCODE::BBR0.byte() && bit_instructions => Some(OPCODE::BBR0),
+        x if x == OPCODE::BBR1.byte() && bit_instructions => Some(OPCODE::BBR1),
+        x if x == OPCODE::BBR2.byte() && bit_instructions => Some(OPCODE::BBR2),
+        x if x == OPCODE::BBR3.byte() && bit_instructions => Some(OPCODE::BBR3),
+        x if x == OPCODE::BBR4.byte() && bit_instructions => Some(OPCODE::BBR4),
+        x if x == OPCODE::BBR5.byte() && bit_instructions => Some(OPCODE::BBR5),
+        x if x == OPCODE::BBR6.byte() && bit_instructions => Some(OPCODE::BBR6),
+        x if x == OPCODE::BBR7.byte() && bit_instructions => Some(OPCODE::BBR7),
+        x if x == OPCODE::BBS0.byte() && bit_instructions => Some(OPCODE::BBS0),
+        x if x == OPCODE::BBS1.byte() && bit_instructions => Some(OPCODE::BBS1),
+        x if x == OPCODE::BBS2.byte() && bit_instructions => Some(OPCODE::BBS2),
+        x if x == OPCODE::BBS3.byte() && bit_instructions => Some(OPCODE::BBS3),
+        x if x == OPCODE::BBS4.byte() && bit_instructions => Some(OPCODE::BBS4),
+        x if x == OPCODE::BBS5.byte() && bit_instructions => Some(OPCODE::BBS5),
+        x if x == OPCODE::BBS6.byte() && bit_instructions => Some(OPCODE::BBS6),
+        x if x == OPCODE::BBS7.byte() && bit_instructions => Some(OPCODE::BBS7),
+        x if x == OPCODE::WAI.byte() && variant == Variant::WDC65C02 => Some(OPCODE::WAI),
+        x if x == OPCODE::STP.byte() && variant == Variant::WDC65C02 => Some(OPCODE::STP),
+        x if x == OPCODE::NOP_C_03.byte() => Some(OPCODE::NOP_C_03),
+        x if x == OPCODE::NOP_C_07.byte() => Some(OPCODE::NOP_C_07),
+        x if x == OPCODE::NOP_C_0B.byte() => Some(OPCODE::NOP_C_0B),
+        x if x == OPCODE::NOP_C_0F.byte() => Some(OPCODE::NOP_C_0F),
+        x if x == OPCODE::NOP_C_13.byte() => Some(OPCODE::NOP_C_13),
+        x if x == OPCODE::NOP_C_17.byte() => Some(OPCODE::NOP_C_17),
+        x if x == OPCODE::NOP_C_1B.byte() => Some(OPCODE::NOP_C_1B),
+        x if x == OPCODE::NOP_C_1F.byte() => Some(OPCODE::NOP_C_1F),
+        x if x == OPCODE::NOP_C_23.byte() => Some(OPCODE::NOP_C_23),
+        x if x == OPCODE::NOP_C_27.byte() => Some(OPCODE::NOP_C_27),
+        x if x == OPCODE::NOP_C_2B.byte() => Some(OPCODE::NOP_C_2B),
+        x if x == OPCODE::NOP_C_2F.byte() => Some(OPCODE::NOP_C_2F),
+        x if x == OPCODE::NOP_C_33.byte() => Some(OPCODE::NOP_C_33),
+        x if x == OPCODE::NOP_C_37.byte() => Some(OPCODE::NOP_C_37),
+        x if x == OPCODE::NOP_C_3B.byte() => Some(OPCODE::NOP_C_3B),
+        x if x == OPCODE::NOP_C_3F.byte() => Some(OPCODE::NOP_C_3F),
+        x if x == OPCODE::NOP_C_43.byte() => Some(OPCODE::NOP_C_43),
+        x if x == OPCODE::NOP_C_47.byte() => Some(OPCODE::NOP_C_47),
+        x if x == OPCODE::NOP_C_4B.byte() => Some(OPCODE::NOP_C_4B),
+        x if x == OPCODE::NOP_C_4F.byte() => Some(OPCODE::NOP_C_4F),
+        x if x == OPCODE::NOP_C_53.byte() => Some(OPCODE::NOP_C_53),
+        x if x == OPCODE::NOP_C_57.byte() => Some(OPCODE::NOP_C_57),
+        x if x == OPCODE::NOP_C_5B.byte() => Some(OPCODE::NOP_C_5B),
+        x if x == OPCODE::NOP_C_5F.byte() => Some(OPCODE::NOP_C_5F),
+        x if x == OPCODE::NOP_C_63.byte() => Some(OPCODE::NOP_C_63),
+        x if x == OPCODE::NOP_C_67.byte() => Some(OPCODE::NOP_C_67),
+        x if x == OPCODE::NOP_C_6B.byte() => Some(OPCODE::NOP_C_6B),
+        x if x == OPCODE::NOP_C_6F.byte() => Some(OPCODE::NOP_C_6F),
+        x if x == OPCODE::NOP_C_73.byte() => Some(OPCODE::NOP_C_73),
+        x if x == OPCODE::NOP_C_77.byte() => Some(OPCODE::NOP_C_77),
+        x if x == OPCODE::NOP_C_7B.byte() => Some(OPCODE::NOP_C_7B),
+        x if x == OPCODE::NOP_C_7F.byte() => Some(OPCODE::NOP_C_7F),
+        x if x == OPCODE::NOP_C_83.byte() => Some(OPCODE::NOP_C_83),
+        x if x == OPCODE::NOP_C_87.byte() => Some(OPCODE::NOP_C_87),
+        x if x == OPCODE::NOP_C_8B.byte() => Some(OPCODE::NOP_C_8B),
+        x if x == OPCODE::NOP_C_8F.byte() => Some(OPCODE::NOP_C_8F),
+        x if x == OPCODE::NOP_C_93.byte() => Some(OPCODE::NOP_C_93),
+        x if x == OPCODE::NOP_C_97.byte() => Some(OPCODE::NOP_C_97),
+        x if x == OPCODE::NOP_C_9B.byte() => Some(OPCODE::NOP_C_9B),
+        x if x == OPCODE::NOP_C_9F.byte() => Some(OPCODE::NOP_C_9F),
+        x if x == OPCODE::NOP_C_A3.byte() => Some(OPCODE::NOP_C_A3),
+        x if x == OPCODE::NOP_C_A7.byte() => Some(OPCODE::NOP_C_A7),
+        x if x == OPCODE::NOP_C_AB.byte() => Some(OPCODE::NOP_C_AB),
+        x if x == OPCODE::NOP_C_AF.byte() => Some(OPCODE::NOP_C_AF),
+        x if x == OPCODE::NOP_C_B3.byte() => Some(OPCODE::NOP_C_B3),
+        x if x == OPCODE::NOP_C_B7.byte() => Some(OPCODE::NOP_C_B7),
+        x if x == OPCODE::NOP_C_BB.byte() => Some(OPCODE::NOP_C_BB),
+        x if x == OPCODE::NOP_C_BF.byte() => Some(OPCODE::NOP_C_BF),
+        x if x == OPCODE::NOP_C_C3.byte() => Some(OPCODE::NOP_C_C3),
+        x if x == OPCODE::NOP_C_C7.byte() => Some(OPCODE::NOP_C_C7),
+        x if x == OPCODE::NOP_C_CB.byte() => Some(OPCODE::NOP_C_CB),
+        x if x == OPCODE::NOP_C_CF.byte() => Some(OPCODE::NOP_C_CF),
+        x if x == OPCODE::NOP_C_D3.byte() => Some(OPCODE::NOP_C_D3),
+        x if x == OPCODE::NOP_C_D7.byte() => Some(OPCODE::NOP_C_D7),
+        x if x == OPCODE::NOP_C_DB.byte() => Some(OPCODE::NOP_C_DB),
+        x if x == OPCODE::NOP_C_DF.byte() => Some(OPCODE::NOP_C_DF),
+        x if x == OPCODE::NOP_C_E3.byte() => Some(OPCODE::NOP_C_E3),
+        x if x == OPCODE::NOP_C_E7.byte() => Some(OPCODE::NOP_C_E7),
+        x if x == OPCODE::NOP_C_EB.byte() => Some(OPCODE::NOP_C_EB),
+        x if x == OPCODE::NOP_C_EF.byte() => Some(OPCODE::NOP_C_EF),
+        x if x == OPCODE::NOP_C_F3.byte() => Some(OPCODE::NOP_C_F3),
+        x if x == OPCODE::NOP_C_F7.byte() => Some(OPCODE::NOP_C_F7),
+        x if x == OPCODE::NOP_C_FB.byte() => Some(OPCODE::NOP_C_FB),
+        x if x == OPCODE::NOP_C_FF.byte() => Some(OPCODE::NOP_C_FF),
+        x if x == OPCODE::NOP_C_I_02.byte() => Some(OPCODE::NOP_C_I_02),
+        x if x == OPCODE::NOP_C_I_22.byte() => Some(OPCODE::NOP_C_I_22),
+        x if x == OPCODE::NOP_C_I_42.byte() => Some(OPCODE::NOP_C_I_42),
+        x if x == OPCODE::NOP_C_I_62.byte() => Some(OPCODE::NOP_C_I_62),
+        x if x == OPCODE::NOP_C_A_5C.byte() => Some(OPCODE::NOP_C_A_5C),
+        x if x == OPCODE::NOP_C_A_DC.byte() => Some(OPCODE::NOP_C_A_DC),
+        x if x == OPCODE::NOP_C_A_FC.byte() => Some(OPCODE::NOP_C_A_FC),
+        _ => None
     }
 }
 impl From<OPCODE> for Instruction {
@@ -268,25 +778,25 @@ impl From<OPCODE> for Instruction {
             OPCODE::LDA_IX => Instruction { opc: code, param: Vec::new(), size: 2, cycles: 6},
             OPCODE::LDA_IY => Instruction { opc: code, param: Vec::new(), size: 2, cycles: 5},
 
-            OPCODE::LDX_I => Instruction { opc: code, param: Vec::new(), size: 2, cycles: 4},
+            OPCODE::LDX_I => Instruction { opc: code, param: Vec::new(), size: 2, cycles: 2},
             OPCODE::LDX_A => Instruction { opc: code, param: Vec::new(), size: 3, cycles: 4 },
             OPCODE::LDX_AY => Instruction { opc: code, param: Vec::new(), size: 3, cycles: 4},
-            OPCODE::LDX_ZP => Instruction { opc: code, param: Vec::new(), size: 2, cycles: 6},
-            OPCODE::LDX_ZPY => Instruction { opc: code, param: Vec::new(), size: 2, cycles: 5},
+            OPCODE::LDX_ZP => Instruction { opc: code, param: Vec::new(), size: 2, cycles: 3},
+            OPCODE::LDX_ZPY => Instruction { opc: code, param: Vec::new(), size: 2, cycles: 4},
 
-            OPCODE::LDY_I => Instruction { opc: code, param: Vec::new(), size: 2, cycles: 4},
+            OPCODE::LDY_I => Instruction { opc: code, param: Vec::new(), size: 2, cycles: 2},
             OPCODE::LDY_A => Instruction { opc: code, param: Vec::new(), size: 3, cycles: 4 },
             OPCODE::LDY_AX => Instruction { opc: code, param: Vec::new(), size: 3, cycles: 4},
-            OPCODE::LDY_ZP => Instruction { opc: code, param: Vec::new(), size: 2, cycles: 6},
-            OPCODE::LDY_ZPX => Instruction { opc: code, param: Vec::new(), size: 2, cycles: 5},
+            OPCODE::LDY_ZP => Instruction { opc: code, param: Vec::new(), size: 2, cycles: 3},
+            OPCODE::LDY_ZPX => Instruction { opc: code, param: Vec::new(), size: 2, cycles: 4},
 
             OPCODE::CMP_I => Instruction { opc: code, param: Vec::new(), size: 2, cycles: 2},
-            OPCODE::CMP_ZP => Instruction { opc: code, param: Vec::new(), size: 3, cycles: 3},
+            OPCODE::CMP_ZP => Instruction { opc: code, param: Vec::new(), size: 2, cycles: 3},
             OPCODE::CMP_ZPX => Instruction { opc: code, param: Vec::new(), size: 2, cycles: 4},
             OPCODE::CMP_A => Instruction { opc: code, param: Vec::new(), size: 3, cycles: 4},
             OPCODE::CMP_AX => Instruction { opc: code, param: Vec::new(), size: 3, cycles: 4},
             OPCODE::CMP_AY => Instruction { opc: code, param: Vec::new(), size: 3, cycles: 4},
-            OPCODE::CMP_IX => Instruction { opc: code, param: Vec::new(), size: 3, cycles: 6},
+            OPCODE::CMP_IX => Instruction { opc: code, param: Vec::new(), size: 2, cycles: 6},
             OPCODE::CMP_IY => Instruction { opc: code, param: Vec::new(), size: 2, cycles: 5},
 
             OPCODE::CPX_I => Instruction { opc: code, param: Vec::new(), size: 2, cycles: 2},
@@ -307,8 +817,8 @@ impl From<OPCODE> for Instruction {
             OPCODE::ADC_IY => Instruction { opc: code, param: Vec::new(), size: 2, cycles: 5},
 
             OPCODE::AND_I => Instruction { opc: code, param: Vec::new(), size: 2, cycles: 2},
-            OPCODE::AND_ZP => Instruction { opc: code, param: Vec::new(), size: 2, cycles: 2},
-            OPCODE::AND_ZPX => Instruction { opc: code, param: Vec::new(), size: 2, cycles: 3},
+            OPCODE::AND_ZP => Instruction { opc: code, param: Vec::new(), size: 2, cycles: 3},
+            OPCODE::AND_ZPX => Instruction { opc: code, param: Vec::new(), size: 2, cycles: 4},
             OPCODE::AND_A => Instruction { opc: code, param: Vec::new(), size: 3, cycles: 4},
             OPCODE::AND_AX => Instruction { opc: code, param: Vec::new(), size: 3, cycles: 4},
             OPCODE::AND_AY => Instruction { opc: code, param: Vec::new(), size: 3, cycles: 4},
@@ -376,8 +886,8 @@ impl From<OPCODE> for Instruction {
             OPCODE::NOP => Instruction { opc: code, param: Vec::new(), size: 1, cycles: 2},
 
             OPCODE::ORA_I => Instruction { opc: code, param: Vec::new(), size: 2, cycles: 2},
-            OPCODE::ORA_ZP => Instruction { opc: code, param: Vec::new(), size: 2, cycles: 2},
-            OPCODE::ORA_ZPX => Instruction { opc: code, param: Vec::new(), size: 2, cycles: 3},
+            OPCODE::ORA_ZP => Instruction { opc: code, param: Vec::new(), size: 2, cycles: 3},
+            OPCODE::ORA_ZPX => Instruction { opc: code, param: Vec::new(), size: 2, cycles: 4},
             OPCODE::ORA_A => Instruction { opc: code, param: Vec::new(), size: 3, cycles: 4},
             OPCODE::ORA_AX => Instruction { opc: code, param: Vec::new(), size: 3, cycles: 4},
             OPCODE::ORA_AY => Instruction { opc: code, param: Vec::new(), size: 3, cycles: 4},
@@ -413,6 +923,7 @@ impl From<OPCODE> for Instruction {
             OPCODE::SBC_ZPX => Instruction { opc: code, param: Vec::new(), size: 2, cycles: 4},
             OPCODE::SBC_A => Instruction { opc: code, param: Vec::new(), size: 3, cycles: 4},
             OPCODE::SBC_AX => Instruction { opc: code, param: Vec::new(), size: 3, cycles: 4},
+            OPCODE::SBC_AY => Instruction { opc: code, param: Vec::new(), size: 3, cycles: 4},
             OPCODE::SBC_IX => Instruction { opc: code, param: Vec::new(), size: 2, cycles: 6},
             OPCODE::SBC_IY => Instruction { opc: code, param: Vec::new(), size: 2, cycles: 5},
 
@@ -422,7 +933,260 @@ impl From<OPCODE> for Instruction {
             OPCODE::PLA => Instruction { opc: code, param: Vec::new(), size: 1, cycles: 4},
             OPCODE::PHP => Instruction { opc: code, param: Vec::new(), size: 1, cycles: 3},
             OPCODE::PLP => Instruction { opc: code, param: Vec::new(), size: 1, cycles: 4},
-            _ => panic!("Not implemented!")
+
+            OPCODE::SLO_ZP => Instruction { opc: code, param: Vec::new(), size: 2, cycles: 5 },
+            OPCODE::SLO_ZPX => Instruction { opc: code, param: Vec::new(), size: 2, cycles: 6 },
+            OPCODE::SLO_A => Instruction { opc: code, param: Vec::new(), size: 3, cycles: 6 },
+            OPCODE::SLO_AX => Instruction { opc: code, param: Vec::new(), size: 3, cycles: 7 },
+            OPCODE::SLO_AY => Instruction { opc: code, param: Vec::new(), size: 3, cycles: 7 },
+            OPCODE::SLO_IX => Instruction { opc: code, param: Vec::new(), size: 2, cycles: 8 },
+            OPCODE::SLO_IY => Instruction { opc: code, param: Vec::new(), size: 2, cycles: 8 },
+
+            OPCODE::RLA_ZP => Instruction { opc: code, param: Vec::new(), size: 2, cycles: 5 },
+            OPCODE::RLA_ZPX => Instruction { opc: code, param: Vec::new(), size: 2, cycles: 6 },
+            OPCODE::RLA_A => Instruction { opc: code, param: Vec::new(), size: 3, cycles: 6 },
+            OPCODE::RLA_AX => Instruction { opc: code, param: Vec::new(), size: 3, cycles: 7 },
+            OPCODE::RLA_AY => Instruction { opc: code, param: Vec::new(), size: 3, cycles: 7 },
+            OPCODE::RLA_IX => Instruction { opc: code, param: Vec::new(), size: 2, cycles: 8 },
+            OPCODE::RLA_IY => Instruction { opc: code, param: Vec::new(), size: 2, cycles: 8 },
+
+            OPCODE::SRE_ZP => Instruction { opc: code, param: Vec::new(), size: 2, cycles: 5 },
+            OPCODE::SRE_ZPX => Instruction { opc: code, param: Vec::new(), size: 2, cycles: 6 },
+            OPCODE::SRE_A => Instruction { opc: code, param: Vec::new(), size: 3, cycles: 6 },
+            OPCODE::SRE_AX => Instruction { opc: code, param: Vec::new(), size: 3, cycles: 7 },
+            OPCODE::SRE_AY => Instruction { opc: code, param: Vec::new(), size: 3, cycles: 7 },
+            OPCODE::SRE_IX => Instruction { opc: code, param: Vec::new(), size: 2, cycles: 8 },
+            OPCODE::SRE_IY => Instruction { opc: code, param: Vec::new(), size: 2, cycles: 8 },
+
+            OPCODE::RRA_ZP => Instruction { opc: code, param: Vec::new(), size: 2, cycles: 5 },
+            OPCODE::RRA_ZPX => Instruction { opc: code, param: Vec::new(), size: 2, cycles: 6 },
+            OPCODE::RRA_A => Instruction { opc: code, param: Vec::new(), size: 3, cycles: 6 },
+            OPCODE::RRA_AX => Instruction { opc: code, param: Vec::new(), size: 3, cycles: 7 },
+            OPCODE::RRA_AY => Instruction { opc: code, param: Vec::new(), size: 3, cycles: 7 },
+            OPCODE::RRA_IX => Instruction { opc: code, param: Vec::new(), size: 2, cycles: 8 },
+            OPCODE::RRA_IY => Instruction { opc: code, param: Vec::new(), size: 2, cycles: 8 },
+
+            OPCODE::DCP_ZP => Instruction { opc: code, param: Vec::new(), size: 2, cycles: 5 },
+            OPCODE::DCP_ZPX => Instruction { opc: code, param: Vec::new(), size: 2, cycles: 6 },
+            OPCODE::DCP_A => Instruction { opc: code, param: Vec::new(), size: 3, cycles: 6 },
+            OPCODE::DCP_AX => Instruction { opc: code, param: Vec::new(), size: 3, cycles: 7 },
+            OPCODE::DCP_AY => Instruction { opc: code, param: Vec::new(), size: 3, cycles: 7 },
+            OPCODE::DCP_IX => Instruction { opc: code, param: Vec::new(), size: 2, cycles: 8 },
+            OPCODE::DCP_IY => Instruction { opc: code, param: Vec::new(), size: 2, cycles: 8 },
+
+            OPCODE::ISC_ZP => Instruction { opc: code, param: Vec::new(), size: 2, cycles: 5 },
+            OPCODE::ISC_ZPX => Instruction { opc: code, param: Vec::new(), size: 2, cycles: 6 },
+            OPCODE::ISC_A => Instruction { opc: code, param: Vec::new(), size: 3, cycles: 6 },
+            OPCODE::ISC_AX => Instruction { opc: code, param: Vec::new(), size: 3, cycles: 7 },
+            OPCODE::ISC_AY => Instruction { opc: code, param: Vec::new(), size: 3, cycles: 7 },
+            OPCODE::ISC_IX => Instruction { opc: code, param: Vec::new(), size: 2, cycles: 8 },
+            OPCODE::ISC_IY => Instruction { opc: code, param: Vec::new(), size: 2, cycles: 8 },
+
+            OPCODE::SAX_ZP => Instruction { opc: code, param: Vec::new(), size: 2, cycles: 3 },
+            OPCODE::SAX_ZPY => Instruction { opc: code, param: Vec::new(), size: 2, cycles: 4 },
+            OPCODE::SAX_A => Instruction { opc: code, param: Vec::new(), size: 3, cycles: 4 },
+            OPCODE::SAX_IX => Instruction { opc: code, param: Vec::new(), size: 2, cycles: 6 },
+
+            OPCODE::LAX_ZP => Instruction { opc: code, param: Vec::new(), size: 2, cycles: 3 },
+            OPCODE::LAX_ZPY => Instruction { opc: code, param: Vec::new(), size: 2, cycles: 4 },
+            OPCODE::LAX_A => Instruction { opc: code, param: Vec::new(), size: 3, cycles: 4 },
+            OPCODE::LAX_AY => Instruction { opc: code, param: Vec::new(), size: 3, cycles: 4 },
+            OPCODE::LAX_IX => Instruction { opc: code, param: Vec::new(), size: 2, cycles: 6 },
+            OPCODE::LAX_IY => Instruction { opc: code, param: Vec::new(), size: 2, cycles: 5 },
+
+            OPCODE::ANC_I => Instruction { opc: code, param: Vec::new(), size: 2, cycles: 2 },
+            OPCODE::ANC_I_2B => Instruction { opc: code, param: Vec::new(), size: 2, cycles: 2 },
+            OPCODE::ALR_I => Instruction { opc: code, param: Vec::new(), size: 2, cycles: 2 },
+            OPCODE::ARR_I => Instruction { opc: code, param: Vec::new(), size: 2, cycles: 2 },
+            OPCODE::SBX_I => Instruction { opc: code, param: Vec::new(), size: 2, cycles: 2 },
+            OPCODE::SBC_I_EB => Instruction { opc: code, param: Vec::new(), size: 2, cycles: 2 },
+            OPCODE::LAS_AY => Instruction { opc: code, param: Vec::new(), size: 3, cycles: 4 },
+            OPCODE::ANE_I => Instruction { opc: code, param: Vec::new(), size: 2, cycles: 2 },
+            OPCODE::LXA_I => Instruction { opc: code, param: Vec::new(), size: 2, cycles: 2 },
+            OPCODE::SHA_AY => Instruction { opc: code, param: Vec::new(), size: 3, cycles: 5 },
+            OPCODE::SHA_IY => Instruction { opc: code, param: Vec::new(), size: 2, cycles: 6 },
+            OPCODE::SHX_AY => Instruction { opc: code, param: Vec::new(), size: 3, cycles: 5 },
+            OPCODE::SHY_AX => Instruction { opc: code, param: Vec::new(), size: 3, cycles: 5 },
+            OPCODE::TAS_AY => Instruction { opc: code, param: Vec::new(), size: 3, cycles: 5 },
+
+            OPCODE::NOP_1A => Instruction { opc: code, param: Vec::new(), size: 1, cycles: 2 },
+            OPCODE::NOP_3A => Instruction { opc: code, param: Vec::new(), size: 1, cycles: 2 },
+            OPCODE::NOP_5A => Instruction { opc: code, param: Vec::new(), size: 1, cycles: 2 },
+            OPCODE::NOP_7A => Instruction { opc: code, param: Vec::new(), size: 1, cycles: 2 },
+            OPCODE::NOP_DA => Instruction { opc: code, param: Vec::new(), size: 1, cycles: 2 },
+            OPCODE::NOP_FA => Instruction { opc: code, param: Vec::new(), size: 1, cycles: 2 },
+            OPCODE::NOP_I_80 => Instruction { opc: code, param: Vec::new(), size: 2, cycles: 2 },
+            OPCODE::NOP_I_82 => Instruction { opc: code, param: Vec::new(), size: 2, cycles: 2 },
+            OPCODE::NOP_I_89 => Instruction { opc: code, param: Vec::new(), size: 2, cycles: 2 },
+            OPCODE::NOP_I_C2 => Instruction { opc: code, param: Vec::new(), size: 2, cycles: 2 },
+            OPCODE::NOP_I_E2 => Instruction { opc: code, param: Vec::new(), size: 2, cycles: 2 },
+            OPCODE::NOP_ZP_04 => Instruction { opc: code, param: Vec::new(), size: 2, cycles: 3 },
+            OPCODE::NOP_ZP_44 => Instruction { opc: code, param: Vec::new(), size: 2, cycles: 3 },
+            OPCODE::NOP_ZP_64 => Instruction { opc: code, param: Vec::new(), size: 2, cycles: 3 },
+            OPCODE::NOP_ZPX_14 => Instruction { opc: code, param: Vec::new(), size: 2, cycles: 4 },
+            OPCODE::NOP_ZPX_34 => Instruction { opc: code, param: Vec::new(), size: 2, cycles: 4 },
+            OPCODE::NOP_ZPX_54 => Instruction { opc: code, param: Vec::new(), size: 2, cycles: 4 },
+            OPCODE::NOP_ZPX_74 => Instruction { opc: code, param: Vec::new(), size: 2, cycles: 4 },
+            OPCODE::NOP_ZPX_D4 => Instruction { opc: code, param: Vec::new(), size: 2, cycles: 4 },
+            OPCODE::NOP_ZPX_F4 => Instruction { opc: code, param: Vec::new(), size: 2, cycles: 4 },
+            OPCODE::NOP_A_0C => Instruction { opc: code, param: Vec::new(), size: 3, cycles: 4 },
+            OPCODE::NOP_AX_1C => Instruction { opc: code, param: Vec::new(), size: 3, cycles: 4 },
+            OPCODE::NOP_AX_3C => Instruction { opc: code, param: Vec::new(), size: 3, cycles: 4 },
+            OPCODE::NOP_AX_5C => Instruction { opc: code, param: Vec::new(), size: 3, cycles: 4 },
+            OPCODE::NOP_AX_7C => Instruction { opc: code, param: Vec::new(), size: 3, cycles: 4 },
+            OPCODE::NOP_AX_DC => Instruction { opc: code, param: Vec::new(), size: 3, cycles: 4 },
+            OPCODE::NOP_AX_FC => Instruction { opc: code, param: Vec::new(), size: 3, cycles: 4 },
+
+            OPCODE::JAM_02 => Instruction { opc: code, param: Vec::new(), size: 1, cycles: 2 },
+            OPCODE::JAM_12 => Instruction { opc: code, param: Vec::new(), size: 1, cycles: 2 },
+            OPCODE::JAM_22 => Instruction { opc: code, param: Vec::new(), size: 1, cycles: 2 },
+            OPCODE::JAM_32 => Instruction { opc: code, param: Vec::new(), size: 1, cycles: 2 },
+            OPCODE::JAM_42 => Instruction { opc: code, param: Vec::new(), size: 1, cycles: 2 },
+            OPCODE::JAM_52 => Instruction { opc: code, param: Vec::new(), size: 1, cycles: 2 },
+            OPCODE::JAM_62 => Instruction { opc: code, param: Vec::new(), size: 1, cycles: 2 },
+            OPCODE::JAM_72 => Instruction { opc: code, param: Vec::new(), size: 1, cycles: 2 },
+            OPCODE::JAM_92 => Instruction { opc: code, param: Vec::new(), size: 1, cycles: 2 },
+            OPCODE::JAM_B2 => Instruction { opc: code, param: Vec::new(), size: 1, cycles: 2 },
+            OPCODE::JAM_D2 => Instruction { opc: code, param: Vec::new(), size: 1, cycles: 2 },
+            OPCODE::JAM_F2 => Instruction { opc: code, param: Vec::new(), size: 1, cycles: 2 },
+
+            OPCODE::ORA_IZP => Instruction { opc: code, param: Vec::new(), size: 2, cycles: 5 },
+            OPCODE::AND_IZP => Instruction { opc: code, param: Vec::new(), size: 2, cycles: 5 },
+            OPCODE::EOR_IZP => Instruction { opc: code, param: Vec::new(), size: 2, cycles: 5 },
+            OPCODE::ADC_IZP => Instruction { opc: code, param: Vec::new(), size: 2, cycles: 5 },
+            OPCODE::STA_IZP => Instruction { opc: code, param: Vec::new(), size: 2, cycles: 5 },
+            OPCODE::LDA_IZP => Instruction { opc: code, param: Vec::new(), size: 2, cycles: 5 },
+            OPCODE::CMP_IZP => Instruction { opc: code, param: Vec::new(), size: 2, cycles: 5 },
+            OPCODE::SBC_IZP => Instruction { opc: code, param: Vec::new(), size: 2, cycles: 5 },
+
+            OPCODE::BIT_I => Instruction { opc: code, param: Vec::new(), size: 2, cycles: 2 },
+            OPCODE::BIT_ZPX => Instruction { opc: code, param: Vec::new(), size: 2, cycles: 4 },
+            OPCODE::BIT_AX => Instruction { opc: code, param: Vec::new(), size: 3, cycles: 4 },
+            OPCODE::INC_ACC => Instruction { opc: code, param: Vec::new(), size: 1, cycles: 2 },
+            OPCODE::DEC_ACC => Instruction { opc: code, param: Vec::new(), size: 1, cycles: 2 },
+            OPCODE::JMP_IAX => Instruction { opc: code, param: Vec::new(), size: 3, cycles: 6 },
+            OPCODE::BRA => Instruction { opc: code, param: Vec::new(), size: 2, cycles: 2 },
+            OPCODE::PHX => Instruction { opc: code, param: Vec::new(), size: 1, cycles: 3 },
+            OPCODE::PLX => Instruction { opc: code, param: Vec::new(), size: 1, cycles: 4 },
+            OPCODE::PHY => Instruction { opc: code, param: Vec::new(), size: 1, cycles: 3 },
+            OPCODE::PLY => Instruction { opc: code, param: Vec::new(), size: 1, cycles: 4 },
+
+            OPCODE::STZ_ZP => Instruction { opc: code, param: Vec::new(), size: 2, cycles: 3 },
+            OPCODE::STZ_ZPX => Instruction { opc: code, param: Vec::new(), size: 2, cycles: 4 },
+            OPCODE::STZ_A => Instruction { opc: code, param: Vec::new(), size: 3, cycles: 4 },
+            OPCODE::STZ_AX => Instruction { opc: code, param: Vec::new(), size: 3, cycles: 5 },
+            OPCODE::TRB_ZP => Instruction { opc: code, param: Vec::new(), size: 2, cycles: 5 },
+            OPCODE::TRB_A => Instruction { opc: code, param: Vec::new(), size: 3, cycles: 6 },
+            OPCODE::TSB_ZP => Instruction { opc: code, param: Vec::new(), size: 2, cycles: 5 },
+            OPCODE::TSB_A => Instruction { opc: code, param: Vec::new(), size: 3, cycles: 6 },
+
+            OPCODE::RMB0 => Instruction { opc: code, param: Vec::new(), size: 2, cycles: 5 },
+            OPCODE::RMB1 => Instruction { opc: code, param: Vec::new(), size: 2, cycles: 5 },
+            OPCODE::RMB2 => Instruction { opc: code, param: Vec::new(), size: 2, cycles: 5 },
+            OPCODE::RMB3 => Instruction { opc: code, param: Vec::new(), size: 2, cycles: 5 },
+            OPCODE::RMB4 => Instruction { opc: code, param: Vec::new(), size: 2, cycles: 5 },
+            OPCODE::RMB5 => Instruction { opc: code, param: Vec::new(), size: 2, cycles: 5 },
+            OPCODE::RMB6 => Instruction { opc: code, param: Vec::new(), size: 2, cycles: 5 },
+            OPCODE::RMB7 => Instruction { opc: code, param: Vec::new(), size: 2, cycles: 5 },
+            OPCODE::SMB0 => Instruction { opc: code, param: Vec::new(), size: 2, cycles: 5 },
+            OPCODE::SMB1 => Instruction { opc: code, param: Vec::new(), size: 2, cycles: 5 },
+            OPCODE::SMB2 => Instruction { opc: code, param: Vec::new(), size: 2, cycles: 5 },
+            OPCODE::SMB3 => Instruction { opc: code, param: Vec::new(), size: 2, cycles: 5 },
+            OPCODE::SMB4 => Instruction { opc: code, param: Vec::new(), size: 2, cycles: 5 },
+            OPCODE::SMB5 => Instruction { opc: code, param: Vec::new(), size: 2, cycles: 5 },
+            OPCODE::SMB6 => Instruction { opc: code, param: Vec::new(), size: 2, cycles: 5 },
+            OPCODE::SMB7 => Instruction { opc: code, param: Vec::new(), size: 2, cycles: 5 },
+            OPCODE::BBR0 => Instruction { opc: code, param: Vec::new(), size: 3, cycles: 5 },
+            OPCODE::BBR1 => Instruction { opc: code, param: Vec::new(), size: 3, cycles: 5 },
+            OPCODE::BBR2 => Instruction { opc: code, param: Vec::new(), size: 3, cycles: 5 },
+            OPCODE::BBR3 => Instruction { opc: code, param: Vec::new(), size: 3, cycles: 5 },
+            OPCODE::BBR4 => Instruction { opc: code, param: Vec::new(), size: 3, cycles: 5 },
+            OPCODE::BBR5 => Instruction { opc: code, param: Vec::new(), size: 3, cycles: 5 },
+            OPCODE::BBR6 => Instruction { opc: code, param: Vec::new(), size: 3, cycles: 5 },
+            OPCODE::BBR7 => Instruction { opc: code, param: Vec::new(), size: 3, cycles: 5 },
+            OPCODE::BBS0 => Instruction { opc: code, param: Vec::new(), size: 3, cycles: 5 },
+            OPCODE::BBS1 => Instruction { opc: code, param: Vec::new(), size: 3, cycles: 5 },
+            OPCODE::BBS2 => Instruction { opc: code, param: Vec::new(), size: 3, cycles: 5 },
+            OPCODE::BBS3 => Instruction { opc: code, param: Vec::new(), size: 3, cycles: 5 },
+            OPCODE::BBS4 => Instruction { opc: code, param: Vec::new(), size: 3, cycles: 5 },
+            OPCODE::BBS5 => Instruction { opc: code, param: Vec::new(), size: 3, cycles: 5 },
+            OPCODE::BBS6 => Instruction { opc: code, param: Vec::new(), size: 3, cycles: 5 },
+            OPCODE::BBS7 => Instruction { opc: code, param: Vec::new(), size: 3, cycles: 5 },
+
+            OPCODE::WAI => Instruction { opc: code, param: Vec::new(), size: 1, cycles: 3 },
+            OPCODE::STP => Instruction { opc: code, param: Vec::new(), size: 1, cycles: 3 },
+
+            OPCODE::NOP_C_03 => Instruction { opc: code, param: Vec::new(), size: 1, cycles: 1 },
+            OPCODE::NOP_C_07 => Instruction { opc: code, param: Vec::new(), size: 1, cycles: 1 },
+            OPCODE::NOP_C_0B => Instruction { opc: code, param: Vec::new(), size: 1, cycles: 1 },
+            OPCODE::NOP_C_0F => Instruction { opc: code, param: Vec::new(), size: 1, cycles: 1 },
+            OPCODE::NOP_C_13 => Instruction { opc: code, param: Vec::new(), size: 1, cycles: 1 },
+            OPCODE::NOP_C_17 => Instruction { opc: code, param: Vec::new(), size: 1, cycles: 1 },
+            OPCODE::NOP_C_1B => Instruction { opc: code, param: Vec::new(), size: 1, cycles: 1 },
+            OPCODE::NOP_C_1F => Instruction { opc: code, param: Vec::new(), size: 1, cycles: 1 },
+            OPCODE::NOP_C_23 => Instruction { opc: code, param: Vec::new(), size: 1, cycles: 1 },
+            OPCODE::NOP_C_27 => Instruction { opc: code, param: Vec::new(), size: 1, cycles: 1 },
+            OPCODE::NOP_C_2B => Instruction { opc: code, param: Vec::new(), size: 1, cycles: 1 },
+            OPCODE::NOP_C_2F => Instruction { opc: code, param: Vec::new(), size: 1, cycles: 1 },
+            OPCODE::NOP_C_33 => Instruction { opc: code, param: Vec::new(), size: 1, cycles: 1 },
+            OPCODE::NOP_C_37 => Instruction { opc: code, param: Vec::new(), size: 1, cycles: 1 },
+            OPCODE::NOP_C_3B => Instruction { opc: code, param: Vec::new(), size: 1, cycles: 1 },
+            OPCODE::NOP_C_3F => Instruction { opc: code, param: Vec::new(), size: 1, cycles: 1 },
+            OPCODE::NOP_C_43 => Instruction { opc: code, param: Vec::new(), size: 1, cycles: 1 },
+            OPCODE::NOP_C_47 => Instruction { opc: code, param: Vec::new(), size: 1, cycles: 1 },
+            OPCODE::NOP_C_4B => Instruction { opc: code, param: Vec::new(), size: 1, cycles: 1 },
+            OPCODE::NOP_C_4F => Instruction { opc: code, param: Vec::new(), size: 1, cycles: 1 },
+            OPCODE::NOP_C_53 => Instruction { opc: code, param: Vec::new(), size: 1, cycles: 1 },
+            OPCODE::NOP_C_57 => Instruction { opc: code, param: Vec::new(), size: 1, cycles: 1 },
+            OPCODE::NOP_C_5B => Instruction { opc: code, param: Vec::new(), size: 1, cycles: 1 },
+            OPCODE::NOP_C_5F => Instruction { opc: code, param: Vec::new(), size: 1, cycles: 1 },
+            OPCODE::NOP_C_63 => Instruction { opc: code, param: Vec::new(), size: 1, cycles: 1 },
+            OPCODE::NOP_C_67 => Instruction { opc: code, param: Vec::new(), size: 1, cycles: 1 },
+            OPCODE::NOP_C_6B => Instruction { opc: code, param: Vec::new(), size: 1, cycles: 1 },
+            OPCODE::NOP_C_6F => Instruction { opc: code, param: Vec::new(), size: 1, cycles: 1 },
+            OPCODE::NOP_C_73 => Instruction { opc: code, param: Vec::new(), size: 1, cycles: 1 },
+            OPCODE::NOP_C_77 => Instruction { opc: code, param: Vec::new(), size: 1, cycles: 1 },
+            OPCODE::NOP_C_7B => Instruction { opc: code, param: Vec::new(), size: 1, cycles: 1 },
+            OPCODE::NOP_C_7F => Instruction { opc: code, param: Vec::new(), size: 1, cycles: 1 },
+            OPCODE::NOP_C_83 => Instruction { opc: code, param: Vec::new(), size: 1, cycles: 1 },
+            OPCODE::NOP_C_87 => Instruction { opc: code, param: Vec::new(), size: 1, cycles: 1 },
+            OPCODE::NOP_C_8B => Instruction { opc: code, param: Vec::new(), size: 1, cycles: 1 },
+            OPCODE::NOP_C_8F => Instruction { opc: code, param: Vec::new(), size: 1, cycles: 1 },
+            OPCODE::NOP_C_93 => Instruction { opc: code, param: Vec::new(), size: 1, cycles: 1 },
+            OPCODE::NOP_C_97 => Instruction { opc: code, param: Vec::new(), size: 1, cycles: 1 },
+            OPCODE::NOP_C_9B => Instruction { opc: code, param: Vec::new(), size: 1, cycles: 1 },
+            OPCODE::NOP_C_9F => Instruction { opc: code, param: Vec::new(), size: 1, cycles: 1 },
+            OPCODE::NOP_C_A3 => Instruction { opc: code, param: Vec::new(), size: 1, cycles: 1 },
+            OPCODE::NOP_C_A7 => Instruction { opc: code, param: Vec::new(), size: 1, cycles: 1 },
+            OPCODE::NOP_C_AB => Instruction { opc: code, param: Vec::new(), size: 1, cycles: 1 },
+            OPCODE::NOP_C_AF => Instruction { opc: code, param: Vec::new(), size: 1, cycles: 1 },
+            OPCODE::NOP_C_B3 => Instruction { opc: code, param: Vec::new(), size: 1, cycles: 1 },
+            OPCODE::NOP_C_B7 => Instruction { opc: code, param: Vec::new(), size: 1, cycles: 1 },
+            OPCODE::NOP_C_BB => Instruction { opc: code, param: Vec::new(), size: 1, cycles: 1 },
+            OPCODE::NOP_C_BF => Instruction { opc: code, param: Vec::new(), size: 1, cycles: 1 },
+            OPCODE::NOP_C_C3 => Instruction { opc: code, param: Vec::new(), size: 1, cycles: 1 },
+            OPCODE::NOP_C_C7 => Instruction { opc: code, param: Vec::new(), size: 1, cycles: 1 },
+            OPCODE::NOP_C_CB => Instruction { opc: code, param: Vec::new(), size: 1, cycles: 1 },
+            OPCODE::NOP_C_CF => Instruction { opc: code, param: Vec::new(), size: 1, cycles: 1 },
+            OPCODE::NOP_C_D3 => Instruction { opc: code, param: Vec::new(), size: 1, cycles: 1 },
+            OPCODE::NOP_C_D7 => Instruction { opc: code, param: Vec::new(), size: 1, cycles: 1 },
+            OPCODE::NOP_C_DB => Instruction { opc: code, param: Vec::new(), size: 1, cycles: 1 },
+            OPCODE::NOP_C_DF => Instruction { opc: code, param: Vec::new(), size: 1, cycles: 1 },
+            OPCODE::NOP_C_E3 => Instruction { opc: code, param: Vec::new(), size: 1, cycles: 1 },
+            OPCODE::NOP_C_E7 => Instruction { opc: code, param: Vec::new(), size: 1, cycles: 1 },
+            OPCODE::NOP_C_EB => Instruction { opc: code, param: Vec::new(), size: 1, cycles: 1 },
+            OPCODE::NOP_C_EF => Instruction { opc: code, param: Vec::new(), size: 1, cycles: 1 },
+            OPCODE::NOP_C_F3 => Instruction { opc: code, param: Vec::new(), size: 1, cycles: 1 },
+            OPCODE::NOP_C_F7 => Instruction { opc: code, param: Vec::new(), size: 1, cycles: 1 },
+            OPCODE::NOP_C_FB => Instruction { opc: code, param: Vec::new(), size: 1, cycles: 1 },
+            OPCODE::NOP_C_FF => Instruction { opc: code, param: Vec::new(), size: 1, cycles: 1 },
+            OPCODE::NOP_C_I_02 => Instruction { opc: code, param: Vec::new(), size: 2, cycles: 2 },
+            OPCODE::NOP_C_I_22 => Instruction { opc: code, param: Vec::new(), size: 2, cycles: 2 },
+            OPCODE::NOP_C_I_42 => Instruction { opc: code, param: Vec::new(), size: 2, cycles: 2 },
+            OPCODE::NOP_C_I_62 => Instruction { opc: code, param: Vec::new(), size: 2, cycles: 2 },
+            OPCODE::NOP_C_A_5C => Instruction { opc: code, param: Vec::new(), size: 3, cycles: 8 },
+            OPCODE::NOP_C_A_DC => Instruction { opc: code, param: Vec::new(), size: 3, cycles: 4 },
+            OPCODE::NOP_C_A_FC => Instruction { opc: code, param: Vec::new(), size: 3, cycles: 4 },
         }
     }
 }
@@ -472,7 +1236,7 @@ impl TryFrom<u8> for Instruction {
             x if x == OPCODE::CMP_ZPX as u8 => Ok(Instruction { opc: OPCODE::CMP_ZPX, param: Vec::new(), size: 2, cycles: 4 }),
             x if x == OPCODE::CMP_A as u8 => Ok(Instruction { opc: OPCODE::CMP_A, param: Vec::new(), size: 3, cycles: 4 }),
             x if x == OPCODE::CMP_AX as u8 => Ok(Instruction { opc: OPCODE::CMP_AX, param: Vec::new(), size: 3, cycles: 4 }),
-            x if x == OPCODE::CMP_AY as u8 => Ok(Instruction { opc: OPCODE::CMP_AY, param: Vec::new(), size: 3, cycles: 3 }),
+            x if x == OPCODE::CMP_AY as u8 => Ok(Instruction { opc: OPCODE::CMP_AY, param: Vec::new(), size: 3, cycles: 4 }),
             x if x == OPCODE::CMP_IX as u8 => Ok(Instruction { opc: OPCODE::CMP_IX, param: Vec::new(), size: 2, cycles: 6 }),
             x if x == OPCODE::CMP_IY as u8 => Ok(Instruction { opc: OPCODE::CMP_IY, param: Vec::new(), size: 2, cycles: 5 }),
 
@@ -494,8 +1258,8 @@ impl TryFrom<u8> for Instruction {
             x if x == OPCODE::ADC_IY as u8 => Ok(Instruction { opc: OPCODE::ADC_IY, param: Vec::new(), size: 2, cycles: 5}),
 
             x if x == OPCODE::AND_I as u8 => Ok(Instruction { opc: OPCODE::AND_I, param: Vec::new(), size: 2, cycles: 2}),
-            x if x == OPCODE::AND_ZP as u8 => Ok(Instruction { opc: OPCODE::AND_ZP, param: Vec::new(), size: 2, cycles: 2}),
-            x if x == OPCODE::AND_ZPX as u8 => Ok(Instruction { opc: OPCODE::AND_ZPX, param: Vec::new(), size: 2, cycles: 3}),
+            x if x == OPCODE::AND_ZP as u8 => Ok(Instruction { opc: OPCODE::AND_ZP, param: Vec::new(), size: 2, cycles: 3}),
+            x if x == OPCODE::AND_ZPX as u8 => Ok(Instruction { opc: OPCODE::AND_ZPX, param: Vec::new(), size: 2, cycles: 4}),
             x if x == OPCODE::AND_A as u8 => Ok(Instruction { opc: OPCODE::AND_A, param: Vec::new(), size: 3, cycles: 4}),
             x if x == OPCODE::AND_AX as u8 => Ok(Instruction { opc: OPCODE::AND_AX, param: Vec::new(), size: 3, cycles: 4}),
             x if x == OPCODE::AND_AY as u8 => Ok(Instruction { opc: OPCODE::AND_AY, param: Vec::new(), size: 3, cycles: 4}),
@@ -563,8 +1327,8 @@ impl TryFrom<u8> for Instruction {
             x if x == OPCODE::NOP as u8 => Ok(Instruction { opc: OPCODE::NOP, param: Vec::new(), size: 1, cycles: 2}),
 
             x if x == OPCODE::ORA_I as u8 => Ok(Instruction { opc: OPCODE::ORA_I, param: Vec::new(), size: 2, cycles: 2}),
-            x if x == OPCODE::ORA_ZP as u8 => Ok(Instruction { opc: OPCODE::ORA_ZP, param: Vec::new(), size: 2, cycles: 2}),
-            x if x == OPCODE::ORA_ZPX as u8 => Ok(Instruction { opc: OPCODE::ORA_ZPX, param: Vec::new(), size: 2, cycles: 3}),
+            x if x == OPCODE::ORA_ZP as u8 => Ok(Instruction { opc: OPCODE::ORA_ZP, param: Vec::new(), size: 2, cycles: 3}),
+            x if x == OPCODE::ORA_ZPX as u8 => Ok(Instruction { opc: OPCODE::ORA_ZPX, param: Vec::new(), size: 2, cycles: 4}),
             x if x == OPCODE::ORA_A as u8 => Ok(Instruction { opc: OPCODE::ORA_A, param: Vec::new(), size: 3, cycles: 4}),
             x if x == OPCODE::ORA_AX as u8 => Ok(Instruction { opc: OPCODE::ORA_AX, param: Vec::new(), size: 3, cycles: 4}),
             x if x == OPCODE::ORA_AY as u8 => Ok(Instruction { opc: OPCODE::ORA_AY, param: Vec::new(), size: 3, cycles: 4}),
@@ -600,6 +1364,7 @@ impl TryFrom<u8> for Instruction {
             x if x == OPCODE::SBC_ZPX as u8 => Ok(Instruction { opc: OPCODE::SBC_ZPX, param: Vec::new(), size: 2, cycles: 4}),
             x if x == OPCODE::SBC_A as u8 => Ok(Instruction { opc: OPCODE::SBC_A, param: Vec::new(), size: 3, cycles: 4}),
             x if x == OPCODE::SBC_AX as u8 => Ok(Instruction { opc: OPCODE::SBC_AX, param: Vec::new(), size: 3, cycles: 4}),
+            x if x == OPCODE::SBC_AY as u8 => Ok(Instruction { opc: OPCODE::SBC_AY, param: Vec::new(), size: 3, cycles: 4}),
             x if x == OPCODE::SBC_IX as u8 => Ok(Instruction { opc: OPCODE::SBC_IX, param: Vec::new(), size: 2, cycles: 6}),
             x if x == OPCODE::SBC_IY as u8 => Ok(Instruction { opc: OPCODE::SBC_IY, param: Vec::new(), size: 2, cycles: 5}),
 
@@ -609,6 +1374,122 @@ impl TryFrom<u8> for Instruction {
             x if x == OPCODE::PLA as u8 => Ok(Instruction { opc: OPCODE::PLA, param: Vec::new(), size: 1, cycles: 4}),
             x if x == OPCODE::PHP as u8 => Ok(Instruction { opc: OPCODE::PHP, param: Vec::new(), size: 1, cycles: 3}),
             x if x == OPCODE::PLP as u8 => Ok(Instruction { opc: OPCODE::PLP, param: Vec::new(), size: 1, cycles: 4}),
+
+            x if x == OPCODE::SLO_ZP as u8 => Ok(Instruction { opc: OPCODE::SLO_ZP, param: Vec::new(), size: 2, cycles: 5 }),
+            x if x == OPCODE::SLO_ZPX as u8 => Ok(Instruction { opc: OPCODE::SLO_ZPX, param: Vec::new(), size: 2, cycles: 6 }),
+            x if x == OPCODE::SLO_A as u8 => Ok(Instruction { opc: OPCODE::SLO_A, param: Vec::new(), size: 3, cycles: 6 }),
+            x if x == OPCODE::SLO_AX as u8 => Ok(Instruction { opc: OPCODE::SLO_AX, param: Vec::new(), size: 3, cycles: 7 }),
+            x if x == OPCODE::SLO_AY as u8 => Ok(Instruction { opc: OPCODE::SLO_AY, param: Vec::new(), size: 3, cycles: 7 }),
+            x if x == OPCODE::SLO_IX as u8 => Ok(Instruction { opc: OPCODE::SLO_IX, param: Vec::new(), size: 2, cycles: 8 }),
+            x if x == OPCODE::SLO_IY as u8 => Ok(Instruction { opc: OPCODE::SLO_IY, param: Vec::new(), size: 2, cycles: 8 }),
+
+            x if x == OPCODE::RLA_ZP as u8 => Ok(Instruction { opc: OPCODE::RLA_ZP, param: Vec::new(), size: 2, cycles: 5 }),
+            x if x == OPCODE::RLA_ZPX as u8 => Ok(Instruction { opc: OPCODE::RLA_ZPX, param: Vec::new(), size: 2, cycles: 6 }),
+            x if x == OPCODE::RLA_A as u8 => Ok(Instruction { opc: OPCODE::RLA_A, param: Vec::new(), size: 3, cycles: 6 }),
+            x if x == OPCODE::RLA_AX as u8 => Ok(Instruction { opc: OPCODE::RLA_AX, param: Vec::new(), size: 3, cycles: 7 }),
+            x if x == OPCODE::RLA_AY as u8 => Ok(Instruction { opc: OPCODE::RLA_AY, param: Vec::new(), size: 3, cycles: 7 }),
+            x if x == OPCODE::RLA_IX as u8 => Ok(Instruction { opc: OPCODE::RLA_IX, param: Vec::new(), size: 2, cycles: 8 }),
+            x if x == OPCODE::RLA_IY as u8 => Ok(Instruction { opc: OPCODE::RLA_IY, param: Vec::new(), size: 2, cycles: 8 }),
+
+            x if x == OPCODE::SRE_ZP as u8 => Ok(Instruction { opc: OPCODE::SRE_ZP, param: Vec::new(), size: 2, cycles: 5 }),
+            x if x == OPCODE::SRE_ZPX as u8 => Ok(Instruction { opc: OPCODE::SRE_ZPX, param: Vec::new(), size: 2, cycles: 6 }),
+            x if x == OPCODE::SRE_A as u8 => Ok(Instruction { opc: OPCODE::SRE_A, param: Vec::new(), size: 3, cycles: 6 }),
+            x if x == OPCODE::SRE_AX as u8 => Ok(Instruction { opc: OPCODE::SRE_AX, param: Vec::new(), size: 3, cycles: 7 }),
+            x if x == OPCODE::SRE_AY as u8 => Ok(Instruction { opc: OPCODE::SRE_AY, param: Vec::new(), size: 3, cycles: 7 }),
+            x if x == OPCODE::SRE_IX as u8 => Ok(Instruction { opc: OPCODE::SRE_IX, param: Vec::new(), size: 2, cycles: 8 }),
+            x if x == OPCODE::SRE_IY as u8 => Ok(Instruction { opc: OPCODE::SRE_IY, param: Vec::new(), size: 2, cycles: 8 }),
+
+            x if x == OPCODE::RRA_ZP as u8 => Ok(Instruction { opc: OPCODE::RRA_ZP, param: Vec::new(), size: 2, cycles: 5 }),
+            x if x == OPCODE::RRA_ZPX as u8 => Ok(Instruction { opc: OPCODE::RRA_ZPX, param: Vec::new(), size: 2, cycles: 6 }),
+            x if x == OPCODE::RRA_A as u8 => Ok(Instruction { opc: OPCODE::RRA_A, param: Vec::new(), size: 3, cycles: 6 }),
+            x if x == OPCODE::RRA_AX as u8 => Ok(Instruction { opc: OPCODE::RRA_AX, param: Vec::new(), size: 3, cycles: 7 }),
+            x if x == OPCODE::RRA_AY as u8 => Ok(Instruction { opc: OPCODE::RRA_AY, param: Vec::new(), size: 3, cycles: 7 }),
+            x if x == OPCODE::RRA_IX as u8 => Ok(Instruction { opc: OPCODE::RRA_IX, param: Vec::new(), size: 2, cycles: 8 }),
+            x if x == OPCODE::RRA_IY as u8 => Ok(Instruction { opc: OPCODE::RRA_IY, param: Vec::new(), size: 2, cycles: 8 }),
+
+            x if x == OPCODE::DCP_ZP as u8 => Ok(Instruction { opc: OPCODE::DCP_ZP, param: Vec::new(), size: 2, cycles: 5 }),
+            x if x == OPCODE::DCP_ZPX as u8 => Ok(Instruction { opc: OPCODE::DCP_ZPX, param: Vec::new(), size: 2, cycles: 6 }),
+            x if x == OPCODE::DCP_A as u8 => Ok(Instruction { opc: OPCODE::DCP_A, param: Vec::new(), size: 3, cycles: 6 }),
+            x if x == OPCODE::DCP_AX as u8 => Ok(Instruction { opc: OPCODE::DCP_AX, param: Vec::new(), size: 3, cycles: 7 }),
+            x if x == OPCODE::DCP_AY as u8 => Ok(Instruction { opc: OPCODE::DCP_AY, param: Vec::new(), size: 3, cycles: 7 }),
+            x if x == OPCODE::DCP_IX as u8 => Ok(Instruction { opc: OPCODE::DCP_IX, param: Vec::new(), size: 2, cycles: 8 }),
+            x if x == OPCODE::DCP_IY as u8 => Ok(Instruction { opc: OPCODE::DCP_IY, param: Vec::new(), size: 2, cycles: 8 }),
+
+            x if x == OPCODE::ISC_ZP as u8 => Ok(Instruction { opc: OPCODE::ISC_ZP, param: Vec::new(), size: 2, cycles: 5 }),
+            x if x == OPCODE::ISC_ZPX as u8 => Ok(Instruction { opc: OPCODE::ISC_ZPX, param: Vec::new(), size: 2, cycles: 6 }),
+            x if x == OPCODE::ISC_A as u8 => Ok(Instruction { opc: OPCODE::ISC_A, param: Vec::new(), size: 3, cycles: 6 }),
+            x if x == OPCODE::ISC_AX as u8 => Ok(Instruction { opc: OPCODE::ISC_AX, param: Vec::new(), size: 3, cycles: 7 }),
+            x if x == OPCODE::ISC_AY as u8 => Ok(Instruction { opc: OPCODE::ISC_AY, param: Vec::new(), size: 3, cycles: 7 }),
+            x if x == OPCODE::ISC_IX as u8 => Ok(Instruction { opc: OPCODE::ISC_IX, param: Vec::new(), size: 2, cycles: 8 }),
+            x if x == OPCODE::ISC_IY as u8 => Ok(Instruction { opc: OPCODE::ISC_IY, param: Vec::new(), size: 2, cycles: 8 }),
+
+            x if x == OPCODE::SAX_ZP as u8 => Ok(Instruction { opc: OPCODE::SAX_ZP, param: Vec::new(), size: 2, cycles: 3 }),
+            x if x == OPCODE::SAX_ZPY as u8 => Ok(Instruction { opc: OPCODE::SAX_ZPY, param: Vec::new(), size: 2, cycles: 4 }),
+            x if x == OPCODE::SAX_A as u8 => Ok(Instruction { opc: OPCODE::SAX_A, param: Vec::new(), size: 3, cycles: 4 }),
+            x if x == OPCODE::SAX_IX as u8 => Ok(Instruction { opc: OPCODE::SAX_IX, param: Vec::new(), size: 2, cycles: 6 }),
+
+            x if x == OPCODE::LAX_ZP as u8 => Ok(Instruction { opc: OPCODE::LAX_ZP, param: Vec::new(), size: 2, cycles: 3 }),
+            x if x == OPCODE::LAX_ZPY as u8 => Ok(Instruction { opc: OPCODE::LAX_ZPY, param: Vec::new(), size: 2, cycles: 4 }),
+            x if x == OPCODE::LAX_A as u8 => Ok(Instruction { opc: OPCODE::LAX_A, param: Vec::new(), size: 3, cycles: 4 }),
+            x if x == OPCODE::LAX_AY as u8 => Ok(Instruction { opc: OPCODE::LAX_AY, param: Vec::new(), size: 3, cycles: 4 }),
+            x if x == OPCODE::LAX_IX as u8 => Ok(Instruction { opc: OPCODE::LAX_IX, param: Vec::new(), size: 2, cycles: 6 }),
+            x if x == OPCODE::LAX_IY as u8 => Ok(Instruction { opc: OPCODE::LAX_IY, param: Vec::new(), size: 2, cycles: 5 }),
+
+            x if x == OPCODE::ANC_I as u8 => Ok(Instruction { opc: OPCODE::ANC_I, param: Vec::new(), size: 2, cycles: 2 }),
+            x if x == OPCODE::ANC_I_2B as u8 => Ok(Instruction { opc: OPCODE::ANC_I_2B, param: Vec::new(), size: 2, cycles: 2 }),
+            x if x == OPCODE::ALR_I as u8 => Ok(Instruction { opc: OPCODE::ALR_I, param: Vec::new(), size: 2, cycles: 2 }),
+            x if x == OPCODE::ARR_I as u8 => Ok(Instruction { opc: OPCODE::ARR_I, param: Vec::new(), size: 2, cycles: 2 }),
+            x if x == OPCODE::SBX_I as u8 => Ok(Instruction { opc: OPCODE::SBX_I, param: Vec::new(), size: 2, cycles: 2 }),
+            x if x == OPCODE::SBC_I_EB as u8 => Ok(Instruction { opc: OPCODE::SBC_I_EB, param: Vec::new(), size: 2, cycles: 2 }),
+            x if x == OPCODE::LAS_AY as u8 => Ok(Instruction { opc: OPCODE::LAS_AY, param: Vec::new(), size: 3, cycles: 4 }),
+            x if x == OPCODE::ANE_I as u8 => Ok(Instruction { opc: OPCODE::ANE_I, param: Vec::new(), size: 2, cycles: 2 }),
+            x if x == OPCODE::LXA_I as u8 => Ok(Instruction { opc: OPCODE::LXA_I, param: Vec::new(), size: 2, cycles: 2 }),
+            x if x == OPCODE::SHA_AY as u8 => Ok(Instruction { opc: OPCODE::SHA_AY, param: Vec::new(), size: 3, cycles: 5 }),
+            x if x == OPCODE::SHA_IY as u8 => Ok(Instruction { opc: OPCODE::SHA_IY, param: Vec::new(), size: 2, cycles: 6 }),
+            x if x == OPCODE::SHX_AY as u8 => Ok(Instruction { opc: OPCODE::SHX_AY, param: Vec::new(), size: 3, cycles: 5 }),
+            x if x == OPCODE::SHY_AX as u8 => Ok(Instruction { opc: OPCODE::SHY_AX, param: Vec::new(), size: 3, cycles: 5 }),
+            x if x == OPCODE::TAS_AY as u8 => Ok(Instruction { opc: OPCODE::TAS_AY, param: Vec::new(), size: 3, cycles: 5 }),
+
+            x if x == OPCODE::NOP_1A as u8 => Ok(Instruction { opc: OPCODE::NOP_1A, param: Vec::new(), size: 1, cycles: 2 }),
+            x if x == OPCODE::NOP_3A as u8 => Ok(Instruction { opc: OPCODE::NOP_3A, param: Vec::new(), size: 1, cycles: 2 }),
+            x if x == OPCODE::NOP_5A as u8 => Ok(Instruction { opc: OPCODE::NOP_5A, param: Vec::new(), size: 1, cycles: 2 }),
+            x if x == OPCODE::NOP_7A as u8 => Ok(Instruction { opc: OPCODE::NOP_7A, param: Vec::new(), size: 1, cycles: 2 }),
+            x if x == OPCODE::NOP_DA as u8 => Ok(Instruction { opc: OPCODE::NOP_DA, param: Vec::new(), size: 1, cycles: 2 }),
+            x if x == OPCODE::NOP_FA as u8 => Ok(Instruction { opc: OPCODE::NOP_FA, param: Vec::new(), size: 1, cycles: 2 }),
+            x if x == OPCODE::NOP_I_80 as u8 => Ok(Instruction { opc: OPCODE::NOP_I_80, param: Vec::new(), size: 2, cycles: 2 }),
+            x if x == OPCODE::NOP_I_82 as u8 => Ok(Instruction { opc: OPCODE::NOP_I_82, param: Vec::new(), size: 2, cycles: 2 }),
+            x if x == OPCODE::NOP_I_89 as u8 => Ok(Instruction { opc: OPCODE::NOP_I_89, param: Vec::new(), size: 2, cycles: 2 }),
+            x if x == OPCODE::NOP_I_C2 as u8 => Ok(Instruction { opc: OPCODE::NOP_I_C2, param: Vec::new(), size: 2, cycles: 2 }),
+            x if x == OPCODE::NOP_I_E2 as u8 => Ok(Instruction { opc: OPCODE::NOP_I_E2, param: Vec::new(), size: 2, cycles: 2 }),
+            x if x == OPCODE::NOP_ZP_04 as u8 => Ok(Instruction { opc: OPCODE::NOP_ZP_04, param: Vec::new(), size: 2, cycles: 3 }),
+            x if x == OPCODE::NOP_ZP_44 as u8 => Ok(Instruction { opc: OPCODE::NOP_ZP_44, param: Vec::new(), size: 2, cycles: 3 }),
+            x if x == OPCODE::NOP_ZP_64 as u8 => Ok(Instruction { opc: OPCODE::NOP_ZP_64, param: Vec::new(), size: 2, cycles: 3 }),
+            x if x == OPCODE::NOP_ZPX_14 as u8 => Ok(Instruction { opc: OPCODE::NOP_ZPX_14, param: Vec::new(), size: 2, cycles: 4 }),
+            x if x == OPCODE::NOP_ZPX_34 as u8 => Ok(Instruction { opc: OPCODE::NOP_ZPX_34, param: Vec::new(), size: 2, cycles: 4 }),
+            x if x == OPCODE::NOP_ZPX_54 as u8 => Ok(Instruction { opc: OPCODE::NOP_ZPX_54, param: Vec::new(), size: 2, cycles: 4 }),
+            x if x == OPCODE::NOP_ZPX_74 as u8 => Ok(Instruction { opc: OPCODE::NOP_ZPX_74, param: Vec::new(), size: 2, cycles: 4 }),
+            x if x == OPCODE::NOP_ZPX_D4 as u8 => Ok(Instruction { opc: OPCODE::NOP_ZPX_D4, param: Vec::new(), size: 2, cycles: 4 }),
+            x if x == OPCODE::NOP_ZPX_F4 as u8 => Ok(Instruction { opc: OPCODE::NOP_ZPX_F4, param: Vec::new(), size: 2, cycles: 4 }),
+            x if x == OPCODE::NOP_A_0C as u8 => Ok(Instruction { opc: OPCODE::NOP_A_0C, param: Vec::new(), size: 3, cycles: 4 }),
+            x if x == OPCODE::NOP_AX_1C as u8 => Ok(Instruction { opc: OPCODE::NOP_AX_1C, param: Vec::new(), size: 3, cycles: 4 }),
+            x if x == OPCODE::NOP_AX_3C as u8 => Ok(Instruction { opc: OPCODE::NOP_AX_3C, param: Vec::new(), size: 3, cycles: 4 }),
+            x if x == OPCODE::NOP_AX_5C as u8 => Ok(Instruction { opc: OPCODE::NOP_AX_5C, param: Vec::new(), size: 3, cycles: 4 }),
+            x if x == OPCODE::NOP_AX_7C as u8 => Ok(Instruction { opc: OPCODE::NOP_AX_7C, param: Vec::new(), size: 3, cycles: 4 }),
+            x if x == OPCODE::NOP_AX_DC as u8 => Ok(Instruction { opc: OPCODE::NOP_AX_DC, param: Vec::new(), size: 3, cycles: 4 }),
+            x if x == OPCODE::NOP_AX_FC as u8 => Ok(Instruction { opc: OPCODE::NOP_AX_FC, param: Vec::new(), size: 3, cycles: 4 }),
+
+            x if x == OPCODE::JAM_02 as u8 => Ok(Instruction { opc: OPCODE::JAM_02, param: Vec::new(), size: 1, cycles: 2 }),
+            x if x == OPCODE::JAM_12 as u8 => Ok(Instruction { opc: OPCODE::JAM_12, param: Vec::new(), size: 1, cycles: 2 }),
+            x if x == OPCODE::JAM_22 as u8 => Ok(Instruction { opc: OPCODE::JAM_22, param: Vec::new(), size: 1, cycles: 2 }),
+            x if x == OPCODE::JAM_32 as u8 => Ok(Instruction { opc: OPCODE::JAM_32, param: Vec::new(), size: 1, cycles: 2 }),
+            x if x == OPCODE::JAM_42 as u8 => Ok(Instruction { opc: OPCODE::JAM_42, param: Vec::new(), size: 1, cycles: 2 }),
+            x if x == OPCODE::JAM_52 as u8 => Ok(Instruction { opc: OPCODE::JAM_52, param: Vec::new(), size: 1, cycles: 2 }),
+            x if x == OPCODE::JAM_62 as u8 => Ok(Instruction { opc: OPCODE::JAM_62, param: Vec::new(), size: 1, cycles: 2 }),
+            x if x == OPCODE::JAM_72 as u8 => Ok(Instruction { opc: OPCODE::JAM_72, param: Vec::new(), size: 1, cycles: 2 }),
+            x if x == OPCODE::JAM_92 as u8 => Ok(Instruction { opc: OPCODE::JAM_92, param: Vec::new(), size: 1, cycles: 2 }),
+            x if x == OPCODE::JAM_B2 as u8 => Ok(Instruction { opc: OPCODE::JAM_B2, param: Vec::new(), size: 1, cycles: 2 }),
+            x if x == OPCODE::JAM_D2 as u8 => Ok(Instruction { opc: OPCODE::JAM_D2, param: Vec::new(), size: 1, cycles: 2 }),
+            x if x == OPCODE::JAM_F2 as u8 => Ok(Instruction { opc: OPCODE::JAM_F2, param: Vec::new(), size: 1, cycles: 2 }),
             _ => Err(())
         }
     }
@@ -0,0 +1,22 @@
+pub use crate::cpu::CPU;
+pub use crate::instructions::{Instruction, OPCODE};
+pub use crate::memory::Memory;
+
+pub mod alu;
+pub mod assembler;
+pub mod bus;
+pub mod cpu;
+pub mod debugger;
+pub mod disassembler;
+pub mod dispatch;
+pub mod error;
+pub mod history;
+pub mod instructions;
+pub mod loader;
+pub mod mapper;
+pub mod memory;
+pub mod register;
+pub mod run;
+pub mod savestate;
+mod tests;
+pub mod trace;
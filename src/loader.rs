@@ -0,0 +1,360 @@
+use crate::error::LoadError;
+use crate::memory::Memory;
+
+/* bytes that go to one address, the address can be beyond $FFFF until the image is loaded */
+#[derive(Clone, Debug, PartialEq)]
+pub struct Segment {
+    pub addr: u32,
+    pub data: Vec<u8>,
+}
+
+/* the segments of a program file and the entry point if the file names one */
+#[derive(Clone, Debug, PartialEq, Default)]
+pub struct Image {
+    pub segments: Vec<Segment>,
+    pub start: Option<u32>,
+}
+
+impl Image {
+    /* a raw .bin file, placed at addr */
+    pub fn raw(data: &[u8], addr: u16) -> Image {
+        Image {
+            segments: vec![Segment {
+                addr: addr as u32,
+                data: data.to_vec(),
+            }],
+            start: None,
+        }
+    }
+}
+
+/*
+    Copies the segments into the memory, bypassing the memory map so ROM can be filled.
+    Nothing is written if a segment leaves the address space or two segments overlap.
+*/
+pub fn load(memory: &mut Memory, image: &Image) -> Result<(), LoadError> {
+    let mut segments: Vec<&Segment> = image
+        .segments
+        .iter()
+        .filter(|segment| !segment.data.is_empty())
+        .collect();
+    segments.sort_by_key(|segment| segment.addr);
+    for segment in &segments {
+        if segment.addr as u64 + segment.data.len() as u64 > u16::MAX as u64 + 1 {
+            return Err(LoadError::OutOfRange {
+                addr: segment.addr,
+                len: segment.data.len(),
+            });
+        }
+    }
+    for pair in segments.windows(2) {
+        if pair[0].addr as usize + pair[0].data.len() > pair[1].addr as usize {
+            return Err(LoadError::Overlap {
+                addr: pair[1].addr as u16,
+            });
+        }
+    }
+    for segment in segments {
+        for (i, value) in segment.data.iter().enumerate() {
+            memory.write_byte(&(segment.addr as u16 + i as u16), value);
+        }
+    }
+    Ok(())
+}
+
+/* the bytes of a hex string, None if it has an odd length or a non hex digit */
+fn hex_bytes(text: &str) -> Option<Vec<u8>> {
+    if !text.len().is_multiple_of(2) || !text.is_ascii() {
+        return None;
+    }
+    (0..text.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(&text[i..i + 2], 16).ok())
+        .collect()
+}
+
+/*
+    Appends to the last segment if the record continues it, records are usually consecutive.
+    The end is computed in u64, a segment can end right at or beyond $FFFFFFFF.
+*/
+pub(crate) fn push_data(image: &mut Image, addr: u32, data: &[u8]) {
+    if let Some(last) = image.segments.last_mut() {
+        if last.addr as u64 + last.data.len() as u64 == addr as u64 {
+            last.data.extend_from_slice(data);
+            return;
+        }
+    }
+    image.segments.push(Segment {
+        addr,
+        data: data.to_vec(),
+    });
+}
+
+/*
+    Intel HEX: ":" count, address, type, data and a checksum that makes the sum of all bytes zero.
+    Extended segment and linear addresses are applied, the start address records set the entry point.
+*/
+pub fn parse_intel_hex(text: &str) -> Result<Image, LoadError> {
+    let mut image = Image::default();
+    /* added to the address of every data record, set by the types 02 and 04 */
+    let mut base: u32 = 0;
+    for (index, line) in text.lines().enumerate() {
+        let line_number = index + 1;
+        let line = line.trim();
+        if line.is_empty() {
+            continue;
+        }
+        let bytes = line
+            .strip_prefix(':')
+            .and_then(hex_bytes)
+            .ok_or(LoadError::Malformed { line: line_number })?;
+        if bytes.len() < 5 || bytes.len() != bytes[0] as usize + 5 {
+            return Err(LoadError::Malformed { line: line_number });
+        }
+        let (record, checksum) = bytes.split_at(bytes.len() - 1);
+        let expected = record
+            .iter()
+            .fold(0u8, |sum, byte| sum.wrapping_add(*byte))
+            .wrapping_neg();
+        if expected != checksum[0] {
+            return Err(LoadError::Checksum {
+                line: line_number,
+                expected,
+                got: checksum[0],
+            });
+        }
+        let addr = ((record[1] as u32) << 8) | record[2] as u32;
+        let data = &record[4..];
+        let word = |data: &[u8]| ((data[0] as u32) << 8) | data[1] as u32;
+        match (record[3], data.len()) {
+            (0x00, _) => push_data(&mut image, base + addr, data),
+            (0x01, _) => return Ok(image),
+            (0x02, 2) => base = word(data) << 4,
+            (0x03, 4) => image.start = Some((word(data) << 4) + word(&data[2..])),
+            (0x04, 2) => base = word(data) << 16,
+            (0x05, 4) => image.start = Some((word(data) << 16) | word(&data[2..])),
+            _ => return Err(LoadError::Malformed { line: line_number }),
+        }
+    }
+    /* files without an end of file record are accepted */
+    Ok(image)
+}
+
+/*
+    Motorola S-record: "S" type, count, address, data and the ones' complement of the byte sum.
+    S1/S2/S3 carry data with 16/24/32 bit addresses, S7/S8/S9 the entry point, S0 and S5/S6 are skipped.
+*/
+pub fn parse_srec(text: &str) -> Result<Image, LoadError> {
+    let mut image = Image::default();
+    for (index, line) in text.lines().enumerate() {
+        let line_number = index + 1;
+        let line = line.trim();
+        if line.is_empty() {
+            continue;
+        }
+        let malformed = LoadError::Malformed { line: line_number };
+        if !line.is_ascii() {
+            return Err(malformed);
+        }
+        let record_type = line
+            .strip_prefix('S')
+            .and_then(|rest| rest.chars().next())
+            .ok_or(malformed)?;
+        let bytes = hex_bytes(&line[2..]).ok_or(malformed)?;
+        if bytes.is_empty() || bytes.len() != bytes[0] as usize + 1 {
+            return Err(malformed);
+        }
+        let (record, checksum) = bytes.split_at(bytes.len() - 1);
+        let expected = !record.iter().fold(0u8, |sum, byte| sum.wrapping_add(*byte));
+        if expected != checksum[0] {
+            return Err(LoadError::Checksum {
+                line: line_number,
+                expected,
+                got: checksum[0],
+            });
+        }
+        let addr_len = match record_type {
+            '0' | '1' | '5' | '9' => 2,
+            '2' | '6' | '8' => 3,
+            '3' | '7' => 4,
+            _ => return Err(malformed),
+        };
+        if record.len() < addr_len + 1 {
+            return Err(malformed);
+        }
+        let addr = record[1..=addr_len]
+            .iter()
+            .fold(0u32, |addr, byte| (addr << 8) | *byte as u32);
+        let data = &record[addr_len + 1..];
+        match record_type {
+            '1' | '2' | '3' => push_data(&mut image, addr, data),
+            '7' | '8' | '9' => image.start = Some(addr),
+            _ => {}
+        }
+    }
+    Ok(image)
+}
+
+/* cc65/ld65 .prg: the load address as a little endian word followed by the data */
+pub fn parse_prg(data: &[u8]) -> Result<Image, LoadError> {
+    if data.len() < 2 {
+        return Err(LoadError::Truncated);
+    }
+    let addr = data[0] as u16 | ((data[1] as u16) << 8);
+    Ok(Image::raw(&data[2..], addr))
+}
+
+/* reads the o65 file front to back */
+struct O65Reader<'a> {
+    data: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> O65Reader<'a> {
+    fn byte(&mut self) -> Result<u8, LoadError> {
+        let byte = *self.data.get(self.pos).ok_or(LoadError::Truncated)?;
+        self.pos += 1;
+        Ok(byte)
+    }
+    fn word(&mut self) -> Result<u16, LoadError> {
+        Ok(self.byte()? as u16 | ((self.byte()? as u16) << 8))
+    }
+    fn bytes(&mut self, len: usize) -> Result<&'a [u8], LoadError> {
+        let bytes = self
+            .data
+            .get(self.pos..self.pos + len)
+            .ok_or(LoadError::Truncated)?;
+        self.pos += len;
+        Ok(bytes)
+    }
+}
+
+const O65_MAGIC: [u8; 6] = [0x01, 0x00, b'o', b'6', b'5', 0x00];
+/* 65816 code, pagewise relocation, 32 bit sizes and chained files are not supported */
+const O65_UNSUPPORTED_MODES: u16 = 0x8000 | 0x4000 | 0x2000 | 0x0400;
+
+/*
+    o65 relocatable object, relocated so the text segment starts at base and the data segment follows it.
+    bss is relocated behind the data, the zero page segment keeps its addresses.
+    Files that still reference undefined labels need a linker and are rejected.
+*/
+pub fn parse_o65(file: &[u8], base: u16) -> Result<Image, LoadError> {
+    let mut reader = O65Reader { data: file, pos: 0 };
+    if reader.bytes(O65_MAGIC.len())? != O65_MAGIC {
+        return Err(LoadError::BadHeader);
+    }
+    let mode = reader.word()?;
+    if mode & O65_UNSUPPORTED_MODES != 0 {
+        return Err(LoadError::Unsupported {
+            feature: "o65 mode",
+        });
+    }
+    let text_base = reader.word()?;
+    let text_len = reader.word()?;
+    let data_base = reader.word()?;
+    let data_len = reader.word()?;
+    let bss_base = reader.word()?;
+    let _bss_len = reader.word()?;
+    let _zp_base = reader.word()?;
+    let _zp_len = reader.word()?;
+    let _stack = reader.word()?;
+    /* header options: a length byte that counts itself, terminated by a zero length */
+    loop {
+        let len = reader.byte()?;
+        if len == 0 {
+            break;
+        }
+        reader.bytes(len as usize - 1)?;
+    }
+    let mut text = reader.bytes(text_len as usize)?.to_vec();
+    let mut data = reader.bytes(data_len as usize)?.to_vec();
+    if reader.word()? != 0 {
+        return Err(LoadError::Unsupported {
+            feature: "o65 undefined references",
+        });
+    }
+
+    let data_addr = base.wrapping_add(text_len);
+    let bss_addr = data_addr.wrapping_add(data_len);
+    /* what gets added to an address in segment 1 (absolute) to 5 (zero page) */
+    let delta = [
+        0,
+        0,
+        base.wrapping_sub(text_base),
+        data_addr.wrapping_sub(data_base),
+        bss_addr.wrapping_sub(bss_base),
+        0,
+    ];
+    relocate_o65(&mut reader, &mut text, &delta)?;
+    relocate_o65(&mut reader, &mut data, &delta)?;
+    Ok(Image {
+        segments: vec![
+            Segment {
+                addr: base as u32,
+                data: text,
+            },
+            Segment {
+                addr: data_addr as u32,
+                data,
+            },
+        ],
+        start: Some(base as u32),
+    })
+}
+
+/* applies one relocation table to its segment */
+fn relocate_o65(
+    reader: &mut O65Reader,
+    segment: &mut [u8],
+    delta: &[u16; 6],
+) -> Result<(), LoadError> {
+    /* offsets are relative to the previous entry, the first one to the byte before the segment */
+    let mut pos: isize = -1;
+    loop {
+        match reader.byte()? {
+            0 => return Ok(()),
+            255 => {
+                pos += 254;
+                continue;
+            }
+            step => pos += step as isize,
+        }
+        let offset = pos as usize;
+        let kind = reader.byte()?;
+        let segment_id = (kind & 0x07) as usize;
+        if segment_id == 0 || segment_id >= delta.len() {
+            return Err(LoadError::Unsupported {
+                feature: "o65 relocation segment",
+            });
+        }
+        let delta = delta[segment_id];
+        match kind & 0xE0 {
+            /* WORD */
+            0x80 => {
+                let bytes = segment
+                    .get_mut(offset..offset + 2)
+                    .ok_or(LoadError::Truncated)?;
+                let value = (bytes[0] as u16 | ((bytes[1] as u16) << 8)).wrapping_add(delta);
+                bytes[0] = value as u8;
+                bytes[1] = (value >> 8) as u8;
+            }
+            /* HIGH, the low byte the carry comes from is stored in the table */
+            0x40 => {
+                let low = reader.byte()?;
+                let byte = segment.get_mut(offset).ok_or(LoadError::Truncated)?;
+                let value = (((*byte as u16) << 8) | low as u16).wrapping_add(delta);
+                *byte = (value >> 8) as u8;
+            }
+            /* LOW */
+            0x20 => {
+                let byte = segment.get_mut(offset).ok_or(LoadError::Truncated)?;
+                *byte = byte.wrapping_add(delta as u8);
+            }
+            _ => {
+                return Err(LoadError::Unsupported {
+                    feature: "o65 relocation type",
+                })
+            }
+        }
+    }
+}
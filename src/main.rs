@@ -1,20 +1,17 @@
 extern crate core;
 
-use crate::cpu::CPU;
-use crate::instructions::{Instruction, OPCODE};
-use crate::memory::Memory;
-use crate::OPCODE::LDA_A;
-
-mod cpu;
-mod instructions;
-mod memory;
-mod register;
-mod tests;
+use simulator6502::run::{RunLimits, StopReason};
+use simulator6502::OPCODE::LDA_A;
+use simulator6502::{Instruction, Memory, CPU};
 
 fn main() {
     let mut cpu: CPU = CPU::new(0, 0, 0, 0);
     let mut mem: Memory = Memory::new();
-    mem.push_back_ins(Instruction::new(LDA_A, &vec![0x12, 0x23]));
+    mem.push_back_ins(Instruction::new(LDA_A, &vec![0x12, 0x23]).unwrap());
     mem.physical_mem[0x2312] = 0xFF;
-    cpu.execute(&mut mem);
+    /* the memory behind the program is zeroed, the BRK there ends it */
+    let summary = cpu.run(&mut mem, &RunLimits::new().brk());
+    if let StopReason::Halted(error) = summary.reason {
+        eprintln!("{}", error);
+    }
 }
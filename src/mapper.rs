@@ -0,0 +1,290 @@
+use crate::error::SnapshotError;
+
+/*
+    Bank switching in front of the Memory, for programs that do not fit into 64K.
+    A mapper answers the reads of the addresses it banks and watches the writes for its control registers.
+*/
+pub trait Mapper {
+    /* the byte the current banks put at the address, None leaves the address to the Memory */
+    fn read(&self, addr: u16) -> Option<u8>;
+    fn write(&mut self, addr: u16, value: u8) -> MapperWrite;
+    /* the bank state, enough to bring the mapper back to it with restore */
+    fn snapshot(&self) -> Vec<u8>;
+    /* whether restore would accept the snapshot, without changing anything */
+    fn check_snapshot(&self, snapshot: &[u8]) -> Result<(), SnapshotError>;
+    fn restore(&mut self, snapshot: &[u8]) -> Result<(), SnapshotError>;
+}
+
+/* what a mapper did with a write */
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum MapperWrite {
+    /* not an address of the mapper, the Memory handles the write */
+    PASS,
+    /* a control register or banked RAM took the write */
+    TAKEN,
+    /* the address shows banked ROM, the ROM write policy of the Memory applies */
+    ROM,
+}
+
+/* size of the window a bank is swapped into */
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum BankSize {
+    KB8,
+    KB16,
+}
+
+impl BankSize {
+    pub fn bytes(self) -> usize {
+        match self {
+            BankSize::KB8 => 0x2000,
+            BankSize::KB16 => 0x4000,
+        }
+    }
+}
+
+/* pads the image with $FF up to a whole number of banks, an empty image still gets one bank */
+fn pad_to_banks(mut image: Vec<u8>, size: BankSize) -> Vec<u8> {
+    let banks = image.len().div_ceil(size.bytes()).max(1);
+    image.resize(banks * size.bytes(), 0xFF);
+    image
+}
+
+fn expect_len(snapshot: &[u8], expected: usize) -> Result<(), SnapshotError> {
+    if snapshot.len() != expected {
+        return Err(SnapshotError {
+            expected,
+            got: snapshot.len(),
+        });
+    }
+    Ok(())
+}
+
+/* one switchable ROM window, the bank is selected by writing its number to a latch register */
+pub struct Latch {
+    rom: Vec<u8>,
+    window: u16,
+    size: BankSize,
+    register: u16,
+    bank: u8,
+}
+
+impl Latch {
+    pub fn new(rom: Vec<u8>, window: u16, size: BankSize, register: u16) -> Latch {
+        Latch {
+            rom: pad_to_banks(rom, size),
+            window,
+            size,
+            register,
+            bank: 0,
+        }
+    }
+    pub fn bank(&self) -> u8 {
+        self.bank
+    }
+    fn banks(&self) -> usize {
+        self.rom.len() / self.size.bytes()
+    }
+    /* offset of the address into the window, None outside of it */
+    fn offset(&self, addr: u16) -> Option<usize> {
+        let offset = addr.wrapping_sub(self.window) as usize;
+        if addr < self.window || offset >= self.size.bytes() {
+            return None;
+        }
+        Some(offset)
+    }
+}
+
+impl Mapper for Latch {
+    fn read(&self, addr: u16) -> Option<u8> {
+        let offset = self.offset(addr)?;
+        Some(self.rom[self.bank as usize * self.size.bytes() + offset])
+    }
+
+    /* unused high bits of the bank number are ignored, the banks mirror */
+    fn write(&mut self, addr: u16, value: u8) -> MapperWrite {
+        if addr == self.register {
+            self.bank = (value as usize % self.banks()) as u8;
+            return MapperWrite::TAKEN;
+        }
+        match self.offset(addr) {
+            Some(_) => MapperWrite::ROM,
+            None => MapperWrite::PASS,
+        }
+    }
+
+    fn snapshot(&self) -> Vec<u8> {
+        vec![self.bank]
+    }
+
+    fn check_snapshot(&self, snapshot: &[u8]) -> Result<(), SnapshotError> {
+        expect_len(snapshot, 1)
+    }
+
+    fn restore(&mut self, snapshot: &[u8]) -> Result<(), SnapshotError> {
+        self.check_snapshot(snapshot)?;
+        self.bank = (snapshot[0] as usize % self.banks()) as u8;
+        Ok(())
+    }
+}
+
+/*
+    UxROM: a switchable 16K bank at $8000-$BFFF and the last bank fixed at $C000-$FFFF.
+    Any write to $8000-$FFFF selects the switchable bank.
+*/
+pub struct UxRom {
+    rom: Vec<u8>,
+    bank: u8,
+}
+
+impl UxRom {
+    pub fn new(rom: Vec<u8>) -> UxRom {
+        UxRom {
+            rom: pad_to_banks(rom, BankSize::KB16),
+            bank: 0,
+        }
+    }
+    pub fn bank(&self) -> u8 {
+        self.bank
+    }
+    fn banks(&self) -> usize {
+        self.rom.len() / BankSize::KB16.bytes()
+    }
+}
+
+impl Mapper for UxRom {
+    fn read(&self, addr: u16) -> Option<u8> {
+        let offset = (addr & 0x3FFF) as usize;
+        match addr {
+            0x8000..=0xBFFF => Some(self.rom[self.bank as usize * BankSize::KB16.bytes() + offset]),
+            0xC000..=0xFFFF => Some(self.rom[(self.banks() - 1) * BankSize::KB16.bytes() + offset]),
+            _ => None,
+        }
+    }
+
+    fn write(&mut self, addr: u16, value: u8) -> MapperWrite {
+        if addr < 0x8000 {
+            return MapperWrite::PASS;
+        }
+        self.bank = (value as usize % self.banks()) as u8;
+        MapperWrite::TAKEN
+    }
+
+    fn snapshot(&self) -> Vec<u8> {
+        vec![self.bank]
+    }
+
+    fn check_snapshot(&self, snapshot: &[u8]) -> Result<(), SnapshotError> {
+        expect_len(snapshot, 1)
+    }
+
+    fn restore(&mut self, snapshot: &[u8]) -> Result<(), SnapshotError> {
+        self.check_snapshot(snapshot)?;
+        self.bank = (snapshot[0] as usize % self.banks()) as u8;
+        Ok(())
+    }
+}
+
+/* slot value of the MmuTable that leaves the 8K slot to the Memory */
+pub const UNBANKED: u8 = 0xFF;
+
+const SLOTS: usize = 8;
+
+/*
+    MMU table: the address space is split into eight 8K slots, each one can show any 8K bank.
+    Banks are numbered ROM first, then RAM. The slot registers are eight bytes starting at `registers`,
+    writing UNBANKED to one gives the slot back to the Memory.
+*/
+pub struct MmuTable {
+    rom: Vec<u8>,
+    ram: Vec<u8>,
+    registers: u16,
+    slots: [u8; SLOTS],
+}
+
+impl MmuTable {
+    pub fn new(rom: Vec<u8>, ram_banks: usize, registers: u16) -> MmuTable {
+        MmuTable {
+            rom: pad_to_banks(rom, BankSize::KB8),
+            ram: vec![0; ram_banks * BankSize::KB8.bytes()],
+            registers,
+            slots: [UNBANKED; SLOTS],
+        }
+    }
+    pub fn slot(&self, slot: usize) -> u8 {
+        self.slots[slot]
+    }
+    pub fn select(&mut self, slot: usize, bank: u8) {
+        self.slots[slot] = if bank == UNBANKED {
+            UNBANKED
+        } else {
+            (bank as usize % self.banks()) as u8
+        };
+    }
+    fn banks(&self) -> usize {
+        (self.rom.len() + self.ram.len()) / BankSize::KB8.bytes()
+    }
+    fn register(&self, addr: u16) -> Option<usize> {
+        let register = addr.wrapping_sub(self.registers) as usize;
+        if addr >= self.registers && register < SLOTS {
+            Some(register)
+        } else {
+            None
+        }
+    }
+    /* offset of the address into ROM followed by RAM, None when its slot is unbanked */
+    fn offset(&self, addr: u16) -> Option<usize> {
+        let bank = self.slots[addr as usize / BankSize::KB8.bytes()];
+        if bank == UNBANKED {
+            return None;
+        }
+        Some(bank as usize * BankSize::KB8.bytes() + (addr & 0x1FFF) as usize)
+    }
+}
+
+impl Mapper for MmuTable {
+    fn read(&self, addr: u16) -> Option<u8> {
+        let offset = self.offset(addr)?;
+        if offset < self.rom.len() {
+            Some(self.rom[offset])
+        } else {
+            Some(self.ram[offset - self.rom.len()])
+        }
+    }
+
+    fn write(&mut self, addr: u16, value: u8) -> MapperWrite {
+        if let Some(register) = self.register(addr) {
+            self.select(register, value);
+            return MapperWrite::TAKEN;
+        }
+        match self.offset(addr) {
+            /* banked ROM does not change */
+            Some(offset) if offset < self.rom.len() => MapperWrite::ROM,
+            Some(offset) => {
+                let rom_len = self.rom.len();
+                self.ram[offset - rom_len] = value;
+                MapperWrite::TAKEN
+            }
+            None => MapperWrite::PASS,
+        }
+    }
+
+    /* the slot registers followed by the banked RAM */
+    fn snapshot(&self) -> Vec<u8> {
+        let mut snapshot = self.slots.to_vec();
+        snapshot.extend_from_slice(&self.ram);
+        snapshot
+    }
+
+    fn check_snapshot(&self, snapshot: &[u8]) -> Result<(), SnapshotError> {
+        expect_len(snapshot, SLOTS + self.ram.len())
+    }
+
+    fn restore(&mut self, snapshot: &[u8]) -> Result<(), SnapshotError> {
+        self.check_snapshot(snapshot)?;
+        for (slot, &bank) in snapshot[..SLOTS].iter().enumerate() {
+            self.select(slot, bank);
+        }
+        self.ram.copy_from_slice(&snapshot[SLOTS..]);
+        Ok(())
+    }
+}
@@ -1,3 +1,7 @@
+use crate::bus::{Bus, Device};
+use crate::error::SaveStateError;
+use crate::mapper::{Mapper, MapperWrite};
+use crate::savestate::{self, StateReader};
 use crate::Instruction;
 
 /* Zero-page start and end address */
@@ -24,36 +28,381 @@ pub const VECTOR_ADDR_NMI_LOW: u16 = 0xFFFA;
 pub const VECTOR_ADDR_NMI_HIGH: u16 = 0xFFFB;
 pub const VECTOR_ADDR_RESET_LOW: u16 = 0xFFFC;
 pub const VECTOR_ADDR_RESET_HIGH: u16 = 0xFFFD;
-pub const VECTOR_ADDR_IRQ_BRK_LOW: u16 = 0xFFFD;
+pub const VECTOR_ADDR_IRQ_BRK_LOW: u16 = 0xFFFE;
 pub const VECTOR_ADDR_IRQ_BRK_HIGH: u16 = 0xFFFF;
 
+/* what the cpu finds at a page of the address space */
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum Region {
+    RAM,
+    /* reads the contents, writes do not change them */
+    ROM,
+    /* reads and writes go to the device attached at the address */
+    IO,
+    /* nothing answers, reads return the open bus value */
+    UNMAPPED,
+}
+
+/* what happens to a write into a ROM region */
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum RomWrites {
+    IGNORE,
+    /* the write is dropped and reported as a bus fault */
+    REPORT,
+}
+
+/* the byte a write_byte replaced, written back to undo it */
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct UndoWrite {
+    pub addr: u16,
+    pub old: u8,
+}
+
+const PAGE_COUNT: usize = 0x100;
+
+struct AttachedDevice {
+    start: u16,
+    end: u16,
+    device: Box<dyn Device>,
+}
+
 pub struct Memory {
     pub physical_mem: [u8; u16::MAX as usize + 1],
     instruction_pos: u16,
+    /* the memory map in 256 byte pages */
+    regions: [Region; PAGE_COUNT],
+    devices: Vec<AttachedDevice>,
+    rom_writes: RomWrites,
+    /* the last value on the data bus, unmapped reads return it */
+    open_bus: u8,
+    fault: Option<u16>,
+    /* bank switching, it sees the accesses before the memory map */
+    mapper: Option<Box<dyn Mapper>>,
+    /* the writes to the backing store since the last take_journal, None when nothing is recorded */
+    journal: Option<Vec<UndoWrite>>,
+    /* the writes the mapper took, a change tells that its bank state may have changed */
+    mapper_writes: u64,
 }
 
 impl Memory {
+    /* flat RAM over the whole address space */
     pub fn new() -> Memory {
-        let mem = Memory {
+        Memory {
             physical_mem: [0; u16::MAX as usize + 1],
             instruction_pos: PROGRAM_ROM_S,
-        };
+            regions: [Region::RAM; PAGE_COUNT],
+            devices: Vec::new(),
+            rom_writes: RomWrites::IGNORE,
+            open_bus: 0,
+            fault: None,
+            mapper: None,
+            journal: None,
+            mapper_writes: 0,
+        }
+    }
+    /* RAM up to PROGRAM_RAM_E, the I/O window and ROM from PROGRAM_ROM_S up to the vectors */
+    pub fn with_standard_map() -> Memory {
+        let mut mem = Memory::new();
+        mem.map(ZP_S, PROGRAM_RAM_E, Region::RAM);
+        mem.map(MEMORY_MAPPED_IO_S, MEMORY_MAPPED_IO_E, Region::IO);
+        mem.map(PROGRAM_ROM_S, VECTOR_ADDR_IRQ_BRK_HIGH, Region::ROM);
         mem
     }
+    /* maps all pages touched by start..=end to the region */
+    pub fn map(&mut self, start: u16, end: u16, region: Region) {
+        for page in (start >> 8)..=(end >> 8) {
+            self.regions[page as usize] = region;
+        }
+    }
+    pub fn region(&self, addr: u16) -> Region {
+        self.regions[(addr >> 8) as usize]
+    }
+    /* attaches the device to start..=end, the pages it touches become I/O */
+    pub fn attach(&mut self, start: u16, end: u16, device: Box<dyn Device>) {
+        self.map(start, end, Region::IO);
+        self.devices.push(AttachedDevice { start, end, device });
+    }
+    pub fn set_mapper(&mut self, mapper: Box<dyn Mapper>) {
+        self.mapper = Some(mapper);
+    }
+    pub fn mapper(&self) -> Option<&dyn Mapper> {
+        self.mapper.as_deref()
+    }
+    pub fn mapper_mut(&mut self) -> Option<&mut (dyn Mapper + 'static)> {
+        self.mapper.as_deref_mut()
+    }
+    pub fn set_rom_writes(&mut self, rom_writes: RomWrites) {
+        self.rom_writes = rom_writes;
+    }
+    fn device(&self, addr: u16) -> Option<&AttachedDevice> {
+        self.devices
+            .iter()
+            .find(|attached| attached.start <= addr && addr <= attached.end)
+    }
+    fn device_mut(&mut self, addr: u16) -> Option<&mut AttachedDevice> {
+        self.devices
+            .iter_mut()
+            .find(|attached| attached.start <= addr && addr <= attached.end)
+    }
 
+    /* program loading ignores the memory map, ROM has to be filled somehow */
     pub fn push_back_ins(&mut self, ins: Instruction) {
         let layout = ins.convert_to_mem_layout();
         for i in 0..*ins.size() {
-            self.physical_mem[(i + self.instruction_pos) as usize] = layout[i as usize];
+            self.physical_mem[self.instruction_pos.wrapping_add(i) as usize] = layout[i as usize];
         }
-        self.instruction_pos += ins.size();
+        self.instruction_pos = self.instruction_pos.wrapping_add(*ins.size());
     }
 
+    /* direct access to the backing store, bypasses the memory map and the devices */
     pub fn read_byte(&self, addr: &u16) -> &u8 {
         &self.physical_mem[*addr as usize]
     }
 
     pub fn write_byte(&mut self, addr: &u16, value: &u8) {
+        if let Some(journal) = &mut self.journal {
+            journal.push(UndoWrite {
+                addr: *addr,
+                old: self.physical_mem[*addr as usize],
+            });
+        }
         self.physical_mem[*addr as usize] = *value;
     }
+
+    /*
+        Starts or stops recording the writes of write_byte and of the cpu into RAM.
+        Devices and the bank state of a mapper are not recorded, see mapper_writes.
+    */
+    pub fn set_journaling(&mut self, enabled: bool) {
+        match (enabled, self.journal.is_some()) {
+            (true, false) => self.journal = Some(Vec::new()),
+            (false, true) => self.journal = None,
+            _ => {}
+        }
+    }
+    pub fn journaling(&self) -> bool {
+        self.journal.is_some()
+    }
+    /* swaps the whole journal, None stops recording */
+    pub fn replace_journal(&mut self, journal: Option<Vec<UndoWrite>>) -> Option<Vec<UndoWrite>> {
+        std::mem::replace(&mut self.journal, journal)
+    }
+    pub fn take_journal(&mut self) -> Vec<UndoWrite> {
+        match &mut self.journal {
+            Some(journal) => std::mem::take(journal),
+            None => Vec::new(),
+        }
+    }
+    /* counts every write a mapper took (control registers, banked RAM) since the memory was created */
+    pub fn mapper_writes(&self) -> u64 {
+        self.mapper_writes
+    }
+    /* writes the old bytes back, newest first, without recording them */
+    pub fn undo(&mut self, writes: &[UndoWrite]) {
+        for write in writes.iter().rev() {
+            self.physical_mem[write.addr as usize] = write.old;
+        }
+    }
+}
+
+/* the memory part of a save state that has been read and checked, see savestate::load */
+pub(crate) struct MemoryState<'a> {
+    physical_mem: &'a [u8],
+    regions: [Region; PAGE_COUNT],
+    rom_writes: RomWrites,
+    open_bus: u8,
+    fault: Option<u16>,
+    instruction_pos: u16,
+    mapper: Option<&'a [u8]>,
+    devices: Vec<&'a [u8]>,
+}
+
+/* the memory part of a save state, see savestate::save */
+impl Memory {
+    pub(crate) fn save_state(&self, out: &mut Vec<u8>) {
+        out.extend_from_slice(&self.physical_mem);
+        out.extend(self.regions.iter().map(|region| match region {
+            Region::RAM => 0,
+            Region::ROM => 1,
+            Region::IO => 2,
+            Region::UNMAPPED => 3,
+        }));
+        out.push(match self.rom_writes {
+            RomWrites::IGNORE => 0,
+            RomWrites::REPORT => 1,
+        });
+        out.push(self.open_bus);
+        out.push(self.fault.is_some() as u8);
+        savestate::put_u16(out, self.fault.unwrap_or(0));
+        savestate::put_u16(out, self.instruction_pos);
+        out.push(self.mapper.is_some() as u8);
+        if let Some(mapper) = &self.mapper {
+            savestate::put_block(out, &mapper.snapshot());
+        }
+        savestate::put_u16(out, self.devices.len() as u16);
+        for attached in &self.devices {
+            savestate::put_u16(out, attached.start);
+            savestate::put_u16(out, attached.end);
+            savestate::put_block(out, &attached.device.snapshot());
+        }
+    }
+
+    /* reads the memory part and checks it against this memory, nothing is changed yet */
+    pub(crate) fn read_state<'a>(
+        &self,
+        reader: &mut StateReader<'a>,
+    ) -> Result<MemoryState<'a>, SaveStateError> {
+        let physical_mem = reader.bytes(self.physical_mem.len())?;
+        let mut regions = [Region::RAM; PAGE_COUNT];
+        for (region, byte) in regions.iter_mut().zip(reader.bytes(PAGE_COUNT)?) {
+            *region = match byte {
+                0 => Region::RAM,
+                1 => Region::ROM,
+                2 => Region::IO,
+                3 => Region::UNMAPPED,
+                _ => return Err(SaveStateError::Invalid("region")),
+            };
+        }
+        let rom_writes = match reader.u8()? {
+            0 => RomWrites::IGNORE,
+            1 => RomWrites::REPORT,
+            _ => return Err(SaveStateError::Invalid("rom writes")),
+        };
+        let open_bus = reader.u8()?;
+        let fault = match (reader.u8()?, reader.u16()?) {
+            (0, _) => None,
+            (1, addr) => Some(addr),
+            _ => return Err(SaveStateError::Invalid("bus fault")),
+        };
+        let instruction_pos = reader.u16()?;
+        let mapper = match reader.u8()? {
+            0 => None,
+            1 => Some(reader.block()?),
+            _ => return Err(SaveStateError::Invalid("mapper")),
+        };
+        match (&self.mapper, mapper) {
+            (Some(current), Some(snapshot)) => current.check_snapshot(snapshot)?,
+            (None, None) => {}
+            _ => return Err(SaveStateError::Mismatch("mapper")),
+        }
+        let count = reader.u16()? as usize;
+        if count != self.devices.len() {
+            return Err(SaveStateError::Mismatch("devices"));
+        }
+        let mut devices = Vec::with_capacity(count);
+        for attached in &self.devices {
+            let (start, end) = (reader.u16()?, reader.u16()?);
+            if (start, end) != (attached.start, attached.end) {
+                return Err(SaveStateError::Mismatch("devices"));
+            }
+            let snapshot = reader.block()?;
+            attached.device.check_snapshot(snapshot)?;
+            devices.push(snapshot);
+        }
+        Ok(MemoryState {
+            physical_mem,
+            regions,
+            rom_writes,
+            open_bus,
+            fault,
+            instruction_pos,
+            mapper,
+            devices,
+        })
+    }
+
+    /* applies a state read_state has accepted */
+    pub(crate) fn apply_state(&mut self, state: MemoryState) -> Result<(), SaveStateError> {
+        if let (Some(mapper), Some(snapshot)) = (&mut self.mapper, state.mapper) {
+            mapper.restore(snapshot)?;
+        }
+        for (attached, snapshot) in self.devices.iter_mut().zip(state.devices) {
+            attached.device.restore(snapshot)?;
+        }
+        self.physical_mem.copy_from_slice(state.physical_mem);
+        self.regions = state.regions;
+        self.rom_writes = state.rom_writes;
+        self.open_bus = state.open_bus;
+        self.fault = state.fault;
+        self.instruction_pos = state.instruction_pos;
+        Ok(())
+    }
+}
+
+impl Default for Memory {
+    fn default() -> Memory {
+        Memory::new()
+    }
+}
+
+/* the cpu side of the memory, goes through the memory map */
+impl Bus for Memory {
+    fn read(&mut self, addr: u16) -> u8 {
+        if let Some(value) = self.mapper.as_ref().and_then(|mapper| mapper.read(addr)) {
+            self.open_bus = value;
+            return value;
+        }
+        let value = match self.region(addr) {
+            Region::RAM | Region::ROM => self.physical_mem[addr as usize],
+            Region::IO => match self.device_mut(addr) {
+                Some(attached) => {
+                    let offset = addr - attached.start;
+                    attached.device.read(offset)
+                }
+                None => self.open_bus,
+            },
+            Region::UNMAPPED => self.open_bus,
+        };
+        self.open_bus = value;
+        value
+    }
+
+    fn write(&mut self, addr: u16, value: u8) {
+        self.open_bus = value;
+        let mapped = match &mut self.mapper {
+            Some(mapper) => mapper.write(addr, value),
+            None => MapperWrite::PASS,
+        };
+        let region = match mapped {
+            MapperWrite::PASS => self.region(addr),
+            MapperWrite::TAKEN => {
+                self.mapper_writes += 1;
+                return;
+            }
+            MapperWrite::ROM => Region::ROM,
+        };
+        match region {
+            Region::RAM => self.write_byte(&addr, &value),
+            Region::ROM => {
+                if self.rom_writes == RomWrites::REPORT {
+                    self.fault = Some(addr);
+                }
+            }
+            Region::IO => {
+                if let Some(attached) = self.device_mut(addr) {
+                    let offset = addr - attached.start;
+                    attached.device.write(offset, value);
+                }
+            }
+            Region::UNMAPPED => {}
+        }
+    }
+
+    fn peek(&self, addr: u16) -> u8 {
+        if let Some(value) = self.mapper.as_ref().and_then(|mapper| mapper.read(addr)) {
+            return value;
+        }
+        match self.region(addr) {
+            Region::RAM | Region::ROM => self.physical_mem[addr as usize],
+            Region::IO => match self.device(addr) {
+                Some(attached) => attached.device.peek(addr - attached.start),
+                None => self.open_bus,
+            },
+            Region::UNMAPPED => self.open_bus,
+        }
+    }
+
+    fn take_fault(&mut self) -> Option<u16> {
+        self.fault.take()
+    }
 }
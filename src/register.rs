@@ -9,3 +9,67 @@ impl<T: Copy + Add<Output = T> + Sub<Output = T>> Register<T> {
         Register { value }
     }
 }
+
+/* bit masks of the flags inside the processor status register */
+#[derive(Copy, Clone, Debug, PartialEq)]
+#[repr(u8)]
+pub enum Flag {
+    C = 0x01,
+    Z = 0x02,
+    I = 0x04,
+    D = 0x08,
+    B = 0x10,
+    U = 0x20,
+    V = 0x40,
+    N = 0x80,
+}
+
+/*
+    Processor status register (P).
+    B and the unused bit only exist on the data bus when P is pushed, they are never stored.
+*/
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct StatusRegister {
+    value: u8,
+}
+
+impl StatusRegister {
+    pub fn new(value: u8) -> StatusRegister {
+        let mut status = StatusRegister { value: 0 };
+        status.load(value);
+        status
+    }
+    pub fn get(&self, flag: Flag) -> bool {
+        self.value & flag as u8 != 0
+    }
+    pub fn set(&mut self, flag: Flag) {
+        self.value |= flag as u8;
+    }
+    pub fn clear(&mut self, flag: Flag) {
+        self.value &= !(flag as u8);
+    }
+    pub fn update(&mut self, flag: Flag, value: bool) {
+        if value {
+            self.set(flag);
+        } else {
+            self.clear(flag);
+        }
+    }
+    /* sets N and Z according to a result byte */
+    pub fn set_nz(&mut self, value: u8) {
+        self.update(Flag::Z, value == 0);
+        self.update(Flag::N, value & 0x80 != 0);
+    }
+    /* the byte pushed onto the stack, the unused bit is always set */
+    pub fn to_byte(self, b_flag: bool) -> u8 {
+        let mut value = self.value | Flag::U as u8;
+        if b_flag {
+            value |= Flag::B as u8;
+        }
+        value
+    }
+    /* loads a byte pulled from the stack, B and the unused bit are ignored */
+    pub fn load(&mut self, value: u8) {
+        self.value = value & !(Flag::B as u8 | Flag::U as u8);
+    }
+}
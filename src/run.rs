@@ -0,0 +1,137 @@
+use crate::bus::Bus;
+use crate::cpu::{StepOutcome, CPU};
+use crate::debugger::Access;
+use crate::error::CpuError;
+use crate::OPCODE;
+
+/* when CPU::run has to give control back, everything that is None or false never stops it */
+#[derive(Copy, Clone, Debug, PartialEq, Default)]
+pub struct RunLimits {
+    /* clock cycles the run may spend, the instruction that reaches the budget is still finished */
+    pub cycles: Option<u64>,
+    pub instructions: Option<u64>,
+    /* stop as soon as the program counter arrives at this address */
+    pub pc: Option<u16>,
+    /* stop in front of a BRK instead of executing it, unless an interrupt is serviced first */
+    pub brk: bool,
+}
+
+impl RunLimits {
+    pub fn new() -> RunLimits {
+        RunLimits::default()
+    }
+    pub fn cycles(mut self, cycles: u64) -> RunLimits {
+        self.cycles = Some(cycles);
+        self
+    }
+    pub fn instructions(mut self, instructions: u64) -> RunLimits {
+        self.instructions = Some(instructions);
+        self
+    }
+    pub fn pc(mut self, pc: u16) -> RunLimits {
+        self.pc = Some(pc);
+        self
+    }
+    pub fn brk(mut self) -> RunLimits {
+        self.brk = true;
+        self
+    }
+}
+
+/* why a run ended */
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum StopReason {
+    CycleBudget,
+    InstructionCount,
+    PcReached(u16),
+    /* the program counter points to a BRK that was not executed */
+    Brk {
+        addr: u16,
+    },
+    /* the predicate of run_until returned true */
+    Predicate,
+    /* Debugger::run stopped in front of the instruction at addr */
+    Breakpoint {
+        id: usize,
+        addr: u16,
+    },
+    /* Debugger::run stopped after the instruction that accessed addr, value is the byte transferred */
+    Watchpoint {
+        id: usize,
+        addr: u16,
+        access: Access,
+        value: u8,
+    },
+    /* WAI without an interrupt that could wake the cpu up */
+    Waiting,
+    /* STP stopped the clock */
+    Stopped,
+    /* an illegal opcode, a JAM or a bus fault halted the cpu */
+    Halted(CpuError),
+}
+
+/* the outcome of a run and what it cost */
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct RunSummary {
+    pub reason: StopReason,
+    pub instructions: u64,
+    pub cycles: u64,
+}
+
+impl CPU {
+    pub fn run<B: Bus + ?Sized>(&mut self, bus: &mut B, limits: &RunLimits) -> RunSummary {
+        self.run_until(bus, limits, |_| false)
+    }
+
+    /* like run, but also stops once the predicate holds after an instruction or interrupt */
+    pub fn run_until<B: Bus + ?Sized>(
+        &mut self,
+        bus: &mut B,
+        limits: &RunLimits,
+        mut predicate: impl FnMut(&CPU) -> bool,
+    ) -> RunSummary {
+        let start_cycles = self.clock_cycles_elapsed();
+        let mut instructions = 0;
+        let reason = loop {
+            let cycles = self.clock_cycles_elapsed() - start_cycles;
+            if limits.cycles.is_some_and(|budget| cycles >= budget) {
+                break StopReason::CycleBudget;
+            }
+            if limits
+                .instructions
+                .is_some_and(|count| instructions >= count)
+            {
+                break StopReason::InstructionCount;
+            }
+            let pc = self.program_counter().value;
+            /* a pending interrupt runs first, the BRK is only reached after its handler returned */
+            if limits.brk
+                && self.halted().is_none()
+                && !self.interrupt_pending()
+                && bus.peek(pc) == OPCODE::BRK.byte()
+            {
+                break StopReason::Brk { addr: pc };
+            }
+            match self.execute(bus) {
+                Ok(StepOutcome::Executed { .. }) => instructions += 1,
+                Ok(StepOutcome::Interrupt) => {}
+                Ok(StepOutcome::Waiting) => break StopReason::Waiting,
+                Ok(StepOutcome::Stopped) => break StopReason::Stopped,
+                Err(error) => break StopReason::Halted(error),
+            }
+            if let Some(target) = limits.pc {
+                if self.program_counter().value == target {
+                    break StopReason::PcReached(target);
+                }
+            }
+            if predicate(self) {
+                break StopReason::Predicate;
+            }
+        };
+        RunSummary {
+            reason,
+            instructions,
+            cycles: self.clock_cycles_elapsed() - start_cycles,
+        }
+    }
+}
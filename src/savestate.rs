@@ -0,0 +1,184 @@
+use crate::cpu::{CpuState, Variant, CPU};
+use crate::error::{CpuError, SaveStateError};
+use crate::memory::Memory;
+use crate::register::StatusRegister;
+
+const MAGIC: [u8; 4] = *b"S65S";
+/* bumped whenever the layout changes, load only accepts versions it knows */
+pub const VERSION: u16 = 1;
+
+/*
+    Everything about the cpu and the memory in a little endian byte string:
+    magic, version, cpu state, memory contents and map, mapper and device snapshots.
+    The mapper and the devices themselves are not saved, load expects the memory to have the same ones.
+*/
+pub fn save(cpu: &CPU, memory: &Memory) -> Vec<u8> {
+    let mut out = Vec::new();
+    out.extend_from_slice(&MAGIC);
+    put_u16(&mut out, VERSION);
+    save_cpu(&mut out, cpu);
+    memory.save_state(&mut out);
+    out
+}
+
+/*
+    The cpu and the memory are only changed once the whole state has been read and checked,
+    including the snapshots of the mapper and the devices.
+*/
+pub fn load(cpu: &mut CPU, memory: &mut Memory, data: &[u8]) -> Result<(), SaveStateError> {
+    let mut reader = StateReader { data, pos: 0 };
+    if reader.bytes(MAGIC.len())? != MAGIC {
+        return Err(SaveStateError::BadMagic);
+    }
+    let version = reader.u16()?;
+    if version != VERSION {
+        return Err(SaveStateError::UnsupportedVersion(version));
+    }
+    let (variant, trap_undocumented, state) = load_cpu(&mut reader)?;
+    let memory_state = memory.read_state(&mut reader)?;
+    if reader.pos != data.len() {
+        return Err(SaveStateError::Invalid("trailing bytes"));
+    }
+    memory.apply_state(memory_state)?;
+    cpu.set_variant(variant);
+    cpu.set_trap_undocumented(trap_undocumented);
+    cpu.restore_state(&state);
+    Ok(())
+}
+
+pub(crate) fn put_u16(out: &mut Vec<u8>, value: u16) {
+    out.extend_from_slice(&value.to_le_bytes());
+}
+pub(crate) fn put_u32(out: &mut Vec<u8>, value: u32) {
+    out.extend_from_slice(&value.to_le_bytes());
+}
+/* a snapshot with its length in front */
+pub(crate) fn put_block(out: &mut Vec<u8>, block: &[u8]) {
+    put_u32(out, block.len() as u32);
+    out.extend_from_slice(block);
+}
+
+/* reads the save state front to back */
+pub(crate) struct StateReader<'a> {
+    data: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> StateReader<'a> {
+    pub(crate) fn bytes(&mut self, len: usize) -> Result<&'a [u8], SaveStateError> {
+        let bytes = self
+            .data
+            .get(self.pos..self.pos + len)
+            .ok_or(SaveStateError::Truncated)?;
+        self.pos += len;
+        Ok(bytes)
+    }
+    pub(crate) fn u8(&mut self) -> Result<u8, SaveStateError> {
+        Ok(self.bytes(1)?[0])
+    }
+    pub(crate) fn u16(&mut self) -> Result<u16, SaveStateError> {
+        let bytes = self.bytes(2)?;
+        Ok(u16::from_le_bytes([bytes[0], bytes[1]]))
+    }
+    pub(crate) fn u32(&mut self) -> Result<u32, SaveStateError> {
+        let bytes = self.bytes(4)?;
+        Ok(u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]))
+    }
+    pub(crate) fn u64(&mut self) -> Result<u64, SaveStateError> {
+        Ok(self.u32()? as u64 | ((self.u32()? as u64) << 32))
+    }
+    pub(crate) fn block(&mut self) -> Result<&'a [u8], SaveStateError> {
+        let len = self.u32()?;
+        self.bytes(len as usize)
+    }
+}
+
+/* bits of the cpu flags byte */
+const IRQ_LINE: u8 = 0x01;
+const NMI_LINE: u8 = 0x02;
+const NMI_PENDING: u8 = 0x04;
+const WAITING: u8 = 0x08;
+const STOPPED: u8 = 0x10;
+const TRAP_UNDOCUMENTED: u8 = 0x20;
+
+fn save_cpu(out: &mut Vec<u8>, cpu: &CPU) {
+    let state = cpu.state();
+    out.push(match cpu.variant() {
+        Variant::NMOS6502 => 0,
+        Variant::CMOS65C02 => 1,
+        Variant::ROCKWELL65C02 => 2,
+        Variant::WDC65C02 => 3,
+    });
+    out.extend_from_slice(&[
+        state.a,
+        state.x,
+        state.y,
+        state.ins,
+        state.s,
+        state.p.to_byte(false),
+    ]);
+    put_u16(out, state.program_counter);
+    out.extend_from_slice(&state.clock_cycles_elapsed.to_le_bytes());
+    let flags = [
+        (state.irq_line, IRQ_LINE),
+        (state.nmi_line, NMI_LINE),
+        (state.nmi_pending, NMI_PENDING),
+        (state.waiting, WAITING),
+        (state.stopped, STOPPED),
+        (cpu.trap_undocumented(), TRAP_UNDOCUMENTED),
+    ];
+    out.push(
+        flags
+            .iter()
+            .filter(|(set, _)| *set)
+            .fold(0, |byte, (_, bit)| byte | bit),
+    );
+    /* the error, its opcode (if it has one) and its address */
+    let (kind, opcode, addr) = match state.halted {
+        None => (0, 0, 0),
+        Some(CpuError::IllegalOpcode { opcode, addr }) => (1, opcode, addr),
+        Some(CpuError::Jammed { opcode, addr }) => (2, opcode, addr),
+        Some(CpuError::BusFault { addr }) => (3, 0, addr),
+    };
+    out.extend_from_slice(&[kind, opcode]);
+    put_u16(out, addr);
+}
+
+fn load_cpu(reader: &mut StateReader) -> Result<(Variant, bool, CpuState), SaveStateError> {
+    let variant = match reader.u8()? {
+        0 => Variant::NMOS6502,
+        1 => Variant::CMOS65C02,
+        2 => Variant::ROCKWELL65C02,
+        3 => Variant::WDC65C02,
+        _ => return Err(SaveStateError::Invalid("variant")),
+    };
+    let registers = reader.bytes(6)?;
+    let program_counter = reader.u16()?;
+    let clock_cycles_elapsed = reader.u64()?;
+    let flags = reader.u8()?;
+    let (kind, opcode, addr) = (reader.u8()?, reader.u8()?, reader.u16()?);
+    let halted = match kind {
+        0 => None,
+        1 => Some(CpuError::IllegalOpcode { opcode, addr }),
+        2 => Some(CpuError::Jammed { opcode, addr }),
+        3 => Some(CpuError::BusFault { addr }),
+        _ => return Err(SaveStateError::Invalid("cpu error")),
+    };
+    let state = CpuState {
+        a: registers[0],
+        x: registers[1],
+        y: registers[2],
+        ins: registers[3],
+        s: registers[4],
+        p: StatusRegister::new(registers[5]),
+        program_counter,
+        clock_cycles_elapsed,
+        irq_line: flags & IRQ_LINE != 0,
+        nmi_line: flags & NMI_LINE != 0,
+        nmi_pending: flags & NMI_PENDING != 0,
+        halted,
+        waiting: flags & WAITING != 0,
+        stopped: flags & STOPPED != 0,
+    };
+    Ok((variant, flags & TRAP_UNDOCUMENTED != 0, state))
+}
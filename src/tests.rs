@@ -1,77 +1,114 @@
-/*TODO: Write test cases for the lsr ins. and the ora ins. */
+/*TODO: Write test cases for the ora ins. */
 
 #[cfg(test)]
 mod tests {
-    use crate::memory::ZP_S;
+    use std::cell::RefCell;
+    use std::rc::Rc;
+    use std::time::Instant;
+
+    use crate::alu;
+    use crate::assembler;
+    use crate::bus::{Bus, Device};
+    use crate::cpu::{AddressingMode, StepOutcome, Variant};
+    use crate::debugger::{Access, Compare, Condition, CpuRegister, Debugger};
+    use crate::disassembler::{self, Disassembler};
+    use crate::dispatch;
+    use crate::error::{
+        AsmError, AsmErrorKind, CpuError, InstructionError, LoadError, SaveStateError,
+        SnapshotError,
+    };
+    use crate::history::History;
+    use crate::loader::{self, Image, Segment};
+    use crate::mapper::{BankSize, Latch, Mapper, MmuTable, UxRom, UNBANKED};
+    use crate::memory::{Region, RomWrites, UndoWrite, ZP_S};
+    use crate::register::{Flag, StatusRegister};
+    use crate::run::{RunLimits, StopReason};
+    use crate::savestate;
+    use crate::trace::{self, Tracer};
+    use crate::OPCODE::LDA_A;
     use crate::OPCODE::{
         ADC_A, ADC_AX, ADC_AY, ADC_I, ADC_IX, ADC_IY, ADC_ZP, ADC_ZPX, AND_A, AND_AX, AND_AY,
         AND_I, AND_IX, AND_IY, AND_ZP, AND_ZPX, ASL_A, ASL_ACC, ASL_AX, ASL_ZP, ASL_ZPX, BCC, BCS,
-        BEQ, BIT_A, BIT_ZP, BMI, BNE, BPL, BVC, BVS, CMP_I, CPX_A, CPX_I, CPX_ZP, CPY_A, CPY_I,
-        CPY_ZP, DEC_A, DEC_AX, DEC_ZP, DEC_ZPX, DEX, INC_A, INC_AX, INC_ZP, INC_ZPX, INX, JMP_A,
-        JMP_I, LDA_AX, LDA_AY, LDA_I, LDA_IX, LDA_IY, LDA_ZP, LDA_ZPX, LDX_A, LDX_AY, LDX_I,
-        LDX_ZP, LDX_ZPY, LDY_A, LDY_AX, LDY_I, LDY_ZP, LDY_ZPX, STA_A, STA_AX, STA_AY, STA_IX,
-        STA_IY, STA_ZP, STA_ZPX, STX_A, STX_ZP, STX_ZPY, STY_A, STY_ZP, STY_ZPX,
+        BEQ, BIT_A, BIT_ZP, BMI, BNE, BPL, BRK, BVC, BVS, CLC, CLD, CLI, CLV, CMP_AY, CMP_I, CPX_A,
+        CPX_I, CPX_ZP, CPY_A, CPY_I, CPY_ZP, DEC_A, DEC_AX, DEC_ZP, DEC_ZPX, DEX, DEY, EOR_A,
+        EOR_AX, EOR_AY, EOR_I, EOR_IX, EOR_IY, EOR_ZP, EOR_ZPX, INC_A, INC_AX, INC_ZP, INC_ZPX,
+        INX, INY, JMP_A, JMP_I, JSR, LDA_AX, LDA_AY, LDA_I, LDA_IX, LDA_IY, LDA_ZP, LDA_ZPX, LDX_A,
+        LDX_AY, LDX_I, LDX_ZP, LDX_ZPY, LDY_A, LDY_AX, LDY_I, LDY_ZP, LDY_ZPX, LSR_A, LSR_ACC,
+        LSR_AX, LSR_ZP, LSR_ZPX, ORA_ZP, PHA, PHP, PLA, PLP, ROL_A, ROL_ACC, ROL_AX, ROL_ZP,
+        ROL_ZPX, ROR_A, ROR_ACC, ROR_AX, ROR_ZP, ROR_ZPX, RTI, RTS, SBC_A, SBC_AX, SBC_AY, SBC_I,
+        SBC_IX, SBC_IY, SBC_ZP, SBC_ZPX, SEC, SED, SEI, STA_A, STA_AX, STA_AY, STA_IX, STA_IY,
+        STA_ZP, STA_ZPX, STX_A, STX_ZP, STX_ZPY, STY_A, STY_ZP, STY_ZPX, TAX, TAY, TSX, TXA, TXS,
+        TYA,
+    };
+    use crate::OPCODE::{
+        ALR_I, ANC_I, ANE_I, ARR_I, DCP_ZP, ISC_ZP, JAM_02, LAS_AY, LAX_IY, LAX_ZP, LXA_I, NOP_1A,
+        NOP_AX_1C, NOP_I_80, NOP_ZPX_14, RLA_ZP, RRA_ZP, SAX_ZP, SBC_I_EB, SBX_I, SHA_AY, SHX_AY,
+        SHY_AX, SLO_ZP, SRE_ZP, TAS_AY,
     };
-    use crate::{Instruction, Memory, CPU, LDA_A};
+    use crate::OPCODE::{
+        BBR3, BBS3, BIT_I, BRA, DEC_ACC, INC_ACC, JMP_IAX, LDA_IZP, PHX, PHY, PLX, PLY, RMB3, SMB3,
+        STA_IZP, STP, STZ_ZP, TRB_ZP, TSB_A, WAI,
+    };
+    use crate::{Instruction, Memory, CPU, OPCODE};
 
     #[test]
     fn test_sta_zp() {
         let mut cpu: CPU = CPU::new(0x11, 0, 0, 0);
         let mut mem: Memory = Memory::new();
-        mem.push_back_ins(Instruction::new(STA_ZP, &vec![0x10]));
-        cpu.execute(&mut mem);
+        mem.push_back_ins(Instruction::new(STA_ZP, &vec![0x10]).unwrap());
+        cpu.execute(&mut mem).unwrap();
         assert_eq!(*mem.read_byte(&(ZP_S + 0x10 as u16)), 0x11);
     }
     #[test]
     fn test_sta_zpx() {
         let mut cpu: CPU = CPU::new(0x11, 0x50, 0, 0);
         let mut mem: Memory = Memory::new();
-        mem.push_back_ins(Instruction::new(STA_ZPX, &vec![0x10]));
-        cpu.execute(&mut mem);
+        mem.push_back_ins(Instruction::new(STA_ZPX, &vec![0x10]).unwrap());
+        cpu.execute(&mut mem).unwrap();
         assert_eq!(*mem.read_byte(&(ZP_S + 0x60 as u16)), 0x11);
     }
     #[test]
     fn test_sta_a() {
         let mut cpu: CPU = CPU::new(0x13, 0, 0, 0);
         let mut mem: Memory = Memory::new();
-        mem.push_back_ins(Instruction::new(STA_A, &vec![0x10, 0x40]));
-        cpu.execute(&mut mem);
+        mem.push_back_ins(Instruction::new(STA_A, &vec![0x10, 0x40]).unwrap());
+        cpu.execute(&mut mem).unwrap();
         assert_eq!(*mem.read_byte(&(0x4010 as u16)), 0x13);
     }
     #[test]
     fn test_sta_ax() {
         let mut cpu: CPU = CPU::new(0x13, 0x02, 0, 0);
         let mut mem: Memory = Memory::new();
-        mem.push_back_ins(Instruction::new(STA_AX, &vec![0x10, 0x40]));
-        cpu.execute(&mut mem);
+        mem.push_back_ins(Instruction::new(STA_AX, &vec![0x10, 0x40]).unwrap());
+        cpu.execute(&mut mem).unwrap();
         assert_eq!(*mem.read_byte(&(0x4012 as u16)), 0x13);
     }
     #[test]
     fn test_sta_ay() {
         let mut cpu: CPU = CPU::new(0x13, 0, 0x02, 0);
         let mut mem: Memory = Memory::new();
-        mem.push_back_ins(Instruction::new(STA_AY, &vec![0x10, 0x40]));
-        cpu.execute(&mut mem);
+        mem.push_back_ins(Instruction::new(STA_AY, &vec![0x10, 0x40]).unwrap());
+        cpu.execute(&mut mem).unwrap();
         assert_eq!(*mem.read_byte(&(0x4012 as u16)), 0x13);
     }
     #[test]
     fn test_sta_ix() {
         let mut cpu: CPU = CPU::new(0x13, 0x10, 0, 0);
         let mut mem: Memory = Memory::new();
-        mem.push_back_ins(Instruction::new(STA_IX, &vec![0x40]));
+        mem.push_back_ins(Instruction::new(STA_IX, &vec![0x40]).unwrap());
         mem.write_byte(&(0x50 as u16), &0x12);
         mem.write_byte(&(0x51 as u16), &0x14);
-        cpu.execute(&mut mem);
+        cpu.execute(&mut mem).unwrap();
         assert_eq!(*mem.read_byte(&(0x1412 as u16)), 0x13);
     }
     #[test]
     fn test_sta_iy() {
         let mut cpu: CPU = CPU::new(0x13, 0, 0x10, 0);
         let mut mem: Memory = Memory::new();
-        mem.push_back_ins(Instruction::new(STA_IY, &vec![0x40]));
+        mem.push_back_ins(Instruction::new(STA_IY, &vec![0x40]).unwrap());
         mem.write_byte(&(0x40 as u16), &0x40);
         mem.write_byte(&(0x41 as u16), &0x10);
-        cpu.execute(&mut mem);
+        cpu.execute(&mut mem).unwrap();
         assert_eq!(*mem.read_byte(&(0x1050 as u16)), 0x13);
     }
 
@@ -79,24 +116,24 @@ mod tests {
     fn test_stx_zp() {
         let mut cpu: CPU = CPU::new(0, 0x11, 0, 0);
         let mut mem: Memory = Memory::new();
-        mem.push_back_ins(Instruction::new(STX_ZP, &vec![0x10]));
-        cpu.execute(&mut mem);
+        mem.push_back_ins(Instruction::new(STX_ZP, &vec![0x10]).unwrap());
+        cpu.execute(&mut mem).unwrap();
         assert_eq!(*mem.read_byte(&(ZP_S + 0x10 as u16)), 0x11);
     }
     #[test]
     fn test_stx_zpy() {
         let mut cpu: CPU = CPU::new(0, 0x11, 0x50, 0);
         let mut mem: Memory = Memory::new();
-        mem.push_back_ins(Instruction::new(STX_ZPY, &vec![0x10]));
-        cpu.execute(&mut mem);
+        mem.push_back_ins(Instruction::new(STX_ZPY, &vec![0x10]).unwrap());
+        cpu.execute(&mut mem).unwrap();
         assert_eq!(*mem.read_byte(&(ZP_S + 0x60 as u16)), 0x11);
     }
     #[test]
     fn test_stx_a() {
         let mut cpu: CPU = CPU::new(0, 0x13, 0, 0);
         let mut mem: Memory = Memory::new();
-        mem.push_back_ins(Instruction::new(STX_A, &vec![0x10, 0x40]));
-        cpu.execute(&mut mem);
+        mem.push_back_ins(Instruction::new(STX_A, &vec![0x10, 0x40]).unwrap());
+        cpu.execute(&mut mem).unwrap();
         assert_eq!(*mem.read_byte(&(0x4010 as u16)), 0x13);
     }
 
@@ -104,24 +141,24 @@ mod tests {
     fn test_sty_zp() {
         let mut cpu: CPU = CPU::new(0, 0, 0x11, 0);
         let mut mem: Memory = Memory::new();
-        mem.push_back_ins(Instruction::new(STY_ZP, &vec![0x10]));
-        cpu.execute(&mut mem);
+        mem.push_back_ins(Instruction::new(STY_ZP, &vec![0x10]).unwrap());
+        cpu.execute(&mut mem).unwrap();
         assert_eq!(*mem.read_byte(&(ZP_S + 0x10 as u16)), 0x11);
     }
     #[test]
     fn test_sty_zpx() {
         let mut cpu: CPU = CPU::new(0, 0x50, 0x11, 0);
         let mut mem: Memory = Memory::new();
-        mem.push_back_ins(Instruction::new(STY_ZPX, &vec![0x10]));
-        cpu.execute(&mut mem);
+        mem.push_back_ins(Instruction::new(STY_ZPX, &vec![0x10]).unwrap());
+        cpu.execute(&mut mem).unwrap();
         assert_eq!(*mem.read_byte(&(ZP_S + 0x60 as u16)), 0x11);
     }
     #[test]
     fn test_sty_a() {
         let mut cpu: CPU = CPU::new(0, 0, 0x13, 0);
         let mut mem: Memory = Memory::new();
-        mem.push_back_ins(Instruction::new(STY_A, &vec![0x10, 0x40]));
-        cpu.execute(&mut mem);
+        mem.push_back_ins(Instruction::new(STY_A, &vec![0x10, 0x40]).unwrap());
+        cpu.execute(&mut mem).unwrap();
         assert_eq!(*mem.read_byte(&(0x4010 as u16)), 0x13);
     }
 
@@ -129,75 +166,75 @@ mod tests {
     fn test_lda_i() {
         let mut cpu: CPU = CPU::new(0, 0, 0, 0);
         let mut mem: Memory = Memory::new();
-        mem.push_back_ins(Instruction::new(LDA_I, &vec![0x12]));
-        cpu.execute(&mut mem);
+        mem.push_back_ins(Instruction::new(LDA_I, &vec![0x12]).unwrap());
+        cpu.execute(&mut mem).unwrap();
         assert_eq!(cpu.a().value, 0x12);
     }
     #[test]
     fn test_lda_zp() {
         let mut cpu: CPU = CPU::new(0, 0, 0, 0);
         let mut mem: Memory = Memory::new();
-        mem.push_back_ins(Instruction::new(LDA_ZP, &vec![0x10]));
+        mem.push_back_ins(Instruction::new(LDA_ZP, &vec![0x10]).unwrap());
         mem.physical_mem[(ZP_S + 0x10) as usize] = 0xFF;
-        cpu.execute(&mut mem);
+        cpu.execute(&mut mem).unwrap();
         assert_eq!(cpu.a().value, 0xFF);
     }
     #[test]
     fn test_lda_zpx() {
         let mut cpu: CPU = CPU::new(0, 0x1, 0, 0);
         let mut mem: Memory = Memory::new();
-        mem.push_back_ins(Instruction::new(LDA_ZPX, &vec![0x10]));
+        mem.push_back_ins(Instruction::new(LDA_ZPX, &vec![0x10]).unwrap());
         mem.physical_mem[(ZP_S + 0x11) as usize] = 0xFF;
-        cpu.execute(&mut mem);
+        cpu.execute(&mut mem).unwrap();
         assert_eq!(cpu.a().value, 0xFF);
     }
     #[test]
     fn test_lda_a() {
         let mut cpu: CPU = CPU::new(0, 0, 0, 0);
         let mut mem: Memory = Memory::new();
-        mem.push_back_ins(Instruction::new(LDA_A, &vec![0x10, 0x80]));
+        mem.push_back_ins(Instruction::new(LDA_A, &vec![0x10, 0x80]).unwrap());
         mem.physical_mem[(0x8010) as usize] = 0xFF;
-        cpu.execute(&mut mem);
+        cpu.execute(&mut mem).unwrap();
         assert_eq!(cpu.a().value, 0xFF);
     }
     #[test]
     fn test_lda_ax() {
         let mut cpu: CPU = CPU::new(0, 0x8, 0, 0);
         let mut mem: Memory = Memory::new();
-        mem.push_back_ins(Instruction::new(LDA_AX, &vec![0x10, 0x80]));
+        mem.push_back_ins(Instruction::new(LDA_AX, &vec![0x10, 0x80]).unwrap());
         mem.physical_mem[(0x8018) as usize] = 0xFF;
-        cpu.execute(&mut mem);
+        cpu.execute(&mut mem).unwrap();
         assert_eq!(cpu.a().value, 0xFF);
     }
     #[test]
     fn test_lda_ay() {
         let mut cpu: CPU = CPU::new(0, 0, 0x2, 0);
         let mut mem: Memory = Memory::new();
-        mem.push_back_ins(Instruction::new(LDA_AY, &vec![0x10, 0x80]));
+        mem.push_back_ins(Instruction::new(LDA_AY, &vec![0x10, 0x80]).unwrap());
         mem.physical_mem[(0x8012) as usize] = 0xFF;
-        cpu.execute(&mut mem);
+        cpu.execute(&mut mem).unwrap();
         assert_eq!(cpu.a().value, 0xFF);
     }
     #[test]
     fn test_lda_ix() {
         let mut cpu: CPU = CPU::new(0, 0x2, 0, 0);
         let mut mem: Memory = Memory::new();
-        mem.push_back_ins(Instruction::new(LDA_IX, &vec![0x20]));
+        mem.push_back_ins(Instruction::new(LDA_IX, &vec![0x20]).unwrap());
         mem.physical_mem[(0x22) as usize] = 0x10;
         mem.physical_mem[(0x23) as usize] = 0xFF;
         mem.physical_mem[(0xFF10) as usize] = 0xAA;
-        cpu.execute(&mut mem);
+        cpu.execute(&mut mem).unwrap();
         assert_eq!(cpu.a().value, 0xAA);
     }
     #[test]
     fn test_lda_iy() {
         let mut cpu: CPU = CPU::new(0, 0, 0x40, 0);
         let mut mem: Memory = Memory::new();
-        mem.push_back_ins(Instruction::new(LDA_IY, &vec![0x50]));
+        mem.push_back_ins(Instruction::new(LDA_IY, &vec![0x50]).unwrap());
         mem.physical_mem[(0x0050) as usize] = 0x20;
         mem.physical_mem[(0x0051) as usize] = 0x10;
         mem.physical_mem[(0x1060) as usize] = 0xFF;
-        cpu.execute(&mut mem);
+        cpu.execute(&mut mem).unwrap();
         assert_eq!(cpu.a().value, 0xFF);
     }
 
@@ -205,44 +242,44 @@ mod tests {
     fn test_ldx_i() {
         let mut cpu: CPU = CPU::new(0, 0, 0, 0);
         let mut mem: Memory = Memory::new();
-        mem.push_back_ins(Instruction::new(LDX_I, &vec![0x12]));
-        cpu.execute(&mut mem);
+        mem.push_back_ins(Instruction::new(LDX_I, &vec![0x12]).unwrap());
+        cpu.execute(&mut mem).unwrap();
         assert_eq!(cpu.x().value, 0x12);
     }
     #[test]
     fn test_ldx_a() {
         let mut cpu: CPU = CPU::new(0, 0, 0, 0);
         let mut mem: Memory = Memory::new();
-        mem.push_back_ins(Instruction::new(LDX_A, &vec![0x10, 0x80]));
+        mem.push_back_ins(Instruction::new(LDX_A, &vec![0x10, 0x80]).unwrap());
         mem.physical_mem[(0x8010) as usize] = 0xFF;
-        cpu.execute(&mut mem);
+        cpu.execute(&mut mem).unwrap();
         assert_eq!(cpu.x().value, 0xFF);
     }
     #[test]
     fn test_ldx_ay() {
         let mut cpu: CPU = CPU::new(0, 0, 0x2, 0);
         let mut mem: Memory = Memory::new();
-        mem.push_back_ins(Instruction::new(LDX_AY, &vec![0x10, 0x80]));
+        mem.push_back_ins(Instruction::new(LDX_AY, &vec![0x10, 0x80]).unwrap());
         mem.physical_mem[(0x8012) as usize] = 0xFF;
-        cpu.execute(&mut mem);
+        cpu.execute(&mut mem).unwrap();
         assert_eq!(cpu.x().value, 0xFF);
     }
     #[test]
     fn test_ldx_zp() {
         let mut cpu: CPU = CPU::new(0, 0, 0, 0);
         let mut mem: Memory = Memory::new();
-        mem.push_back_ins(Instruction::new(LDX_ZP, &vec![0x10]));
+        mem.push_back_ins(Instruction::new(LDX_ZP, &vec![0x10]).unwrap());
         mem.physical_mem[(ZP_S + 0x10) as usize] = 0xFF;
-        cpu.execute(&mut mem);
+        cpu.execute(&mut mem).unwrap();
         assert_eq!(cpu.x().value, 0xFF);
     }
     #[test]
     fn test_ldx_zpy() {
         let mut cpu: CPU = CPU::new(0, 0, 0x1, 0);
         let mut mem: Memory = Memory::new();
-        mem.push_back_ins(Instruction::new(LDX_ZPY, &vec![0x10]));
+        mem.push_back_ins(Instruction::new(LDX_ZPY, &vec![0x10]).unwrap());
         mem.physical_mem[(ZP_S + 0x11) as usize] = 0xFF;
-        cpu.execute(&mut mem);
+        cpu.execute(&mut mem).unwrap();
         assert_eq!(cpu.x().value, 0xFF);
     }
 
@@ -250,44 +287,44 @@ mod tests {
     fn test_ldy_i() {
         let mut cpu: CPU = CPU::new(0, 0, 0, 0);
         let mut mem: Memory = Memory::new();
-        mem.push_back_ins(Instruction::new(LDY_I, &vec![0x12]));
-        cpu.execute(&mut mem);
+        mem.push_back_ins(Instruction::new(LDY_I, &vec![0x12]).unwrap());
+        cpu.execute(&mut mem).unwrap();
         assert_eq!(cpu.y().value, 0x12);
     }
     #[test]
     fn test_ldy_a() {
         let mut cpu: CPU = CPU::new(0, 0, 0, 0);
         let mut mem: Memory = Memory::new();
-        mem.push_back_ins(Instruction::new(LDY_A, &vec![0x10, 0x80]));
+        mem.push_back_ins(Instruction::new(LDY_A, &vec![0x10, 0x80]).unwrap());
         mem.physical_mem[(0x8010) as usize] = 0xFF;
-        cpu.execute(&mut mem);
+        cpu.execute(&mut mem).unwrap();
         assert_eq!(cpu.y().value, 0xFF);
     }
     #[test]
     fn test_ldy_ax() {
         let mut cpu: CPU = CPU::new(0, 0x2, 0, 0);
         let mut mem: Memory = Memory::new();
-        mem.push_back_ins(Instruction::new(LDY_AX, &vec![0x10, 0x80]));
+        mem.push_back_ins(Instruction::new(LDY_AX, &vec![0x10, 0x80]).unwrap());
         mem.physical_mem[(0x8012) as usize] = 0xFF;
-        cpu.execute(&mut mem);
+        cpu.execute(&mut mem).unwrap();
         assert_eq!(cpu.y().value, 0xFF);
     }
     #[test]
     fn test_ldy_zp() {
         let mut cpu: CPU = CPU::new(0, 0, 0, 0);
         let mut mem: Memory = Memory::new();
-        mem.push_back_ins(Instruction::new(LDY_ZP, &vec![0x10]));
+        mem.push_back_ins(Instruction::new(LDY_ZP, &vec![0x10]).unwrap());
         mem.physical_mem[(ZP_S + 0x10) as usize] = 0xFF;
-        cpu.execute(&mut mem);
+        cpu.execute(&mut mem).unwrap();
         assert_eq!(cpu.y().value, 0xFF);
     }
     #[test]
     fn test_ldy_zpx() {
         let mut cpu: CPU = CPU::new(0, 0x1, 0, 0);
         let mut mem: Memory = Memory::new();
-        mem.push_back_ins(Instruction::new(LDY_ZPX, &vec![0x10]));
+        mem.push_back_ins(Instruction::new(LDY_ZPX, &vec![0x10]).unwrap());
         mem.physical_mem[(ZP_S + 0x11) as usize] = 0xFF;
-        cpu.execute(&mut mem);
+        cpu.execute(&mut mem).unwrap();
         assert_eq!(cpu.y().value, 0xFF);
     }
 
@@ -295,82 +332,82 @@ mod tests {
     fn test_cpx_i() {
         let mut cpu: CPU = CPU::new(0, 0x10, 0, 0);
         let mut mem: Memory = Memory::new();
-        mem.push_back_ins(Instruction::new(CPX_I, &vec![0x10]));
-        cpu.execute(&mut mem);
+        mem.push_back_ins(Instruction::new(CPX_I, &vec![0x10]).unwrap());
+        cpu.execute(&mut mem).unwrap();
 
         /* test whether a equal can be detected */
         /* to detect a equals only the zero flag has to be true */
-        assert_eq!(*cpu.n_flag(), false);
-        assert_eq!(*cpu.z_flag(), true);
-        assert_eq!(*cpu.c_flag(), true);
+        assert_eq!(cpu.n_flag(), false);
+        assert_eq!(cpu.z_flag(), true);
+        assert_eq!(cpu.c_flag(), true);
     }
     #[test]
     fn test_cpx_a() {
         let mut cpu: CPU = CPU::new(0, 0x10, 0, 0);
         let mut mem: Memory = Memory::new();
-        mem.push_back_ins(Instruction::new(CPX_A, &vec![0x10, 0x80]));
+        mem.push_back_ins(Instruction::new(CPX_A, &vec![0x10, 0x80]).unwrap());
         mem.physical_mem[(0x8010 as usize)] = 0x10;
-        cpu.execute(&mut mem);
+        cpu.execute(&mut mem).unwrap();
 
-        assert_eq!(*cpu.n_flag(), false);
-        assert_eq!(*cpu.z_flag(), true);
-        assert_eq!(*cpu.c_flag(), true);
+        assert_eq!(cpu.n_flag(), false);
+        assert_eq!(cpu.z_flag(), true);
+        assert_eq!(cpu.c_flag(), true);
     }
     #[test]
     fn test_cpx_zp() {
         let mut cpu: CPU = CPU::new(0, 0x10, 0, 0);
         let mut mem: Memory = Memory::new();
-        mem.push_back_ins(Instruction::new(CPX_ZP, &vec![0x20]));
+        mem.push_back_ins(Instruction::new(CPX_ZP, &vec![0x20]).unwrap());
         mem.physical_mem[(ZP_S + 0x20) as usize] = 0x10;
-        cpu.execute(&mut mem);
+        cpu.execute(&mut mem).unwrap();
 
-        assert_eq!(*cpu.n_flag(), false);
-        assert_eq!(*cpu.z_flag(), true);
-        assert_eq!(*cpu.c_flag(), true);
+        assert_eq!(cpu.n_flag(), false);
+        assert_eq!(cpu.z_flag(), true);
+        assert_eq!(cpu.c_flag(), true);
     }
 
     #[test]
     fn test_cpy_i() {
         let mut cpu: CPU = CPU::new(0, 0, 0x10, 0);
         let mut mem: Memory = Memory::new();
-        mem.push_back_ins(Instruction::new(CPY_I, &vec![0x10]));
-        cpu.execute(&mut mem);
+        mem.push_back_ins(Instruction::new(CPY_I, &vec![0x10]).unwrap());
+        cpu.execute(&mut mem).unwrap();
 
-        assert_eq!(*cpu.n_flag(), false);
-        assert_eq!(*cpu.z_flag(), true);
-        assert_eq!(*cpu.c_flag(), true);
+        assert_eq!(cpu.n_flag(), false);
+        assert_eq!(cpu.z_flag(), true);
+        assert_eq!(cpu.c_flag(), true);
     }
     #[test]
     fn test_cpy_a() {
         let mut cpu: CPU = CPU::new(0, 0, 0x10, 0);
         let mut mem: Memory = Memory::new();
-        mem.push_back_ins(Instruction::new(CPY_A, &vec![0x10, 0x80]));
+        mem.push_back_ins(Instruction::new(CPY_A, &vec![0x10, 0x80]).unwrap());
         mem.physical_mem[(0x8010 as usize)] = 0x10;
-        cpu.execute(&mut mem);
+        cpu.execute(&mut mem).unwrap();
 
-        assert_eq!(*cpu.n_flag(), false);
-        assert_eq!(*cpu.z_flag(), true);
-        assert_eq!(*cpu.c_flag(), true);
+        assert_eq!(cpu.n_flag(), false);
+        assert_eq!(cpu.z_flag(), true);
+        assert_eq!(cpu.c_flag(), true);
     }
     #[test]
     fn test_cpy_zp() {
         let mut cpu: CPU = CPU::new(0, 0, 0x10, 0);
         let mut mem: Memory = Memory::new();
-        mem.push_back_ins(Instruction::new(CPY_ZP, &vec![0x20]));
+        mem.push_back_ins(Instruction::new(CPY_ZP, &vec![0x20]).unwrap());
         mem.physical_mem[(ZP_S + 0x20) as usize] = 0x10;
-        cpu.execute(&mut mem);
+        cpu.execute(&mut mem).unwrap();
 
-        assert_eq!(*cpu.n_flag(), false);
-        assert_eq!(*cpu.z_flag(), true);
-        assert_eq!(*cpu.c_flag(), true);
+        assert_eq!(cpu.n_flag(), false);
+        assert_eq!(cpu.z_flag(), true);
+        assert_eq!(cpu.c_flag(), true);
     }
 
     #[test]
     fn test_adc_i() {
         let mut cpu: CPU = CPU::new(0x10, 0, 0, 0);
         let mut mem: Memory = Memory::new();
-        mem.push_back_ins(Instruction::new(ADC_I, &vec![0x20]));
-        cpu.execute(&mut mem);
+        mem.push_back_ins(Instruction::new(ADC_I, &vec![0x20]).unwrap());
+        cpu.execute(&mut mem).unwrap();
 
         assert_eq!(cpu.a().value, 0x30);
     }
@@ -379,9 +416,9 @@ mod tests {
     fn test_adc_zp() {
         let mut cpu: CPU = CPU::new(0x10, 0, 0, 0);
         let mut mem: Memory = Memory::new();
-        mem.push_back_ins(Instruction::new(ADC_ZP, &vec![0x20]));
+        mem.push_back_ins(Instruction::new(ADC_ZP, &vec![0x20]).unwrap());
         mem.physical_mem[(ZP_S + 0x20) as usize] = 0x32;
-        cpu.execute(&mut mem);
+        cpu.execute(&mut mem).unwrap();
 
         assert_eq!(cpu.a().value, 0x42);
     }
@@ -390,9 +427,9 @@ mod tests {
     fn test_adc_zpx() {
         let mut cpu: CPU = CPU::new(0x10, 0x15, 0, 0);
         let mut mem: Memory = Memory::new();
-        mem.push_back_ins(Instruction::new(ADC_ZPX, &vec![0x20]));
+        mem.push_back_ins(Instruction::new(ADC_ZPX, &vec![0x20]).unwrap());
         mem.physical_mem[(ZP_S + 0x20 + 0x15) as usize] = 0x32;
-        cpu.execute(&mut mem);
+        cpu.execute(&mut mem).unwrap();
 
         assert_eq!(cpu.a().value, 0x42);
     }
@@ -401,9 +438,9 @@ mod tests {
     fn test_adc_a() {
         let mut cpu: CPU = CPU::new(0x10, 0, 0, 0);
         let mut mem: Memory = Memory::new();
-        mem.push_back_ins(Instruction::new(ADC_A, &vec![0x20, 0x40]));
+        mem.push_back_ins(Instruction::new(ADC_A, &vec![0x20, 0x40]).unwrap());
         mem.physical_mem[(0x4020) as usize] = 0x32;
-        cpu.execute(&mut mem);
+        cpu.execute(&mut mem).unwrap();
 
         assert_eq!(cpu.a().value, 0x42);
     }
@@ -412,9 +449,9 @@ mod tests {
     fn test_adc_ax() {
         let mut cpu: CPU = CPU::new(0x10, 0x20, 0, 0);
         let mut mem: Memory = Memory::new();
-        mem.push_back_ins(Instruction::new(ADC_AX, &vec![0x20, 0x40]));
+        mem.push_back_ins(Instruction::new(ADC_AX, &vec![0x20, 0x40]).unwrap());
         mem.physical_mem[(0x4020 + 0x20) as usize] = 0x32;
-        cpu.execute(&mut mem);
+        cpu.execute(&mut mem).unwrap();
 
         assert_eq!(cpu.a().value, 0x42);
     }
@@ -423,9 +460,9 @@ mod tests {
     fn test_adc_ay() {
         let mut cpu: CPU = CPU::new(0x10, 0, 0x20, 0);
         let mut mem: Memory = Memory::new();
-        mem.push_back_ins(Instruction::new(ADC_AY, &vec![0x20, 0x40]));
+        mem.push_back_ins(Instruction::new(ADC_AY, &vec![0x20, 0x40]).unwrap());
         mem.physical_mem[(0x4020 + 0x20) as usize] = 0x32;
-        cpu.execute(&mut mem);
+        cpu.execute(&mut mem).unwrap();
 
         assert_eq!(cpu.a().value, 0x42);
     }
@@ -434,11 +471,11 @@ mod tests {
     fn test_adc_ix() {
         let mut cpu: CPU = CPU::new(0x10, 0x10, 0, 0);
         let mut mem: Memory = Memory::new();
-        mem.push_back_ins(Instruction::new(ADC_IX, &vec![0x40]));
+        mem.push_back_ins(Instruction::new(ADC_IX, &vec![0x40]).unwrap());
         mem.physical_mem[(0x50) as usize] = 0x20;
         mem.physical_mem[(0x51) as usize] = 0x32;
         mem.physical_mem[(0x3220) as usize] = 0x32;
-        cpu.execute(&mut mem);
+        cpu.execute(&mut mem).unwrap();
 
         assert_eq!(cpu.a().value, 0x42);
     }
@@ -447,11 +484,11 @@ mod tests {
     fn test_adc_iy() {
         let mut cpu: CPU = CPU::new(0x10, 0, 0x10, 0);
         let mut mem: Memory = Memory::new();
-        mem.push_back_ins(Instruction::new(ADC_IY, &vec![0x40]));
+        mem.push_back_ins(Instruction::new(ADC_IY, &vec![0x40]).unwrap());
         mem.physical_mem[(0x40) as usize] = 0x11;
         mem.physical_mem[(0x41) as usize] = 0x51;
         mem.physical_mem[(0x5121) as usize] = 0xAA;
-        cpu.execute(&mut mem);
+        cpu.execute(&mut mem).unwrap();
 
         assert_eq!(cpu.a().value, 0xBA);
     }
@@ -460,8 +497,8 @@ mod tests {
     fn test_and_i() {
         let mut cpu: CPU = CPU::new(0xFF, 0, 0, 0);
         let mut mem: Memory = Memory::new();
-        mem.push_back_ins(Instruction::new(AND_I, &vec![0xF0]));
-        cpu.execute(&mut mem);
+        mem.push_back_ins(Instruction::new(AND_I, &vec![0xF0]).unwrap());
+        cpu.execute(&mut mem).unwrap();
         assert_eq!(cpu.a().value, 0xF0);
     }
 
@@ -469,9 +506,9 @@ mod tests {
     fn test_and_zp() {
         let mut cpu: CPU = CPU::new(0xFF, 0, 0, 0);
         let mut mem: Memory = Memory::new();
-        mem.push_back_ins(Instruction::new(AND_ZP, &vec![0x20]));
+        mem.push_back_ins(Instruction::new(AND_ZP, &vec![0x20]).unwrap());
         mem.write_byte(&(ZP_S + 0x20), &(0xF0));
-        cpu.execute(&mut mem);
+        cpu.execute(&mut mem).unwrap();
         assert_eq!(cpu.a().value, 0xF0);
     }
 
@@ -479,9 +516,9 @@ mod tests {
     fn test_and_zpx() {
         let mut cpu: CPU = CPU::new(0xFF, 0x10, 0, 0);
         let mut mem: Memory = Memory::new();
-        mem.push_back_ins(Instruction::new(AND_ZPX, &vec![0x20]));
+        mem.push_back_ins(Instruction::new(AND_ZPX, &vec![0x20]).unwrap());
         mem.write_byte(&(ZP_S + 0x30), &(0xF0));
-        cpu.execute(&mut mem);
+        cpu.execute(&mut mem).unwrap();
         assert_eq!(cpu.a().value, 0xF0);
     }
 
@@ -489,9 +526,9 @@ mod tests {
     fn test_and_a() {
         let mut cpu: CPU = CPU::new(0xFF, 0, 0, 0);
         let mut mem: Memory = Memory::new();
-        mem.push_back_ins(Instruction::new(AND_A, &vec![0x20, 0x40]));
+        mem.push_back_ins(Instruction::new(AND_A, &vec![0x20, 0x40]).unwrap());
         mem.write_byte(&(0x4020), &(0xF0));
-        cpu.execute(&mut mem);
+        cpu.execute(&mut mem).unwrap();
         assert_eq!(cpu.a().value, 0xF0);
     }
 
@@ -499,9 +536,9 @@ mod tests {
     fn test_and_ax() {
         let mut cpu: CPU = CPU::new(0xFF, 0xAA, 0, 0);
         let mut mem: Memory = Memory::new();
-        mem.push_back_ins(Instruction::new(AND_AX, &vec![0x20, 0x40]));
+        mem.push_back_ins(Instruction::new(AND_AX, &vec![0x20, 0x40]).unwrap());
         mem.write_byte(&(0x4020 + 0xAA), &(0xF0));
-        cpu.execute(&mut mem);
+        cpu.execute(&mut mem).unwrap();
         assert_eq!(cpu.a().value, 0xF0);
     }
 
@@ -509,9 +546,9 @@ mod tests {
     fn test_and_ay() {
         let mut cpu: CPU = CPU::new(0xFF, 0, 0xAA, 0);
         let mut mem: Memory = Memory::new();
-        mem.push_back_ins(Instruction::new(AND_AY, &vec![0x20, 0x40]));
+        mem.push_back_ins(Instruction::new(AND_AY, &vec![0x20, 0x40]).unwrap());
         mem.write_byte(&(0x4020 + 0xAA), &(0xF0));
-        cpu.execute(&mut mem);
+        cpu.execute(&mut mem).unwrap();
         assert_eq!(cpu.a().value, 0xF0);
     }
 
@@ -519,11 +556,11 @@ mod tests {
     fn test_and_ix() {
         let mut cpu: CPU = CPU::new(0xFF, 0x10, 0, 0);
         let mut mem: Memory = Memory::new();
-        mem.push_back_ins(Instruction::new(AND_IX, &vec![0x20]));
+        mem.push_back_ins(Instruction::new(AND_IX, &vec![0x20]).unwrap());
         mem.write_byte(&(0x30), &(0x10));
         mem.write_byte(&(0x31), &(0x20));
         mem.write_byte(&(0x2010), &(0xF0));
-        cpu.execute(&mut mem);
+        cpu.execute(&mut mem).unwrap();
         assert_eq!(cpu.a().value, 0xF0);
     }
 
@@ -531,11 +568,11 @@ mod tests {
     fn test_and_iy() {
         let mut cpu: CPU = CPU::new(0xFF, 0, 0x10, 0);
         let mut mem: Memory = Memory::new();
-        mem.push_back_ins(Instruction::new(AND_IY, &vec![0x20]));
+        mem.push_back_ins(Instruction::new(AND_IY, &vec![0x20]).unwrap());
         mem.write_byte(&(0x20), &(0x10));
         mem.write_byte(&(0x21), &(0x20));
         mem.write_byte(&(0x2010 + 0x10), &(0xF0));
-        cpu.execute(&mut mem);
+        cpu.execute(&mut mem).unwrap();
         assert_eq!(cpu.a().value, 0xF0);
     }
 
@@ -543,8 +580,8 @@ mod tests {
     fn test_asl_acc() {
         let mut cpu: CPU = CPU::new(0xFF, 0, 0, 0);
         let mut mem: Memory = Memory::new();
-        mem.push_back_ins(Instruction::new(ASL_ACC, &vec![]));
-        cpu.execute(&mut mem);
+        mem.push_back_ins(Instruction::new(ASL_ACC, &vec![]).unwrap());
+        cpu.execute(&mut mem).unwrap();
         assert_eq!(cpu.a().value, 0xFE);
     }
 
@@ -552,9 +589,9 @@ mod tests {
     fn test_asl_zp() {
         let mut cpu: CPU = CPU::new(0xFF, 0, 0, 0);
         let mut mem: Memory = Memory::new();
-        mem.push_back_ins(Instruction::new(ASL_ZP, &vec![0x20]));
+        mem.push_back_ins(Instruction::new(ASL_ZP, &vec![0x20]).unwrap());
         mem.write_byte(&(ZP_S + 0x20), &(0xFF));
-        cpu.execute(&mut mem);
+        cpu.execute(&mut mem).unwrap();
         assert_eq!(*mem.read_byte(&(ZP_S + 0x20)), 0xFE);
     }
 
@@ -562,9 +599,9 @@ mod tests {
     fn test_asl_zpx() {
         let mut cpu: CPU = CPU::new(0xFF, 0x10, 0, 0);
         let mut mem: Memory = Memory::new();
-        mem.push_back_ins(Instruction::new(ASL_ZPX, &vec![0x20]));
+        mem.push_back_ins(Instruction::new(ASL_ZPX, &vec![0x20]).unwrap());
         mem.write_byte(&(ZP_S + 0x20 + 0x10), &(0xFF));
-        cpu.execute(&mut mem);
+        cpu.execute(&mut mem).unwrap();
         assert_eq!(*mem.read_byte(&(ZP_S + 0x20 + 0x10)), 0xFE);
     }
 
@@ -572,9 +609,9 @@ mod tests {
     fn test_asl_a() {
         let mut cpu: CPU = CPU::new(0xFF, 0, 0, 0);
         let mut mem: Memory = Memory::new();
-        mem.push_back_ins(Instruction::new(ASL_A, &vec![0x20, 0x60]));
+        mem.push_back_ins(Instruction::new(ASL_A, &vec![0x20, 0x60]).unwrap());
         mem.write_byte(&(0x6020), &(0x01));
-        cpu.execute(&mut mem);
+        cpu.execute(&mut mem).unwrap();
         assert_eq!(*mem.read_byte(&(0x6020)), 0x02);
     }
 
@@ -582,9 +619,9 @@ mod tests {
     fn test_asl_ax() {
         let mut cpu: CPU = CPU::new(0xFF, 0x10, 0, 0);
         let mut mem: Memory = Memory::new();
-        mem.push_back_ins(Instruction::new(ASL_AX, &vec![0x20, 0x60]));
+        mem.push_back_ins(Instruction::new(ASL_AX, &vec![0x20, 0x60]).unwrap());
         mem.write_byte(&(0x6020 + 0x10), &(0xFF));
-        cpu.execute(&mut mem);
+        cpu.execute(&mut mem).unwrap();
         assert_eq!(*mem.read_byte(&(0x6020 + 0x10)), 0xFE);
     }
 
@@ -592,37 +629,37 @@ mod tests {
     fn test_bit_zp() {
         let mut cpu: CPU = CPU::new(0xFF, 0, 0, 0);
         let mut mem: Memory = Memory::new();
-        mem.push_back_ins(Instruction::new(BIT_ZP, &vec![0x60]));
+        mem.push_back_ins(Instruction::new(BIT_ZP, &vec![0x60]).unwrap());
         mem.write_byte(&(ZP_S + 0x60), &(0b10100000));
-        cpu.execute(&mut mem);
-        assert_eq!(*cpu.z_flag(), false);
-        assert_eq!(*cpu.n_flag(), true);
-        assert_eq!(*cpu.v_flag(), false);
+        cpu.execute(&mut mem).unwrap();
+        assert_eq!(cpu.z_flag(), false);
+        assert_eq!(cpu.n_flag(), true);
+        assert_eq!(cpu.v_flag(), false);
     }
 
     #[test]
     fn test_bit_a() {
         let mut cpu: CPU = CPU::new(0xFF, 0, 0, 0);
         let mut mem: Memory = Memory::new();
-        mem.push_back_ins(Instruction::new(BIT_A, &vec![0x60, 0x20]));
+        mem.push_back_ins(Instruction::new(BIT_A, &vec![0x60, 0x20]).unwrap());
         mem.write_byte(&(0x2060), &(0b10100000));
-        cpu.execute(&mut mem);
-        assert_eq!(*cpu.z_flag(), false);
-        assert_eq!(*cpu.n_flag(), true);
-        assert_eq!(*cpu.v_flag(), false);
+        cpu.execute(&mut mem).unwrap();
+        assert_eq!(cpu.z_flag(), false);
+        assert_eq!(cpu.n_flag(), true);
+        assert_eq!(cpu.v_flag(), false);
     }
 
     #[test]
     fn test_bpl() {
         let mut cpu: CPU = CPU::new(0x10, 0x20, 0, 0);
         let mut mem: Memory = Memory::new();
-        mem.push_back_ins(Instruction::new(CMP_I, &vec![0x11]));
-        mem.push_back_ins(Instruction::new(BPL, &vec![0x1])); // 1 - Byte offset because INX is 1 Byte lone (jump over it)
-        mem.push_back_ins(Instruction::new(INX, &vec![]));
-        mem.push_back_ins(Instruction::new(DEX, &vec![]));
-        cpu.execute(&mut mem);
-        cpu.execute(&mut mem);
-        cpu.execute(&mut mem);
+        mem.push_back_ins(Instruction::new(CMP_I, &vec![0x09]).unwrap());
+        mem.push_back_ins(Instruction::new(BPL, &vec![0x1]).unwrap()); // 1 - Byte offset because INX is 1 Byte lone (jump over it)
+        mem.push_back_ins(Instruction::new(INX, &vec![]).unwrap());
+        mem.push_back_ins(Instruction::new(DEX, &vec![]).unwrap());
+        cpu.execute(&mut mem).unwrap();
+        cpu.execute(&mut mem).unwrap();
+        cpu.execute(&mut mem).unwrap();
         assert_eq!(cpu.x().value, 0x1F);
     }
 
@@ -630,13 +667,13 @@ mod tests {
     fn test_bmi() {
         let mut cpu: CPU = CPU::new(0x10, 0x20, 0, 0);
         let mut mem: Memory = Memory::new();
-        mem.push_back_ins(Instruction::new(CMP_I, &vec![0x09]));
-        mem.push_back_ins(Instruction::new(BMI, &vec![0x1])); // 1 - Byte offset because INX is 1 Byte lone (jump over it)
-        mem.push_back_ins(Instruction::new(INX, &vec![]));
-        mem.push_back_ins(Instruction::new(DEX, &vec![]));
-        cpu.execute(&mut mem);
-        cpu.execute(&mut mem);
-        cpu.execute(&mut mem);
+        mem.push_back_ins(Instruction::new(CMP_I, &vec![0x11]).unwrap());
+        mem.push_back_ins(Instruction::new(BMI, &vec![0x1]).unwrap()); // 1 - Byte offset because INX is 1 Byte lone (jump over it)
+        mem.push_back_ins(Instruction::new(INX, &vec![]).unwrap());
+        mem.push_back_ins(Instruction::new(DEX, &vec![]).unwrap());
+        cpu.execute(&mut mem).unwrap();
+        cpu.execute(&mut mem).unwrap();
+        cpu.execute(&mut mem).unwrap();
         assert_eq!(cpu.x().value, 0x1F);
     }
 
@@ -644,27 +681,27 @@ mod tests {
     fn test_bvc() {
         let mut cpu: CPU = CPU::new(0xFF, 0x20, 0, 0);
         let mut mem: Memory = Memory::new();
-        mem.push_back_ins(Instruction::new(ADC_I, &vec![0x00]));
-        mem.push_back_ins(Instruction::new(BVC, &vec![0x1])); // 1 - Byte offset because INX is 1 Byte lone (jump over it)
-        mem.push_back_ins(Instruction::new(INX, &vec![]));
-        mem.push_back_ins(Instruction::new(DEX, &vec![]));
-        cpu.execute(&mut mem);
-        cpu.execute(&mut mem);
-        cpu.execute(&mut mem);
+        mem.push_back_ins(Instruction::new(ADC_I, &vec![0x00]).unwrap());
+        mem.push_back_ins(Instruction::new(BVC, &vec![0x1]).unwrap()); // 1 - Byte offset because INX is 1 Byte lone (jump over it)
+        mem.push_back_ins(Instruction::new(INX, &vec![]).unwrap());
+        mem.push_back_ins(Instruction::new(DEX, &vec![]).unwrap());
+        cpu.execute(&mut mem).unwrap();
+        cpu.execute(&mut mem).unwrap();
+        cpu.execute(&mut mem).unwrap();
         assert_eq!(cpu.x().value, 0x1F);
     }
 
     #[test]
     fn test_bvs() {
-        let mut cpu: CPU = CPU::new(0xFF, 0x20, 0, 0);
+        let mut cpu: CPU = CPU::new(0x7F, 0x20, 0, 0);
         let mut mem: Memory = Memory::new();
-        mem.push_back_ins(Instruction::new(ADC_I, &vec![0x01]));
-        mem.push_back_ins(Instruction::new(BVS, &vec![0x1])); // 1 - Byte offset because INX is 1 Byte lone (jump over it)
-        mem.push_back_ins(Instruction::new(INX, &vec![]));
-        mem.push_back_ins(Instruction::new(DEX, &vec![]));
-        cpu.execute(&mut mem);
-        cpu.execute(&mut mem);
-        cpu.execute(&mut mem);
+        mem.push_back_ins(Instruction::new(ADC_I, &vec![0x01]).unwrap());
+        mem.push_back_ins(Instruction::new(BVS, &vec![0x1]).unwrap()); // 1 - Byte offset because INX is 1 Byte lone (jump over it)
+        mem.push_back_ins(Instruction::new(INX, &vec![]).unwrap());
+        mem.push_back_ins(Instruction::new(DEX, &vec![]).unwrap());
+        cpu.execute(&mut mem).unwrap();
+        cpu.execute(&mut mem).unwrap();
+        cpu.execute(&mut mem).unwrap();
         assert_eq!(cpu.x().value, 0x1F);
     }
 
@@ -672,13 +709,13 @@ mod tests {
     fn test_bcc() {
         let mut cpu: CPU = CPU::new(0xFF, 0x20, 0, 0);
         let mut mem: Memory = Memory::new();
-        mem.push_back_ins(Instruction::new(ADC_I, &vec![0x00]));
-        mem.push_back_ins(Instruction::new(BCC, &vec![0x1])); // 1 - Byte offset because INX is 1 Byte lone (jump over it)
-        mem.push_back_ins(Instruction::new(INX, &vec![]));
-        mem.push_back_ins(Instruction::new(DEX, &vec![]));
-        cpu.execute(&mut mem);
-        cpu.execute(&mut mem);
-        cpu.execute(&mut mem);
+        mem.push_back_ins(Instruction::new(ADC_I, &vec![0x00]).unwrap());
+        mem.push_back_ins(Instruction::new(BCC, &vec![0x1]).unwrap()); // 1 - Byte offset because INX is 1 Byte lone (jump over it)
+        mem.push_back_ins(Instruction::new(INX, &vec![]).unwrap());
+        mem.push_back_ins(Instruction::new(DEX, &vec![]).unwrap());
+        cpu.execute(&mut mem).unwrap();
+        cpu.execute(&mut mem).unwrap();
+        cpu.execute(&mut mem).unwrap();
         assert_eq!(cpu.x().value, 0x1F);
     }
 
@@ -686,13 +723,13 @@ mod tests {
     fn test_bcs() {
         let mut cpu: CPU = CPU::new(0xFF, 0x20, 0, 0);
         let mut mem: Memory = Memory::new();
-        mem.push_back_ins(Instruction::new(ADC_I, &vec![0x01]));
-        mem.push_back_ins(Instruction::new(BCS, &vec![0x1])); // 1 - Byte offset because INX is 1 Byte lone (jump over it)
-        mem.push_back_ins(Instruction::new(INX, &vec![]));
-        mem.push_back_ins(Instruction::new(DEX, &vec![]));
-        cpu.execute(&mut mem);
-        cpu.execute(&mut mem);
-        cpu.execute(&mut mem);
+        mem.push_back_ins(Instruction::new(ADC_I, &vec![0x01]).unwrap());
+        mem.push_back_ins(Instruction::new(BCS, &vec![0x1]).unwrap()); // 1 - Byte offset because INX is 1 Byte lone (jump over it)
+        mem.push_back_ins(Instruction::new(INX, &vec![]).unwrap());
+        mem.push_back_ins(Instruction::new(DEX, &vec![]).unwrap());
+        cpu.execute(&mut mem).unwrap();
+        cpu.execute(&mut mem).unwrap();
+        cpu.execute(&mut mem).unwrap();
         assert_eq!(cpu.x().value, 0x1F);
     }
 
@@ -700,13 +737,13 @@ mod tests {
     fn test_bne() {
         let mut cpu: CPU = CPU::new(0xFF, 0x20, 0, 0);
         let mut mem: Memory = Memory::new();
-        mem.push_back_ins(Instruction::new(CMP_I, &vec![0x00]));
-        mem.push_back_ins(Instruction::new(BNE, &vec![0x1])); // 1 - Byte offset because INX is 1 Byte lone (jump over it)
-        mem.push_back_ins(Instruction::new(INX, &vec![]));
-        mem.push_back_ins(Instruction::new(DEX, &vec![]));
-        cpu.execute(&mut mem);
-        cpu.execute(&mut mem);
-        cpu.execute(&mut mem);
+        mem.push_back_ins(Instruction::new(CMP_I, &vec![0x00]).unwrap());
+        mem.push_back_ins(Instruction::new(BNE, &vec![0x1]).unwrap()); // 1 - Byte offset because INX is 1 Byte lone (jump over it)
+        mem.push_back_ins(Instruction::new(INX, &vec![]).unwrap());
+        mem.push_back_ins(Instruction::new(DEX, &vec![]).unwrap());
+        cpu.execute(&mut mem).unwrap();
+        cpu.execute(&mut mem).unwrap();
+        cpu.execute(&mut mem).unwrap();
         assert_eq!(cpu.x().value, 0x1F);
     }
 
@@ -714,13 +751,13 @@ mod tests {
     fn test_beq() {
         let mut cpu: CPU = CPU::new(0xFF, 0x20, 0, 0);
         let mut mem: Memory = Memory::new();
-        mem.push_back_ins(Instruction::new(CMP_I, &vec![0xFF]));
-        mem.push_back_ins(Instruction::new(BEQ, &vec![0x1])); // 1 - Byte offset because INX is 1 Byte lone (jump over it)
-        mem.push_back_ins(Instruction::new(INX, &vec![]));
-        mem.push_back_ins(Instruction::new(DEX, &vec![]));
-        cpu.execute(&mut mem);
-        cpu.execute(&mut mem);
-        cpu.execute(&mut mem);
+        mem.push_back_ins(Instruction::new(CMP_I, &vec![0xFF]).unwrap());
+        mem.push_back_ins(Instruction::new(BEQ, &vec![0x1]).unwrap()); // 1 - Byte offset because INX is 1 Byte lone (jump over it)
+        mem.push_back_ins(Instruction::new(INX, &vec![]).unwrap());
+        mem.push_back_ins(Instruction::new(DEX, &vec![]).unwrap());
+        cpu.execute(&mut mem).unwrap();
+        cpu.execute(&mut mem).unwrap();
+        cpu.execute(&mut mem).unwrap();
         assert_eq!(cpu.x().value, 0x1F);
     }
 
@@ -728,9 +765,9 @@ mod tests {
     fn test_dec_zp() {
         let mut cpu: CPU = CPU::new(0, 0, 0, 0);
         let mut mem: Memory = Memory::new();
-        mem.push_back_ins(Instruction::new(DEC_ZP, &vec![0x10]));
+        mem.push_back_ins(Instruction::new(DEC_ZP, &vec![0x10]).unwrap());
         mem.write_byte(&(ZP_S + 0x10), &(0x14));
-        cpu.execute(&mut mem);
+        cpu.execute(&mut mem).unwrap();
         assert_eq!(*mem.read_byte(&(ZP_S + 0x10)), 0x13);
     }
 
@@ -738,9 +775,9 @@ mod tests {
     fn test_dec_zpx() {
         let mut cpu: CPU = CPU::new(0, 0x10, 0, 0);
         let mut mem: Memory = Memory::new();
-        mem.push_back_ins(Instruction::new(DEC_ZPX, &vec![0x10]));
+        mem.push_back_ins(Instruction::new(DEC_ZPX, &vec![0x10]).unwrap());
         mem.write_byte(&(ZP_S + 0x20), &(0x14));
-        cpu.execute(&mut mem);
+        cpu.execute(&mut mem).unwrap();
         assert_eq!(*mem.read_byte(&(ZP_S + 0x20)), 0x13);
     }
 
@@ -748,9 +785,9 @@ mod tests {
     fn test_dec_a() {
         let mut cpu: CPU = CPU::new(0, 0, 0, 0);
         let mut mem: Memory = Memory::new();
-        mem.push_back_ins(Instruction::new(DEC_A, &vec![0x10, 0x40]));
+        mem.push_back_ins(Instruction::new(DEC_A, &vec![0x10, 0x40]).unwrap());
         mem.write_byte(&(0x4010), &(0x14));
-        cpu.execute(&mut mem);
+        cpu.execute(&mut mem).unwrap();
         assert_eq!(*mem.read_byte(&(0x4010)), 0x13);
     }
 
@@ -758,9 +795,9 @@ mod tests {
     fn test_dec_ax() {
         let mut cpu: CPU = CPU::new(0, 0x10, 0, 0);
         let mut mem: Memory = Memory::new();
-        mem.push_back_ins(Instruction::new(DEC_AX, &vec![0x10, 0x40]));
+        mem.push_back_ins(Instruction::new(DEC_AX, &vec![0x10, 0x40]).unwrap());
         mem.write_byte(&(0x4010 + 0x10), &(0x14));
-        cpu.execute(&mut mem);
+        cpu.execute(&mut mem).unwrap();
         assert_eq!(*mem.read_byte(&(0x4010 + 0x10)), 0x13);
     }
 
@@ -768,9 +805,9 @@ mod tests {
     fn test_inc_zp() {
         let mut cpu: CPU = CPU::new(0, 0, 0, 0);
         let mut mem: Memory = Memory::new();
-        mem.push_back_ins(Instruction::new(INC_ZP, &vec![0x10]));
+        mem.push_back_ins(Instruction::new(INC_ZP, &vec![0x10]).unwrap());
         mem.write_byte(&(ZP_S + 0x10), &(0x14));
-        cpu.execute(&mut mem);
+        cpu.execute(&mut mem).unwrap();
         assert_eq!(*mem.read_byte(&(ZP_S + 0x10)), 0x15);
     }
 
@@ -778,9 +815,9 @@ mod tests {
     fn test_inc_zpx() {
         let mut cpu: CPU = CPU::new(0, 0x10, 0, 0);
         let mut mem: Memory = Memory::new();
-        mem.push_back_ins(Instruction::new(INC_ZPX, &vec![0x10]));
+        mem.push_back_ins(Instruction::new(INC_ZPX, &vec![0x10]).unwrap());
         mem.write_byte(&(ZP_S + 0x20), &(0x14));
-        cpu.execute(&mut mem);
+        cpu.execute(&mut mem).unwrap();
         assert_eq!(*mem.read_byte(&(ZP_S + 0x20)), 0x15);
     }
 
@@ -788,9 +825,9 @@ mod tests {
     fn test_inc_a() {
         let mut cpu: CPU = CPU::new(0, 0, 0, 0);
         let mut mem: Memory = Memory::new();
-        mem.push_back_ins(Instruction::new(INC_A, &vec![0x10, 0x40]));
+        mem.push_back_ins(Instruction::new(INC_A, &vec![0x10, 0x40]).unwrap());
         mem.write_byte(&(0x4010), &(0x14));
-        cpu.execute(&mut mem);
+        cpu.execute(&mut mem).unwrap();
         assert_eq!(*mem.read_byte(&(0x4010)), 0x15);
     }
 
@@ -798,9 +835,9 @@ mod tests {
     fn test_inc_ax() {
         let mut cpu: CPU = CPU::new(0, 0x10, 0, 0);
         let mut mem: Memory = Memory::new();
-        mem.push_back_ins(Instruction::new(INC_AX, &vec![0x10, 0x40]));
+        mem.push_back_ins(Instruction::new(INC_AX, &vec![0x10, 0x40]).unwrap());
         mem.write_byte(&(0x4010 + 0x10), &(0x14));
-        cpu.execute(&mut mem);
+        cpu.execute(&mut mem).unwrap();
         assert_eq!(*mem.read_byte(&(0x4010 + 0x10)), 0x15);
     }
 
@@ -808,12 +845,12 @@ mod tests {
     fn test_jmp_a() {
         let mut cpu: CPU = CPU::new(0, 0x10, 0, 0);
         let mut mem: Memory = Memory::new();
-        mem.push_back_ins(Instruction::new(JMP_A, &vec![0x05, 0x80]));
-        mem.push_back_ins(Instruction::new(INX, &vec![]));
-        mem.push_back_ins(Instruction::new(DEX, &vec![]));
-        mem.push_back_ins(Instruction::new(INX, &vec![]));
-        cpu.execute(&mut mem);
-        cpu.execute(&mut mem);
+        mem.push_back_ins(Instruction::new(JMP_A, &vec![0x05, 0x80]).unwrap());
+        mem.push_back_ins(Instruction::new(INX, &vec![]).unwrap());
+        mem.push_back_ins(Instruction::new(DEX, &vec![]).unwrap());
+        mem.push_back_ins(Instruction::new(INX, &vec![]).unwrap());
+        cpu.execute(&mut mem).unwrap();
+        cpu.execute(&mut mem).unwrap();
         assert_eq!(cpu.x().value, 0x11);
     }
 
@@ -821,14 +858,3493 @@ mod tests {
     fn test_jmp_i() {
         let mut cpu: CPU = CPU::new(0, 0x10, 0, 0);
         let mut mem: Memory = Memory::new();
-        mem.push_back_ins(Instruction::new(JMP_I, &vec![0x00, 0x40]));
-        mem.push_back_ins(Instruction::new(INX, &vec![]));
-        mem.push_back_ins(Instruction::new(DEX, &vec![]));
-        mem.push_back_ins(Instruction::new(INX, &vec![]));
+        mem.push_back_ins(Instruction::new(JMP_I, &vec![0x00, 0x40]).unwrap());
+        mem.push_back_ins(Instruction::new(INX, &vec![]).unwrap());
+        mem.push_back_ins(Instruction::new(DEX, &vec![]).unwrap());
+        mem.push_back_ins(Instruction::new(INX, &vec![]).unwrap());
         mem.write_byte(&(0x4000), &(0x05));
         mem.write_byte(&(0x4001), &(0x80));
-        cpu.execute(&mut mem);
-        cpu.execute(&mut mem);
+        cpu.execute(&mut mem).unwrap();
+        cpu.execute(&mut mem).unwrap();
+        assert_eq!(cpu.x().value, 0x11);
+    }
+
+    #[test]
+    fn test_txs() {
+        let mut cpu: CPU = CPU::new(0, 0x80, 0, 0);
+        let mut mem: Memory = Memory::new();
+        mem.push_back_ins(Instruction::new(TXS, &vec![]).unwrap());
+        cpu.execute(&mut mem).unwrap();
+        assert_eq!(cpu.s().value, 0x80);
+    }
+
+    #[test]
+    fn test_tsx() {
+        let mut cpu: CPU = CPU::new(0, 0, 0, 0);
+        let mut mem: Memory = Memory::new();
+        mem.push_back_ins(Instruction::new(TSX, &vec![]).unwrap());
+        cpu.execute(&mut mem).unwrap();
+        assert_eq!(cpu.x().value, 0xFF);
+        assert_eq!(cpu.n_flag(), true);
+        assert_eq!(cpu.z_flag(), false);
+    }
+
+    #[test]
+    fn test_pha() {
+        let mut cpu: CPU = CPU::new(0x42, 0, 0, 0);
+        let mut mem: Memory = Memory::new();
+        mem.push_back_ins(Instruction::new(PHA, &vec![]).unwrap());
+        cpu.execute(&mut mem).unwrap();
+        assert_eq!(*mem.read_byte(&(0x01FF)), 0x42);
+        assert_eq!(cpu.s().value, 0xFE);
+    }
+
+    #[test]
+    fn test_pla() {
+        let mut cpu: CPU = CPU::new(0x42, 0, 0, 0);
+        let mut mem: Memory = Memory::new();
+        mem.push_back_ins(Instruction::new(PHA, &vec![]).unwrap());
+        mem.push_back_ins(Instruction::new(LDA_I, &vec![0x00]).unwrap());
+        mem.push_back_ins(Instruction::new(PLA, &vec![]).unwrap());
+        cpu.execute(&mut mem).unwrap();
+        cpu.execute(&mut mem).unwrap();
+        cpu.execute(&mut mem).unwrap();
+        assert_eq!(cpu.a().value, 0x42);
+        assert_eq!(cpu.s().value, 0xFF);
+        assert_eq!(cpu.z_flag(), false);
+        assert_eq!(cpu.n_flag(), false);
+    }
+
+    #[test]
+    fn test_php() {
+        let mut cpu: CPU = CPU::new(0x10, 0, 0, 0);
+        let mut mem: Memory = Memory::new();
+        mem.push_back_ins(Instruction::new(CMP_I, &vec![0x10]).unwrap());
+        mem.push_back_ins(Instruction::new(PHP, &vec![]).unwrap());
+        cpu.execute(&mut mem).unwrap();
+        cpu.execute(&mut mem).unwrap();
+        /* Z and C from the compare, B and the unused bit are always pushed as set */
+        assert_eq!(*mem.read_byte(&(0x01FF)), 0b0011_0011);
+        assert_eq!(cpu.s().value, 0xFE);
+    }
+
+    #[test]
+    fn test_plp() {
+        let mut cpu: CPU = CPU::new(0, 0, 0, 0);
+        let mut mem: Memory = Memory::new();
+        mem.push_back_ins(Instruction::new(LDA_I, &vec![0xFF]).unwrap());
+        mem.push_back_ins(Instruction::new(PHA, &vec![]).unwrap());
+        mem.push_back_ins(Instruction::new(PLP, &vec![]).unwrap());
+        cpu.execute(&mut mem).unwrap();
+        cpu.execute(&mut mem).unwrap();
+        cpu.execute(&mut mem).unwrap();
+        assert_eq!(cpu.n_flag(), true);
+        assert_eq!(cpu.v_flag(), true);
+        assert_eq!(cpu.d_flag(), true);
+        assert_eq!(cpu.i_flag(), true);
+        assert_eq!(cpu.z_flag(), true);
+        assert_eq!(cpu.c_flag(), true);
+        assert_eq!(cpu.s().value, 0xFF);
+    }
+
+    #[test]
+    fn test_jsr() {
+        let mut cpu: CPU = CPU::new(0, 0, 0, 0);
+        let mut mem: Memory = Memory::new();
+        mem.push_back_ins(Instruction::new(JSR, &vec![0x34, 0x92]).unwrap());
+        cpu.execute(&mut mem).unwrap();
+        assert_eq!(cpu.program_counter().value, 0x9234);
+        /* the return address points to the last byte of the JSR instruction */
+        assert_eq!(*mem.read_byte(&(0x01FF)), 0x80);
+        assert_eq!(*mem.read_byte(&(0x01FE)), 0x02);
+        assert_eq!(cpu.s().value, 0xFD);
+    }
+
+    #[test]
+    fn test_rts() {
+        let mut cpu: CPU = CPU::new(0, 0x10, 0, 0);
+        let mut mem: Memory = Memory::new();
+        mem.push_back_ins(Instruction::new(JSR, &vec![0x04, 0x80]).unwrap());
+        mem.push_back_ins(Instruction::new(INX, &vec![]).unwrap());
+        mem.push_back_ins(Instruction::new(RTS, &vec![]).unwrap());
+        cpu.execute(&mut mem).unwrap();
+        cpu.execute(&mut mem).unwrap();
+        assert_eq!(cpu.program_counter().value, 0x8003);
+        assert_eq!(cpu.s().value, 0xFF);
+        cpu.execute(&mut mem).unwrap();
+        assert_eq!(cpu.x().value, 0x11);
+    }
+
+    #[test]
+    fn test_brk() {
+        let mut cpu: CPU = CPU::new(0, 0, 0, 0);
+        let mut mem: Memory = Memory::new();
+        mem.push_back_ins(Instruction::new(BRK, &vec![]).unwrap());
+        mem.write_byte(&(0xFFFE), &(0x00));
+        mem.write_byte(&(0xFFFF), &(0x90));
+        cpu.execute(&mut mem).unwrap();
+        assert_eq!(cpu.program_counter().value, 0x9000);
+        assert_eq!(*mem.read_byte(&(0x01FF)), 0x80);
+        assert_eq!(*mem.read_byte(&(0x01FE)), 0x02);
+        /* the pushed status has the B flag and the unused bit set */
+        assert_eq!(*mem.read_byte(&(0x01FD)), 0b0011_0000);
+        assert_eq!(cpu.i_flag(), true);
+        assert_eq!(cpu.s().value, 0xFC);
+    }
+
+    #[test]
+    fn test_rti() {
+        let mut cpu: CPU = CPU::new(0x10, 0, 0, 0);
+        let mut mem: Memory = Memory::new();
+        mem.push_back_ins(Instruction::new(CMP_I, &vec![0x10]).unwrap());
+        mem.push_back_ins(Instruction::new(BRK, &vec![]).unwrap());
+        mem.write_byte(&(0xFFFE), &(0x00));
+        mem.write_byte(&(0xFFFF), &(0x90));
+        mem.write_byte(&(0x9000), &(RTI as u8));
+        cpu.execute(&mut mem).unwrap();
+        cpu.execute(&mut mem).unwrap();
+        cpu.execute(&mut mem).unwrap();
+        assert_eq!(cpu.program_counter().value, 0x8004);
+        assert_eq!(cpu.i_flag(), false);
+        assert_eq!(cpu.z_flag(), true);
+        assert_eq!(cpu.c_flag(), true);
+        assert_eq!(cpu.s().value, 0xFF);
+    }
+
+    #[test]
+    fn test_irq() {
+        let mut cpu: CPU = CPU::new(0, 0x10, 0, 0);
+        let mut mem: Memory = Memory::new();
+        mem.push_back_ins(Instruction::new(INX, &vec![]).unwrap());
+        mem.write_byte(&(0xFFFE), &(0x00));
+        mem.write_byte(&(0xFFFF), &(0x90));
+        mem.write_byte(&(0x9000), &(DEX as u8));
+        cpu.assert_irq();
+        cpu.execute(&mut mem).unwrap();
+        assert_eq!(cpu.program_counter().value, 0x9000);
+        assert_eq!(*mem.read_byte(&(0x01FF)), 0x80);
+        assert_eq!(*mem.read_byte(&(0x01FE)), 0x00);
+        /* a hardware interrupt pushes the status with the B flag cleared */
+        assert_eq!(*mem.read_byte(&(0x01FD)), 0b0010_0000);
+        assert_eq!(cpu.i_flag(), true);
+        /* the line is still asserted but I is set now */
+        cpu.execute(&mut mem).unwrap();
+        assert_eq!(cpu.x().value, 0x0F);
+    }
+
+    #[test]
+    fn test_irq_masked() {
+        let mut cpu: CPU = CPU::new(0x04, 0x10, 0, 0);
+        let mut mem: Memory = Memory::new();
+        mem.push_back_ins(Instruction::new(PHA, &vec![]).unwrap());
+        mem.push_back_ins(Instruction::new(PLP, &vec![]).unwrap());
+        mem.push_back_ins(Instruction::new(INX, &vec![]).unwrap());
+        mem.write_byte(&(0xFFFE), &(0x00));
+        mem.write_byte(&(0xFFFF), &(0x90));
+        cpu.execute(&mut mem).unwrap();
+        cpu.execute(&mut mem).unwrap();
+        cpu.assert_irq();
+        cpu.execute(&mut mem).unwrap();
+        assert_eq!(cpu.x().value, 0x11);
+        assert_eq!(cpu.program_counter().value, 0x8003);
+    }
+
+    #[test]
+    fn test_nmi() {
+        let mut cpu: CPU = CPU::new(0x04, 0x10, 0, 0);
+        let mut mem: Memory = Memory::new();
+        mem.push_back_ins(Instruction::new(PHA, &vec![]).unwrap());
+        mem.push_back_ins(Instruction::new(PLP, &vec![]).unwrap());
+        mem.write_byte(&(0xFFFA), &(0x00));
+        mem.write_byte(&(0xFFFB), &(0xA0));
+        mem.write_byte(&(0xA000), &(INX as u8));
+        mem.write_byte(&(0xA001), &(INX as u8));
+        cpu.execute(&mut mem).unwrap();
+        cpu.execute(&mut mem).unwrap();
+        /* NMI can not be masked by the I flag */
+        cpu.assert_nmi();
+        cpu.execute(&mut mem).unwrap();
+        assert_eq!(cpu.program_counter().value, 0xA000);
+        assert_eq!(*mem.read_byte(&(0x01FF)), 0x80);
+        assert_eq!(*mem.read_byte(&(0x01FE)), 0x02);
+        /* holding the line does not trigger another NMI */
+        cpu.execute(&mut mem).unwrap();
+        cpu.assert_nmi();
+        cpu.execute(&mut mem).unwrap();
+        assert_eq!(cpu.x().value, 0x12);
+    }
+
+    #[test]
+    fn test_nmi_priority() {
+        let mut cpu: CPU = CPU::new(0, 0, 0, 0);
+        let mut mem: Memory = Memory::new();
+        mem.write_byte(&(0xFFFA), &(0x00));
+        mem.write_byte(&(0xFFFB), &(0xA0));
+        mem.write_byte(&(0xFFFE), &(0x00));
+        mem.write_byte(&(0xFFFF), &(0x90));
+        cpu.assert_irq();
+        cpu.assert_nmi();
+        cpu.execute(&mut mem).unwrap();
+        assert_eq!(cpu.program_counter().value, 0xA000);
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut cpu: CPU = CPU::new(0, 0x10, 0, 0);
+        let mut mem: Memory = Memory::new();
+        mem.write_byte(&(0xFFFC), &(0x00));
+        mem.write_byte(&(0xFFFD), &(0xC0));
+        mem.write_byte(&(0xC000), &(INX as u8));
+        cpu.reset(&mut mem);
+        assert_eq!(cpu.program_counter().value, 0xC000);
+        assert_eq!(cpu.s().value, 0xFC);
+        assert_eq!(cpu.i_flag(), true);
+        /* the reset sequence does not write to the stack */
+        assert_eq!(*mem.read_byte(&(0x01FF)), 0x00);
+        cpu.execute(&mut mem).unwrap();
         assert_eq!(cpu.x().value, 0x11);
     }
+
+    #[test]
+    fn test_power_on() {
+        let mut mem: Memory = Memory::new();
+        mem.write_byte(&(0xFFFC), &(0x34));
+        mem.write_byte(&(0xFFFD), &(0x12));
+        let cpu: CPU = CPU::power_on(&mut mem);
+        assert_eq!(cpu.program_counter().value, 0x1234);
+        assert_eq!(cpu.s().value, 0xFD);
+        assert_eq!(cpu.i_flag(), true);
+    }
+
+    #[test]
+    fn test_eor_i() {
+        let mut cpu: CPU = CPU::new(0xFF, 0, 0, 0);
+        let mut mem: Memory = Memory::new();
+        mem.push_back_ins(Instruction::new(EOR_I, &vec![0xF0]).unwrap());
+        cpu.execute(&mut mem).unwrap();
+        assert_eq!(cpu.a().value, 0x0F);
+        assert_eq!(cpu.n_flag(), false);
+    }
+
+    #[test]
+    fn test_eor_zp() {
+        let mut cpu: CPU = CPU::new(0xFF, 0, 0, 0);
+        let mut mem: Memory = Memory::new();
+        mem.push_back_ins(Instruction::new(EOR_ZP, &vec![0x20]).unwrap());
+        mem.write_byte(&(ZP_S + 0x20), &(0xF0));
+        cpu.execute(&mut mem).unwrap();
+        assert_eq!(cpu.a().value, 0x0F);
+    }
+
+    #[test]
+    fn test_eor_zpx() {
+        let mut cpu: CPU = CPU::new(0xFF, 0x10, 0, 0);
+        let mut mem: Memory = Memory::new();
+        mem.push_back_ins(Instruction::new(EOR_ZPX, &vec![0x20]).unwrap());
+        mem.write_byte(&(ZP_S + 0x30), &(0xF0));
+        cpu.execute(&mut mem).unwrap();
+        assert_eq!(cpu.a().value, 0x0F);
+    }
+
+    #[test]
+    fn test_eor_a() {
+        let mut cpu: CPU = CPU::new(0xFF, 0, 0, 0);
+        let mut mem: Memory = Memory::new();
+        mem.push_back_ins(Instruction::new(EOR_A, &vec![0x20, 0x40]).unwrap());
+        mem.write_byte(&(0x4020), &(0xF0));
+        cpu.execute(&mut mem).unwrap();
+        assert_eq!(cpu.a().value, 0x0F);
+    }
+
+    #[test]
+    fn test_eor_ax() {
+        let mut cpu: CPU = CPU::new(0xFF, 0xAA, 0, 0);
+        let mut mem: Memory = Memory::new();
+        mem.push_back_ins(Instruction::new(EOR_AX, &vec![0x20, 0x40]).unwrap());
+        mem.write_byte(&(0x4020 + 0xAA), &(0xF0));
+        cpu.execute(&mut mem).unwrap();
+        assert_eq!(cpu.a().value, 0x0F);
+    }
+
+    #[test]
+    fn test_eor_ay() {
+        let mut cpu: CPU = CPU::new(0xFF, 0, 0xAA, 0);
+        let mut mem: Memory = Memory::new();
+        mem.push_back_ins(Instruction::new(EOR_AY, &vec![0x20, 0x40]).unwrap());
+        mem.write_byte(&(0x4020 + 0xAA), &(0xF0));
+        cpu.execute(&mut mem).unwrap();
+        assert_eq!(cpu.a().value, 0x0F);
+    }
+
+    #[test]
+    fn test_eor_ix() {
+        let mut cpu: CPU = CPU::new(0xFF, 0x10, 0, 0);
+        let mut mem: Memory = Memory::new();
+        mem.push_back_ins(Instruction::new(EOR_IX, &vec![0x20]).unwrap());
+        mem.write_byte(&(0x30), &(0x10));
+        mem.write_byte(&(0x31), &(0x20));
+        mem.write_byte(&(0x2010), &(0xF0));
+        cpu.execute(&mut mem).unwrap();
+        assert_eq!(cpu.a().value, 0x0F);
+    }
+
+    #[test]
+    fn test_eor_iy() {
+        let mut cpu: CPU = CPU::new(0xFF, 0, 0x10, 0);
+        let mut mem: Memory = Memory::new();
+        mem.push_back_ins(Instruction::new(EOR_IY, &vec![0x20]).unwrap());
+        mem.write_byte(&(0x20), &(0x10));
+        mem.write_byte(&(0x21), &(0x20));
+        mem.write_byte(&(0x2010 + 0x10), &(0xFF));
+        cpu.execute(&mut mem).unwrap();
+        assert_eq!(cpu.a().value, 0x00);
+        assert_eq!(cpu.z_flag(), true);
+    }
+
+    #[test]
+    fn test_sbc_i() {
+        let mut cpu: CPU = CPU::new(0x30, 0, 0, 0);
+        let mut mem: Memory = Memory::new();
+        mem.push_back_ins(Instruction::new(SEC, &vec![]).unwrap());
+        mem.push_back_ins(Instruction::new(SBC_I, &vec![0x20]).unwrap());
+        cpu.execute(&mut mem).unwrap();
+        cpu.execute(&mut mem).unwrap();
+        assert_eq!(cpu.a().value, 0x10);
+        assert_eq!(cpu.c_flag(), true);
+        assert_eq!(cpu.v_flag(), false);
+    }
+
+    #[test]
+    fn test_sbc_zp() {
+        let mut cpu: CPU = CPU::new(0x42, 0, 0, 0);
+        let mut mem: Memory = Memory::new();
+        mem.push_back_ins(Instruction::new(SEC, &vec![]).unwrap());
+        mem.push_back_ins(Instruction::new(SBC_ZP, &vec![0x20]).unwrap());
+        mem.write_byte(&(ZP_S + 0x20), &(0x32));
+        cpu.execute(&mut mem).unwrap();
+        cpu.execute(&mut mem).unwrap();
+        assert_eq!(cpu.a().value, 0x10);
+    }
+
+    #[test]
+    fn test_sbc_zpx() {
+        let mut cpu: CPU = CPU::new(0x42, 0x15, 0, 0);
+        let mut mem: Memory = Memory::new();
+        mem.push_back_ins(Instruction::new(SEC, &vec![]).unwrap());
+        mem.push_back_ins(Instruction::new(SBC_ZPX, &vec![0x20]).unwrap());
+        mem.write_byte(&(ZP_S + 0x20 + 0x15), &(0x32));
+        cpu.execute(&mut mem).unwrap();
+        cpu.execute(&mut mem).unwrap();
+        assert_eq!(cpu.a().value, 0x10);
+    }
+
+    #[test]
+    fn test_sbc_a() {
+        let mut cpu: CPU = CPU::new(0x42, 0, 0, 0);
+        let mut mem: Memory = Memory::new();
+        mem.push_back_ins(Instruction::new(SEC, &vec![]).unwrap());
+        mem.push_back_ins(Instruction::new(SBC_A, &vec![0x20, 0x40]).unwrap());
+        mem.write_byte(&(0x4020), &(0x32));
+        cpu.execute(&mut mem).unwrap();
+        cpu.execute(&mut mem).unwrap();
+        assert_eq!(cpu.a().value, 0x10);
+    }
+
+    #[test]
+    fn test_sbc_ax() {
+        let mut cpu: CPU = CPU::new(0x42, 0x20, 0, 0);
+        let mut mem: Memory = Memory::new();
+        mem.push_back_ins(Instruction::new(SEC, &vec![]).unwrap());
+        mem.push_back_ins(Instruction::new(SBC_AX, &vec![0x20, 0x40]).unwrap());
+        mem.write_byte(&(0x4020 + 0x20), &(0x32));
+        cpu.execute(&mut mem).unwrap();
+        cpu.execute(&mut mem).unwrap();
+        assert_eq!(cpu.a().value, 0x10);
+    }
+
+    #[test]
+    fn test_sbc_ay() {
+        let mut cpu: CPU = CPU::new(0x42, 0, 0x20, 0);
+        let mut mem: Memory = Memory::new();
+        mem.push_back_ins(Instruction::new(SEC, &vec![]).unwrap());
+        mem.push_back_ins(Instruction::new(SBC_AY, &vec![0x20, 0x40]).unwrap());
+        mem.write_byte(&(0x4020 + 0x20), &(0x32));
+        cpu.execute(&mut mem).unwrap();
+        cpu.execute(&mut mem).unwrap();
+        assert_eq!(cpu.a().value, 0x10);
+    }
+
+    #[test]
+    fn test_sbc_ix() {
+        let mut cpu: CPU = CPU::new(0x42, 0x10, 0, 0);
+        let mut mem: Memory = Memory::new();
+        mem.push_back_ins(Instruction::new(SEC, &vec![]).unwrap());
+        mem.push_back_ins(Instruction::new(SBC_IX, &vec![0x40]).unwrap());
+        mem.write_byte(&(0x50), &(0x20));
+        mem.write_byte(&(0x51), &(0x32));
+        mem.write_byte(&(0x3220), &(0x32));
+        cpu.execute(&mut mem).unwrap();
+        cpu.execute(&mut mem).unwrap();
+        assert_eq!(cpu.a().value, 0x10);
+    }
+
+    #[test]
+    fn test_sbc_iy() {
+        let mut cpu: CPU = CPU::new(0x42, 0, 0x10, 0);
+        let mut mem: Memory = Memory::new();
+        mem.push_back_ins(Instruction::new(SEC, &vec![]).unwrap());
+        mem.push_back_ins(Instruction::new(SBC_IY, &vec![0x40]).unwrap());
+        mem.write_byte(&(0x40), &(0x11));
+        mem.write_byte(&(0x41), &(0x51));
+        mem.write_byte(&(0x5121), &(0x32));
+        cpu.execute(&mut mem).unwrap();
+        cpu.execute(&mut mem).unwrap();
+        assert_eq!(cpu.a().value, 0x10);
+    }
+
+    #[test]
+    fn test_sbc_borrow() {
+        let mut cpu: CPU = CPU::new(0x10, 0, 0, 0);
+        let mut mem: Memory = Memory::new();
+        mem.push_back_ins(Instruction::new(SBC_I, &vec![0x20]).unwrap());
+        cpu.execute(&mut mem).unwrap();
+        /* the cleared carry subtracts an additional one */
+        assert_eq!(cpu.a().value, 0xEF);
+        assert_eq!(cpu.c_flag(), false);
+        assert_eq!(cpu.n_flag(), true);
+    }
+
+    #[test]
+    fn test_sbc_overflow() {
+        let mut cpu: CPU = CPU::new(0x80, 0, 0, 0);
+        let mut mem: Memory = Memory::new();
+        mem.push_back_ins(Instruction::new(SEC, &vec![]).unwrap());
+        mem.push_back_ins(Instruction::new(SBC_I, &vec![0x01]).unwrap());
+        cpu.execute(&mut mem).unwrap();
+        cpu.execute(&mut mem).unwrap();
+        assert_eq!(cpu.a().value, 0x7F);
+        assert_eq!(cpu.v_flag(), true);
+        assert_eq!(cpu.c_flag(), true);
+    }
+
+    #[test]
+    fn test_rol_acc() {
+        let mut cpu: CPU = CPU::new(0x81, 0, 0, 0);
+        let mut mem: Memory = Memory::new();
+        mem.push_back_ins(Instruction::new(SEC, &vec![]).unwrap());
+        mem.push_back_ins(Instruction::new(ROL_ACC, &vec![]).unwrap());
+        cpu.execute(&mut mem).unwrap();
+        cpu.execute(&mut mem).unwrap();
+        assert_eq!(cpu.a().value, 0x03);
+        assert_eq!(cpu.c_flag(), true);
+    }
+
+    #[test]
+    fn test_rol_zp() {
+        let mut cpu: CPU = CPU::new(0, 0, 0, 0);
+        let mut mem: Memory = Memory::new();
+        mem.push_back_ins(Instruction::new(ROL_ZP, &vec![0x20]).unwrap());
+        mem.write_byte(&(ZP_S + 0x20), &(0x81));
+        cpu.execute(&mut mem).unwrap();
+        assert_eq!(*mem.read_byte(&(ZP_S + 0x20)), 0x02);
+        assert_eq!(cpu.c_flag(), true);
+    }
+
+    #[test]
+    fn test_rol_zpx() {
+        let mut cpu: CPU = CPU::new(0, 0x10, 0, 0);
+        let mut mem: Memory = Memory::new();
+        mem.push_back_ins(Instruction::new(ROL_ZPX, &vec![0x20]).unwrap());
+        mem.write_byte(&(ZP_S + 0x20 + 0x10), &(0x40));
+        cpu.execute(&mut mem).unwrap();
+        assert_eq!(*mem.read_byte(&(ZP_S + 0x20 + 0x10)), 0x80);
+        assert_eq!(cpu.n_flag(), true);
+        assert_eq!(cpu.c_flag(), false);
+    }
+
+    #[test]
+    fn test_rol_a() {
+        let mut cpu: CPU = CPU::new(0, 0, 0, 0);
+        let mut mem: Memory = Memory::new();
+        mem.push_back_ins(Instruction::new(SEC, &vec![]).unwrap());
+        mem.push_back_ins(Instruction::new(ROL_A, &vec![0x20, 0x60]).unwrap());
+        mem.write_byte(&(0x6020), &(0x01));
+        cpu.execute(&mut mem).unwrap();
+        cpu.execute(&mut mem).unwrap();
+        assert_eq!(*mem.read_byte(&(0x6020)), 0x03);
+    }
+
+    #[test]
+    fn test_rol_ax() {
+        let mut cpu: CPU = CPU::new(0, 0x10, 0, 0);
+        let mut mem: Memory = Memory::new();
+        mem.push_back_ins(Instruction::new(ROL_AX, &vec![0x20, 0x60]).unwrap());
+        mem.write_byte(&(0x6020 + 0x10), &(0x80));
+        cpu.execute(&mut mem).unwrap();
+        assert_eq!(*mem.read_byte(&(0x6020 + 0x10)), 0x00);
+        assert_eq!(cpu.z_flag(), true);
+        assert_eq!(cpu.c_flag(), true);
+    }
+
+    #[test]
+    fn test_ror_acc() {
+        let mut cpu: CPU = CPU::new(0x81, 0, 0, 0);
+        let mut mem: Memory = Memory::new();
+        mem.push_back_ins(Instruction::new(ROR_ACC, &vec![]).unwrap());
+        cpu.execute(&mut mem).unwrap();
+        assert_eq!(cpu.a().value, 0x40);
+        assert_eq!(cpu.c_flag(), true);
+    }
+
+    #[test]
+    fn test_ror_zp() {
+        let mut cpu: CPU = CPU::new(0, 0, 0, 0);
+        let mut mem: Memory = Memory::new();
+        mem.push_back_ins(Instruction::new(SEC, &vec![]).unwrap());
+        mem.push_back_ins(Instruction::new(ROR_ZP, &vec![0x20]).unwrap());
+        mem.write_byte(&(ZP_S + 0x20), &(0x02));
+        cpu.execute(&mut mem).unwrap();
+        cpu.execute(&mut mem).unwrap();
+        assert_eq!(*mem.read_byte(&(ZP_S + 0x20)), 0x81);
+        assert_eq!(cpu.n_flag(), true);
+        assert_eq!(cpu.c_flag(), false);
+    }
+
+    #[test]
+    fn test_ror_zpx() {
+        let mut cpu: CPU = CPU::new(0, 0x10, 0, 0);
+        let mut mem: Memory = Memory::new();
+        mem.push_back_ins(Instruction::new(ROR_ZPX, &vec![0x20]).unwrap());
+        mem.write_byte(&(ZP_S + 0x20 + 0x10), &(0x01));
+        cpu.execute(&mut mem).unwrap();
+        assert_eq!(*mem.read_byte(&(ZP_S + 0x20 + 0x10)), 0x00);
+        assert_eq!(cpu.z_flag(), true);
+        assert_eq!(cpu.c_flag(), true);
+    }
+
+    #[test]
+    fn test_ror_a() {
+        let mut cpu: CPU = CPU::new(0, 0, 0, 0);
+        let mut mem: Memory = Memory::new();
+        mem.push_back_ins(Instruction::new(ROR_A, &vec![0x20, 0x60]).unwrap());
+        mem.write_byte(&(0x6020), &(0x80));
+        cpu.execute(&mut mem).unwrap();
+        assert_eq!(*mem.read_byte(&(0x6020)), 0x40);
+    }
+
+    #[test]
+    fn test_ror_ax() {
+        let mut cpu: CPU = CPU::new(0, 0x10, 0, 0);
+        let mut mem: Memory = Memory::new();
+        mem.push_back_ins(Instruction::new(SEC, &vec![]).unwrap());
+        mem.push_back_ins(Instruction::new(ROR_AX, &vec![0x20, 0x60]).unwrap());
+        mem.write_byte(&(0x6020 + 0x10), &(0xFF));
+        cpu.execute(&mut mem).unwrap();
+        cpu.execute(&mut mem).unwrap();
+        assert_eq!(*mem.read_byte(&(0x6020 + 0x10)), 0xFF);
+        assert_eq!(cpu.c_flag(), true);
+    }
+
+    #[test]
+    fn test_lsr_acc() {
+        let mut cpu: CPU = CPU::new(0x81, 0, 0, 0);
+        let mut mem: Memory = Memory::new();
+        mem.push_back_ins(Instruction::new(LSR_ACC, &vec![]).unwrap());
+        cpu.execute(&mut mem).unwrap();
+        assert_eq!(cpu.a().value, 0x40);
+        assert_eq!(cpu.c_flag(), true);
+    }
+
+    #[test]
+    fn test_lsr_zp() {
+        let mut cpu: CPU = CPU::new(0, 0, 0, 0);
+        let mut mem: Memory = Memory::new();
+        mem.push_back_ins(Instruction::new(LSR_ZP, &vec![0x20]).unwrap());
+        mem.write_byte(&(ZP_S + 0x20), &(0x01));
+        cpu.execute(&mut mem).unwrap();
+        assert_eq!(*mem.read_byte(&(ZP_S + 0x20)), 0x00);
+        assert_eq!(cpu.z_flag(), true);
+        assert_eq!(cpu.c_flag(), true);
+    }
+
+    #[test]
+    fn test_lsr_zpx() {
+        let mut cpu: CPU = CPU::new(0, 0x10, 0, 0);
+        let mut mem: Memory = Memory::new();
+        mem.push_back_ins(Instruction::new(LSR_ZPX, &vec![0x20]).unwrap());
+        mem.write_byte(&(ZP_S + 0x20 + 0x10), &(0xFE));
+        cpu.execute(&mut mem).unwrap();
+        assert_eq!(*mem.read_byte(&(ZP_S + 0x20 + 0x10)), 0x7F);
+        assert_eq!(cpu.c_flag(), false);
+    }
+
+    #[test]
+    fn test_lsr_a() {
+        let mut cpu: CPU = CPU::new(0, 0, 0, 0);
+        let mut mem: Memory = Memory::new();
+        mem.push_back_ins(Instruction::new(LSR_A, &vec![0x20, 0x60]).unwrap());
+        mem.write_byte(&(0x6020), &(0x04));
+        cpu.execute(&mut mem).unwrap();
+        assert_eq!(*mem.read_byte(&(0x6020)), 0x02);
+    }
+
+    #[test]
+    fn test_lsr_ax() {
+        let mut cpu: CPU = CPU::new(0, 0x10, 0, 0);
+        let mut mem: Memory = Memory::new();
+        mem.push_back_ins(Instruction::new(LSR_AX, &vec![0x20, 0x60]).unwrap());
+        mem.write_byte(&(0x6020 + 0x10), &(0xFF));
+        cpu.execute(&mut mem).unwrap();
+        assert_eq!(*mem.read_byte(&(0x6020 + 0x10)), 0x7F);
+        assert_eq!(cpu.c_flag(), true);
+    }
+
+    #[test]
+    fn test_tax() {
+        let mut cpu: CPU = CPU::new(0x80, 0, 0, 0);
+        let mut mem: Memory = Memory::new();
+        mem.push_back_ins(Instruction::new(TAX, &vec![]).unwrap());
+        cpu.execute(&mut mem).unwrap();
+        assert_eq!(cpu.x().value, 0x80);
+        assert_eq!(cpu.n_flag(), true);
+    }
+
+    #[test]
+    fn test_tay() {
+        let mut cpu: CPU = CPU::new(0x00, 0, 0x10, 0);
+        let mut mem: Memory = Memory::new();
+        mem.push_back_ins(Instruction::new(TAY, &vec![]).unwrap());
+        cpu.execute(&mut mem).unwrap();
+        assert_eq!(cpu.y().value, 0x00);
+        assert_eq!(cpu.z_flag(), true);
+    }
+
+    #[test]
+    fn test_txa() {
+        let mut cpu: CPU = CPU::new(0, 0x12, 0, 0);
+        let mut mem: Memory = Memory::new();
+        mem.push_back_ins(Instruction::new(TXA, &vec![]).unwrap());
+        cpu.execute(&mut mem).unwrap();
+        assert_eq!(cpu.a().value, 0x12);
+        assert_eq!(cpu.z_flag(), false);
+    }
+
+    #[test]
+    fn test_tya() {
+        let mut cpu: CPU = CPU::new(0, 0, 0x12, 0);
+        let mut mem: Memory = Memory::new();
+        mem.push_back_ins(Instruction::new(TYA, &vec![]).unwrap());
+        cpu.execute(&mut mem).unwrap();
+        assert_eq!(cpu.a().value, 0x12);
+        assert_eq!(cpu.n_flag(), false);
+    }
+
+    #[test]
+    fn test_clc() {
+        let mut cpu: CPU = CPU::new(0, 0, 0, 0);
+        let mut mem: Memory = Memory::new();
+        mem.push_back_ins(Instruction::new(SEC, &vec![]).unwrap());
+        mem.push_back_ins(Instruction::new(CLC, &vec![]).unwrap());
+        cpu.execute(&mut mem).unwrap();
+        cpu.execute(&mut mem).unwrap();
+        assert_eq!(cpu.c_flag(), false);
+    }
+
+    #[test]
+    fn test_sec() {
+        let mut cpu: CPU = CPU::new(0, 0, 0, 0);
+        let mut mem: Memory = Memory::new();
+        mem.push_back_ins(Instruction::new(SEC, &vec![]).unwrap());
+        cpu.execute(&mut mem).unwrap();
+        assert_eq!(cpu.c_flag(), true);
+    }
+
+    #[test]
+    fn test_cli() {
+        let mut cpu: CPU = CPU::new(0, 0, 0, 0);
+        let mut mem: Memory = Memory::new();
+        mem.push_back_ins(Instruction::new(SEI, &vec![]).unwrap());
+        mem.push_back_ins(Instruction::new(CLI, &vec![]).unwrap());
+        cpu.execute(&mut mem).unwrap();
+        cpu.execute(&mut mem).unwrap();
+        assert_eq!(cpu.i_flag(), false);
+    }
+
+    #[test]
+    fn test_sei() {
+        let mut cpu: CPU = CPU::new(0, 0, 0, 0);
+        let mut mem: Memory = Memory::new();
+        mem.push_back_ins(Instruction::new(SEI, &vec![]).unwrap());
+        cpu.execute(&mut mem).unwrap();
+        assert_eq!(cpu.i_flag(), true);
+    }
+
+    #[test]
+    fn test_clv() {
+        let mut cpu: CPU = CPU::new(0x80, 0, 0, 0);
+        let mut mem: Memory = Memory::new();
+        mem.push_back_ins(Instruction::new(SBC_I, &vec![0x01]).unwrap());
+        mem.push_back_ins(Instruction::new(CLV, &vec![]).unwrap());
+        cpu.execute(&mut mem).unwrap();
+        cpu.execute(&mut mem).unwrap();
+        assert_eq!(cpu.v_flag(), false);
+    }
+
+    #[test]
+    fn test_cld() {
+        let mut cpu: CPU = CPU::new(0, 0, 0, 0);
+        let mut mem: Memory = Memory::new();
+        mem.push_back_ins(Instruction::new(SED, &vec![]).unwrap());
+        mem.push_back_ins(Instruction::new(CLD, &vec![]).unwrap());
+        cpu.execute(&mut mem).unwrap();
+        cpu.execute(&mut mem).unwrap();
+        assert_eq!(cpu.d_flag(), false);
+    }
+
+    #[test]
+    fn test_sed() {
+        let mut cpu: CPU = CPU::new(0, 0, 0, 0);
+        let mut mem: Memory = Memory::new();
+        mem.push_back_ins(Instruction::new(SED, &vec![]).unwrap());
+        cpu.execute(&mut mem).unwrap();
+        assert_eq!(cpu.d_flag(), true);
+    }
+
+    #[test]
+    fn test_inx_wrap() {
+        let mut cpu: CPU = CPU::new(0, 0xFF, 0, 0);
+        let mut mem: Memory = Memory::new();
+        mem.push_back_ins(Instruction::new(INX, &vec![]).unwrap());
+        cpu.execute(&mut mem).unwrap();
+        assert_eq!(cpu.x().value, 0x00);
+        assert_eq!(cpu.z_flag(), true);
+    }
+
+    #[test]
+    fn test_dey_wrap() {
+        let mut cpu: CPU = CPU::new(0, 0, 0x00, 0);
+        let mut mem: Memory = Memory::new();
+        mem.push_back_ins(Instruction::new(DEY, &vec![]).unwrap());
+        cpu.execute(&mut mem).unwrap();
+        assert_eq!(cpu.y().value, 0xFF);
+        assert_eq!(cpu.n_flag(), true);
+    }
+
+    #[test]
+    fn test_lda_flags() {
+        let mut cpu: CPU = CPU::new(0, 0, 0, 0);
+        let mut mem: Memory = Memory::new();
+        mem.push_back_ins(Instruction::new(LDA_I, &vec![0x00]).unwrap());
+        cpu.execute(&mut mem).unwrap();
+        assert_eq!(cpu.z_flag(), true);
+        assert_eq!(cpu.n_flag(), false);
+    }
+
+    #[test]
+    fn test_adc_decimal() {
+        let mut cpu: CPU = CPU::new(0x58, 0, 0, 0);
+        let mut mem: Memory = Memory::new();
+        mem.push_back_ins(Instruction::new(SED, &vec![]).unwrap());
+        mem.push_back_ins(Instruction::new(SEC, &vec![]).unwrap());
+        mem.push_back_ins(Instruction::new(ADC_I, &vec![0x46]).unwrap());
+        cpu.execute(&mut mem).unwrap();
+        cpu.execute(&mut mem).unwrap();
+        cpu.execute(&mut mem).unwrap();
+        /* 58 + 46 + 1 = 105 */
+        assert_eq!(cpu.a().value, 0x05);
+        assert_eq!(cpu.c_flag(), true);
+    }
+
+    #[test]
+    fn test_adc_decimal_nmos_flags() {
+        let mut cpu: CPU = CPU::new(0x99, 0, 0, 0);
+        let mut mem: Memory = Memory::new();
+        mem.push_back_ins(Instruction::new(SED, &vec![]).unwrap());
+        mem.push_back_ins(Instruction::new(ADC_I, &vec![0x01]).unwrap());
+        cpu.execute(&mut mem).unwrap();
+        cpu.execute(&mut mem).unwrap();
+        /* Z is taken from the binary sum and N from the unadjusted high nibble */
+        assert_eq!(cpu.a().value, 0x00);
+        assert_eq!(cpu.c_flag(), true);
+        assert_eq!(cpu.z_flag(), false);
+        assert_eq!(cpu.n_flag(), true);
+        assert_eq!(cpu.v_flag(), false);
+    }
+
+    #[test]
+    fn test_adc_decimal_overflow() {
+        let mut cpu: CPU = CPU::new(0x79, 0, 0, 0);
+        let mut mem: Memory = Memory::new();
+        mem.push_back_ins(Instruction::new(SED, &vec![]).unwrap());
+        mem.push_back_ins(Instruction::new(SEC, &vec![]).unwrap());
+        mem.push_back_ins(Instruction::new(ADC_I, &vec![0x00]).unwrap());
+        cpu.execute(&mut mem).unwrap();
+        cpu.execute(&mut mem).unwrap();
+        cpu.execute(&mut mem).unwrap();
+        assert_eq!(cpu.a().value, 0x80);
+        assert_eq!(cpu.v_flag(), true);
+        assert_eq!(cpu.n_flag(), true);
+    }
+
+    #[test]
+    fn test_adc_decimal_cmos_flags() {
+        let mut cpu: CPU = CPU::new(0x99, 0, 0, 0);
+        let mut mem: Memory = Memory::new();
+        cpu.set_variant(Variant::CMOS65C02);
+        mem.push_back_ins(Instruction::new(SED, &vec![]).unwrap());
+        mem.push_back_ins(Instruction::new(ADC_I, &vec![0x01]).unwrap());
+        cpu.execute(&mut mem).unwrap();
+        cpu.execute(&mut mem).unwrap();
+        assert_eq!(cpu.a().value, 0x00);
+        assert_eq!(cpu.c_flag(), true);
+        assert_eq!(cpu.z_flag(), true);
+        assert_eq!(cpu.n_flag(), false);
+    }
+
+    #[test]
+    fn test_sbc_decimal() {
+        let mut cpu: CPU = CPU::new(0x46, 0, 0, 0);
+        let mut mem: Memory = Memory::new();
+        mem.push_back_ins(Instruction::new(SED, &vec![]).unwrap());
+        mem.push_back_ins(Instruction::new(SEC, &vec![]).unwrap());
+        mem.push_back_ins(Instruction::new(SBC_I, &vec![0x17]).unwrap());
+        cpu.execute(&mut mem).unwrap();
+        cpu.execute(&mut mem).unwrap();
+        cpu.execute(&mut mem).unwrap();
+        assert_eq!(cpu.a().value, 0x29);
+        assert_eq!(cpu.c_flag(), true);
+    }
+
+    #[test]
+    fn test_sbc_decimal_borrow() {
+        let mut cpu: CPU = CPU::new(0x00, 0, 0, 0);
+        let mut mem: Memory = Memory::new();
+        mem.push_back_ins(Instruction::new(SED, &vec![]).unwrap());
+        mem.push_back_ins(Instruction::new(SEC, &vec![]).unwrap());
+        mem.push_back_ins(Instruction::new(SBC_I, &vec![0x01]).unwrap());
+        cpu.execute(&mut mem).unwrap();
+        cpu.execute(&mut mem).unwrap();
+        cpu.execute(&mut mem).unwrap();
+        /* N follows the binary result on the NMOS 6502 */
+        assert_eq!(cpu.a().value, 0x99);
+        assert_eq!(cpu.c_flag(), false);
+        assert_eq!(cpu.n_flag(), true);
+    }
+
+    #[test]
+    fn test_sbc_decimal_cmos_flags() {
+        let mut cpu: CPU = CPU::new(0x20, 0, 0, 0);
+        let mut mem: Memory = Memory::new();
+        cpu.set_variant(Variant::CMOS65C02);
+        mem.push_back_ins(Instruction::new(SED, &vec![]).unwrap());
+        mem.push_back_ins(Instruction::new(SEC, &vec![]).unwrap());
+        mem.push_back_ins(Instruction::new(SBC_I, &vec![0x01]).unwrap());
+        cpu.execute(&mut mem).unwrap();
+        cpu.execute(&mut mem).unwrap();
+        cpu.execute(&mut mem).unwrap();
+        /* N and Z are taken from the adjusted result */
+        assert_eq!(cpu.a().value, 0x19);
+        assert_eq!(cpu.c_flag(), true);
+        assert_eq!(cpu.n_flag(), false);
+        assert_eq!(cpu.z_flag(), false);
+    }
+
+    fn status_with_carry(carry: bool) -> StatusRegister {
+        StatusRegister::new(if carry { Flag::C as u8 } else { 0 })
+    }
+
+    #[test]
+    fn test_status_register() {
+        let mut p = StatusRegister::new(0xFF);
+        /* B and the unused bit are not stored */
+        assert_eq!(p.to_byte(false), 0xEF);
+        assert_eq!(p.to_byte(true), 0xFF);
+        p.clear(Flag::N);
+        p.clear(Flag::C);
+        assert_eq!(p.get(Flag::N), false);
+        assert_eq!(p.to_byte(false), 0x6E);
+        p.set_nz(0x00);
+        assert_eq!(p.get(Flag::Z), true);
+        assert_eq!(p.get(Flag::N), false);
+    }
+
+    #[test]
+    fn test_adc_flags_exhaustive() {
+        for a in 0..=0xFFu8 {
+            for value in 0..=0xFFu8 {
+                for carry in [false, true] {
+                    let mut p = status_with_carry(carry);
+                    let result = alu::adc(&mut p, a, value, false);
+                    let unsigned = a as u16 + value as u16 + carry as u16;
+                    let signed = a as i8 as i16 + value as i8 as i16 + carry as i16;
+                    assert_eq!(result, unsigned as u8);
+                    assert_eq!(p.get(Flag::C), unsigned > 0xFF);
+                    assert_eq!(p.get(Flag::V), !(-128..=127).contains(&signed));
+                    assert_eq!(p.get(Flag::Z), result == 0);
+                    assert_eq!(p.get(Flag::N), result >= 0x80);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_sbc_flags_exhaustive() {
+        for a in 0..=0xFFu8 {
+            for value in 0..=0xFFu8 {
+                for carry in [false, true] {
+                    let mut p = status_with_carry(carry);
+                    let result = alu::sbc(&mut p, a, value, false);
+                    let borrow = !carry as i16;
+                    let unsigned = a as i16 - value as i16 - borrow;
+                    let signed = a as i8 as i16 - value as i8 as i16 - borrow;
+                    assert_eq!(result, unsigned as u8);
+                    assert_eq!(p.get(Flag::C), unsigned >= 0);
+                    assert_eq!(p.get(Flag::V), !(-128..=127).contains(&signed));
+                    assert_eq!(p.get(Flag::Z), result == 0);
+                    assert_eq!(p.get(Flag::N), result >= 0x80);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_compare_flags_exhaustive() {
+        for reg in 0..=0xFFu8 {
+            for value in 0..=0xFFu8 {
+                for carry in [false, true] {
+                    /* the incoming carry has no influence on a compare */
+                    let mut p = status_with_carry(carry);
+                    alu::compare(&mut p, reg, value);
+                    assert_eq!(p.get(Flag::C), reg >= value);
+                    assert_eq!(p.get(Flag::Z), reg == value);
+                    assert_eq!(p.get(Flag::N), reg.wrapping_sub(value) >= 0x80);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_bit_flags_exhaustive() {
+        for a in 0..=0xFFu8 {
+            for value in 0..=0xFFu8 {
+                let mut p = StatusRegister::new(0xFF);
+                alu::bit(&mut p, a, value);
+                assert_eq!(p.get(Flag::Z), a & value == 0);
+                assert_eq!(p.get(Flag::N), value & 0x80 != 0);
+                assert_eq!(p.get(Flag::V), value & 0x40 != 0);
+                assert_eq!(p.get(Flag::C), true);
+            }
+        }
+    }
+
+    #[test]
+    fn test_shift_flags_exhaustive() {
+        for value in 0..=0xFFu8 {
+            for carry in [false, true] {
+                let wide = value as u16;
+                let expected = [
+                    (
+                        alu::asl as fn(&mut StatusRegister, u8) -> u8,
+                        wide << 1,
+                        value & 0x80 != 0,
+                    ),
+                    (alu::lsr, wide >> 1, value & 0x01 != 0),
+                    (alu::rol, (wide << 1) | carry as u16, value & 0x80 != 0),
+                    (
+                        alu::ror,
+                        (wide >> 1) | ((carry as u16) << 7),
+                        value & 0x01 != 0,
+                    ),
+                ];
+                for (op, result, carry_out) in expected {
+                    let mut p = status_with_carry(carry);
+                    let shifted = op(&mut p, value);
+                    assert_eq!(shifted, result as u8);
+                    assert_eq!(p.get(Flag::C), carry_out);
+                    assert_eq!(p.get(Flag::Z), shifted == 0);
+                    assert_eq!(p.get(Flag::N), shifted >= 0x80);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_adc_carry_in() {
+        let mut cpu: CPU = CPU::new(0x10, 0, 0, 0);
+        let mut mem: Memory = Memory::new();
+        mem.push_back_ins(Instruction::new(SEC, &vec![]).unwrap());
+        mem.push_back_ins(Instruction::new(ADC_I, &vec![0x20]).unwrap());
+        cpu.execute(&mut mem).unwrap();
+        cpu.execute(&mut mem).unwrap();
+        assert_eq!(cpu.a().value, 0x31);
+        assert_eq!(cpu.c_flag(), false);
+    }
+
+    #[test]
+    fn test_flags_cleared() {
+        let mut cpu: CPU = CPU::new(0x80, 0, 0, 0);
+        let mut mem: Memory = Memory::new();
+        mem.push_back_ins(Instruction::new(CMP_I, &vec![0x80]).unwrap());
+        mem.push_back_ins(Instruction::new(AND_I, &vec![0x81]).unwrap());
+        mem.push_back_ins(Instruction::new(ASL_ACC, &vec![]).unwrap());
+        cpu.execute(&mut mem).unwrap();
+        assert_eq!(cpu.z_flag(), true);
+        cpu.execute(&mut mem).unwrap();
+        /* the Z flag of the compare is cleared by the AND */
+        assert_eq!(cpu.z_flag(), false);
+        assert_eq!(cpu.n_flag(), true);
+        cpu.execute(&mut mem).unwrap();
+        assert_eq!(cpu.a().value, 0x00);
+        assert_eq!(cpu.z_flag(), true);
+        assert_eq!(cpu.n_flag(), false);
+        assert_eq!(cpu.c_flag(), true);
+    }
+
+    #[test]
+    fn test_branch_backwards() {
+        let mut cpu: CPU = CPU::new(0, 0x03, 0, 0);
+        let mut mem: Memory = Memory::new();
+        mem.push_back_ins(Instruction::new(INY, &vec![]).unwrap());
+        mem.push_back_ins(Instruction::new(DEX, &vec![]).unwrap());
+        mem.push_back_ins(Instruction::new(BNE, &vec![0xFC]).unwrap()); // -4: back to INY
+        for _ in 0..9 {
+            cpu.execute(&mut mem).unwrap();
+        }
+        assert_eq!(cpu.x().value, 0x00);
+        assert_eq!(cpu.y().value, 0x03);
+        assert_eq!(cpu.program_counter().value, 0x8004);
+    }
+
+    #[test]
+    fn test_branch_not_taken_cycles() {
+        let mut cpu: CPU = CPU::new(0, 0, 0, 0);
+        let mut mem: Memory = Memory::new();
+        mem.push_back_ins(Instruction::new(BCS, &vec![0x10]).unwrap());
+        cpu.execute(&mut mem).unwrap();
+        assert_eq!(cpu.program_counter().value, 0x8002);
+        assert_eq!(cpu.clock_cycles_elapsed(), 2);
+    }
+
+    #[test]
+    fn test_branch_taken_cycles() {
+        let mut cpu: CPU = CPU::new(0, 0, 0, 0);
+        let mut mem: Memory = Memory::new();
+        mem.push_back_ins(Instruction::new(BCC, &vec![0x10]).unwrap());
+        cpu.execute(&mut mem).unwrap();
+        assert_eq!(cpu.program_counter().value, 0x8012);
+        assert_eq!(cpu.clock_cycles_elapsed(), 3);
+    }
+
+    #[test]
+    fn test_branch_page_cross_cycles() {
+        let mut cpu: CPU = CPU::new(0, 0, 0, 0);
+        let mut mem: Memory = Memory::new();
+        mem.push_back_ins(Instruction::new(BCC, &vec![0x80]).unwrap()); // -128
+        cpu.execute(&mut mem).unwrap();
+        assert_eq!(cpu.program_counter().value, 0x7F82);
+        assert_eq!(cpu.clock_cycles_elapsed(), 4);
+    }
+
+    #[test]
+    fn test_zpx_wraparound() {
+        let mut cpu: CPU = CPU::new(0, 0x02, 0, 0);
+        let mut mem: Memory = Memory::new();
+        mem.push_back_ins(Instruction::new(LDA_ZPX, &vec![0xFF]).unwrap());
+        mem.write_byte(&(ZP_S + 0x01), &(0x42));
+        cpu.execute(&mut mem).unwrap();
+        assert_eq!(cpu.a().value, 0x42);
+    }
+
+    #[test]
+    fn test_zpy_wraparound() {
+        let mut cpu: CPU = CPU::new(0, 0x11, 0x81, 0);
+        let mut mem: Memory = Memory::new();
+        mem.push_back_ins(Instruction::new(STX_ZPY, &vec![0x80]).unwrap());
+        cpu.execute(&mut mem).unwrap();
+        assert_eq!(*mem.read_byte(&(ZP_S + 0x01)), 0x11);
+        assert_eq!(*mem.read_byte(&(0x0101)), 0x00);
+    }
+
+    #[test]
+    fn test_ix_wraparound() {
+        let mut cpu: CPU = CPU::new(0, 0x01, 0, 0);
+        let mut mem: Memory = Memory::new();
+        mem.push_back_ins(Instruction::new(LDA_IX, &vec![0xFE]).unwrap());
+        /* the pointer at $FF takes its high byte from $00 */
+        mem.write_byte(&(0x00FF), &(0x34));
+        mem.write_byte(&(0x0000), &(0x12));
+        mem.write_byte(&(0x1234), &(0x42));
+        cpu.execute(&mut mem).unwrap();
+        assert_eq!(cpu.a().value, 0x42);
+    }
+
+    #[test]
+    fn test_iy_wraparound() {
+        let mut cpu: CPU = CPU::new(0, 0, 0x10, 0);
+        let mut mem: Memory = Memory::new();
+        mem.push_back_ins(Instruction::new(LDA_IY, &vec![0xFF]).unwrap());
+        mem.write_byte(&(0x00FF), &(0xF8));
+        mem.write_byte(&(0x0000), &(0xFF));
+        /* $FFF8 + $10 wraps around to $0008 */
+        mem.write_byte(&(0x0008), &(0x42));
+        cpu.execute(&mut mem).unwrap();
+        assert_eq!(cpu.a().value, 0x42);
+    }
+
+    #[test]
+    fn test_ax_wraparound() {
+        let mut cpu: CPU = CPU::new(0, 0x02, 0, 0);
+        let mut mem: Memory = Memory::new();
+        mem.push_back_ins(Instruction::new(LDA_AX, &vec![0xFF, 0xFF]).unwrap());
+        mem.write_byte(&(0x0001), &(0x42));
+        cpu.execute(&mut mem).unwrap();
+        assert_eq!(cpu.a().value, 0x42);
+    }
+
+    #[test]
+    fn test_jmp_i_page_bug() {
+        let mut cpu: CPU = CPU::new(0, 0, 0, 0);
+        let mut mem: Memory = Memory::new();
+        mem.push_back_ins(Instruction::new(JMP_I, &vec![0xFF, 0x10]).unwrap());
+        mem.write_byte(&(0x10FF), &(0x34));
+        mem.write_byte(&(0x1000), &(0x12));
+        mem.write_byte(&(0x1100), &(0x56));
+        cpu.execute(&mut mem).unwrap();
+        assert_eq!(cpu.program_counter().value, 0x1234);
+    }
+
+    #[test]
+    fn test_jmp_i_cmos() {
+        let mut cpu: CPU = CPU::new(0, 0, 0, 0);
+        let mut mem: Memory = Memory::new();
+        cpu.set_variant(Variant::CMOS65C02);
+        mem.push_back_ins(Instruction::new(JMP_I, &vec![0xFF, 0x10]).unwrap());
+        mem.write_byte(&(0x10FF), &(0x34));
+        mem.write_byte(&(0x1000), &(0x12));
+        mem.write_byte(&(0x1100), &(0x56));
+        cpu.execute(&mut mem).unwrap();
+        assert_eq!(cpu.program_counter().value, 0x5634);
+    }
+
+    #[test]
+    fn test_lda_ax_cycles() {
+        let mut cpu: CPU = CPU::new(0, 0x0F, 0, 0);
+        let mut mem: Memory = Memory::new();
+        mem.push_back_ins(Instruction::new(LDA_AX, &vec![0xF0, 0x10]).unwrap());
+        cpu.execute(&mut mem).unwrap();
+        assert_eq!(cpu.clock_cycles_elapsed(), 4);
+    }
+
+    #[test]
+    fn test_lda_ax_page_cross_cycles() {
+        let mut cpu: CPU = CPU::new(0, 0x10, 0, 0);
+        let mut mem: Memory = Memory::new();
+        mem.push_back_ins(Instruction::new(LDA_AX, &vec![0xF0, 0x10]).unwrap());
+        mem.write_byte(&(0x1100), &(0x42));
+        cpu.execute(&mut mem).unwrap();
+        assert_eq!(cpu.a().value, 0x42);
+        assert_eq!(cpu.clock_cycles_elapsed(), 5);
+    }
+
+    #[test]
+    fn test_cmp_ay_page_cross_cycles() {
+        let mut cpu: CPU = CPU::new(0, 0, 0x01, 0);
+        let mut mem: Memory = Memory::new();
+        mem.push_back_ins(Instruction::new(CMP_AY, &vec![0xFF, 0x10]).unwrap());
+        cpu.execute(&mut mem).unwrap();
+        assert_eq!(cpu.clock_cycles_elapsed(), 5);
+    }
+
+    #[test]
+    fn test_lda_iy_page_cross_cycles() {
+        let mut cpu: CPU = CPU::new(0, 0, 0x20, 0);
+        let mut mem: Memory = Memory::new();
+        mem.push_back_ins(Instruction::new(LDA_IY, &vec![0x10]).unwrap());
+        mem.push_back_ins(Instruction::new(LDA_IY, &vec![0x12]).unwrap());
+        mem.write_byte(&(ZP_S + 0x10), &(0xF0));
+        mem.write_byte(&(ZP_S + 0x11), &(0x10));
+        mem.write_byte(&(ZP_S + 0x12), &(0x00));
+        mem.write_byte(&(ZP_S + 0x13), &(0x10));
+        cpu.execute(&mut mem).unwrap();
+        assert_eq!(cpu.clock_cycles_elapsed(), 6);
+        cpu.execute(&mut mem).unwrap();
+        assert_eq!(cpu.clock_cycles_elapsed(), 11);
+    }
+
+    #[test]
+    fn test_sta_ax_page_cross_cycles() {
+        let mut cpu: CPU = CPU::new(0x42, 0x10, 0, 0);
+        let mut mem: Memory = Memory::new();
+        mem.push_back_ins(Instruction::new(STA_AX, &vec![0xF0, 0x10]).unwrap());
+        cpu.execute(&mut mem).unwrap();
+        assert_eq!(*mem.read_byte(&(0x1100)), 0x42);
+        assert_eq!(cpu.clock_cycles_elapsed(), 5);
+    }
+
+    #[test]
+    fn test_inc_ax_page_cross_cycles() {
+        let mut cpu: CPU = CPU::new(0, 0x10, 0, 0);
+        let mut mem: Memory = Memory::new();
+        mem.push_back_ins(Instruction::new(INC_AX, &vec![0xF0, 0x10]).unwrap());
+        cpu.execute(&mut mem).unwrap();
+        assert_eq!(*mem.read_byte(&(0x1100)), 0x01);
+        assert_eq!(cpu.clock_cycles_elapsed(), 7);
+    }
+
+    #[test]
+    fn test_zero_page_cycles() {
+        let mut cpu: CPU = CPU::new(0, 0, 0, 0);
+        let mut mem: Memory = Memory::new();
+        mem.push_back_ins(Instruction::new(ORA_ZP, &vec![0x10]).unwrap());
+        mem.push_back_ins(Instruction::new(LDX_ZP, &vec![0x10]).unwrap());
+        mem.push_back_ins(Instruction::new(LDY_I, &vec![0x10]).unwrap());
+        cpu.execute(&mut mem).unwrap();
+        assert_eq!(cpu.clock_cycles_elapsed(), 3);
+        cpu.execute(&mut mem).unwrap();
+        assert_eq!(cpu.clock_cycles_elapsed(), 6);
+        cpu.execute(&mut mem).unwrap();
+        assert_eq!(cpu.clock_cycles_elapsed(), 8);
+    }
+
+    #[test]
+    fn test_cycle_tables_agree() {
+        for byte in 0..=u8::MAX {
+            if let Ok(decoded) = Instruction::try_from(byte) {
+                let ins = Instruction::from(*decoded.opc());
+                assert_eq!(ins.cycles(), decoded.cycles(), "opcode {:#04X}", byte);
+                assert_eq!(ins.size(), decoded.size(), "opcode {:#04X}", byte);
+            }
+        }
+    }
+
+    #[test]
+    fn test_slo_zp() {
+        let mut cpu: CPU = CPU::new(0x01, 0, 0, 0);
+        let mut mem: Memory = Memory::new();
+        mem.push_back_ins(Instruction::new(SLO_ZP, &vec![0x10]).unwrap());
+        mem.write_byte(&(ZP_S + 0x10), &(0x81));
+        cpu.execute(&mut mem).unwrap();
+        assert_eq!(*mem.read_byte(&(ZP_S + 0x10)), 0x02);
+        assert_eq!(cpu.a().value, 0x03);
+        assert_eq!(cpu.c_flag(), true);
+        assert_eq!(cpu.clock_cycles_elapsed(), 5);
+    }
+
+    #[test]
+    fn test_rla_zp() {
+        let mut cpu: CPU = CPU::new(0x0F, 0, 0, 0);
+        let mut mem: Memory = Memory::new();
+        mem.push_back_ins(Instruction::new(RLA_ZP, &vec![0x10]).unwrap());
+        mem.write_byte(&(ZP_S + 0x10), &(0x81));
+        cpu.execute(&mut mem).unwrap();
+        assert_eq!(*mem.read_byte(&(ZP_S + 0x10)), 0x02);
+        assert_eq!(cpu.a().value, 0x02);
+        assert_eq!(cpu.c_flag(), true);
+    }
+
+    #[test]
+    fn test_sre_zp() {
+        let mut cpu: CPU = CPU::new(0xFF, 0, 0, 0);
+        let mut mem: Memory = Memory::new();
+        mem.push_back_ins(Instruction::new(SRE_ZP, &vec![0x10]).unwrap());
+        mem.write_byte(&(ZP_S + 0x10), &(0x03));
+        cpu.execute(&mut mem).unwrap();
+        assert_eq!(*mem.read_byte(&(ZP_S + 0x10)), 0x01);
+        assert_eq!(cpu.a().value, 0xFE);
+        assert_eq!(cpu.c_flag(), true);
+        assert_eq!(cpu.n_flag(), true);
+    }
+
+    #[test]
+    fn test_rra_zp() {
+        let mut cpu: CPU = CPU::new(0x10, 0, 0, 0);
+        let mut mem: Memory = Memory::new();
+        mem.push_back_ins(Instruction::new(RRA_ZP, &vec![0x10]).unwrap());
+        mem.write_byte(&(ZP_S + 0x10), &(0x03));
+        cpu.execute(&mut mem).unwrap();
+        /* the carry shifted out by ROR goes into the addition */
+        assert_eq!(*mem.read_byte(&(ZP_S + 0x10)), 0x01);
+        assert_eq!(cpu.a().value, 0x12);
+        assert_eq!(cpu.c_flag(), false);
+    }
+
+    #[test]
+    fn test_dcp_zp() {
+        let mut cpu: CPU = CPU::new(0x41, 0, 0, 0);
+        let mut mem: Memory = Memory::new();
+        mem.push_back_ins(Instruction::new(DCP_ZP, &vec![0x10]).unwrap());
+        mem.write_byte(&(ZP_S + 0x10), &(0x42));
+        cpu.execute(&mut mem).unwrap();
+        assert_eq!(*mem.read_byte(&(ZP_S + 0x10)), 0x41);
+        assert_eq!(cpu.z_flag(), true);
+        assert_eq!(cpu.c_flag(), true);
+    }
+
+    #[test]
+    fn test_isc_zp() {
+        let mut cpu: CPU = CPU::new(0x10, 0, 0, 0);
+        let mut mem: Memory = Memory::new();
+        mem.push_back_ins(Instruction::new(SEC, &vec![]).unwrap());
+        mem.push_back_ins(Instruction::new(ISC_ZP, &vec![0x10]).unwrap());
+        mem.write_byte(&(ZP_S + 0x10), &(0x0F));
+        cpu.execute(&mut mem).unwrap();
+        cpu.execute(&mut mem).unwrap();
+        assert_eq!(*mem.read_byte(&(ZP_S + 0x10)), 0x10);
+        assert_eq!(cpu.a().value, 0x00);
+        assert_eq!(cpu.z_flag(), true);
+        assert_eq!(cpu.c_flag(), true);
+    }
+
+    #[test]
+    fn test_sax_zp() {
+        let mut cpu: CPU = CPU::new(0xF0, 0x3C, 0, 0);
+        let mut mem: Memory = Memory::new();
+        mem.push_back_ins(Instruction::new(SAX_ZP, &vec![0x10]).unwrap());
+        cpu.execute(&mut mem).unwrap();
+        assert_eq!(*mem.read_byte(&(ZP_S + 0x10)), 0x30);
+        assert_eq!(cpu.z_flag(), false);
+    }
+
+    #[test]
+    fn test_lax_zp() {
+        let mut cpu: CPU = CPU::new(0, 0, 0, 0);
+        let mut mem: Memory = Memory::new();
+        mem.push_back_ins(Instruction::new(LAX_ZP, &vec![0x10]).unwrap());
+        mem.write_byte(&(ZP_S + 0x10), &(0x80));
+        cpu.execute(&mut mem).unwrap();
+        assert_eq!(cpu.a().value, 0x80);
+        assert_eq!(cpu.x().value, 0x80);
+        assert_eq!(cpu.n_flag(), true);
+    }
+
+    #[test]
+    fn test_lax_iy_page_cross_cycles() {
+        let mut cpu: CPU = CPU::new(0, 0, 0x20, 0);
+        let mut mem: Memory = Memory::new();
+        mem.push_back_ins(Instruction::new(LAX_IY, &vec![0x10]).unwrap());
+        mem.write_byte(&(ZP_S + 0x10), &(0xF0));
+        mem.write_byte(&(ZP_S + 0x11), &(0x10));
+        mem.write_byte(&(0x1110), &(0x42));
+        cpu.execute(&mut mem).unwrap();
+        assert_eq!(cpu.a().value, 0x42);
+        assert_eq!(cpu.x().value, 0x42);
+        assert_eq!(cpu.clock_cycles_elapsed(), 6);
+    }
+
+    #[test]
+    fn test_anc_i() {
+        let mut cpu: CPU = CPU::new(0x80, 0, 0, 0);
+        let mut mem: Memory = Memory::new();
+        mem.push_back_ins(Instruction::new(ANC_I, &vec![0xFF]).unwrap());
+        cpu.execute(&mut mem).unwrap();
+        assert_eq!(cpu.a().value, 0x80);
+        assert_eq!(cpu.n_flag(), true);
+        assert_eq!(cpu.c_flag(), true);
+    }
+
+    #[test]
+    fn test_alr_i() {
+        let mut cpu: CPU = CPU::new(0x03, 0, 0, 0);
+        let mut mem: Memory = Memory::new();
+        mem.push_back_ins(Instruction::new(ALR_I, &vec![0x03]).unwrap());
+        cpu.execute(&mut mem).unwrap();
+        assert_eq!(cpu.a().value, 0x01);
+        assert_eq!(cpu.c_flag(), true);
+    }
+
+    #[test]
+    fn test_arr_i() {
+        let mut cpu: CPU = CPU::new(0xFF, 0, 0, 0);
+        let mut mem: Memory = Memory::new();
+        mem.push_back_ins(Instruction::new(SEC, &vec![]).unwrap());
+        mem.push_back_ins(Instruction::new(ARR_I, &vec![0xC0]).unwrap());
+        cpu.execute(&mut mem).unwrap();
+        cpu.execute(&mut mem).unwrap();
+        assert_eq!(cpu.a().value, 0xE0);
+        assert_eq!(cpu.n_flag(), true);
+        assert_eq!(cpu.c_flag(), true);
+        assert_eq!(cpu.v_flag(), false);
+    }
+
+    #[test]
+    fn test_arr_i_decimal() {
+        let mut cpu: CPU = CPU::new(0xFF, 0, 0, 0);
+        let mut mem: Memory = Memory::new();
+        mem.push_back_ins(Instruction::new(SED, &vec![]).unwrap());
+        mem.push_back_ins(Instruction::new(ARR_I, &vec![0x99]).unwrap());
+        cpu.execute(&mut mem).unwrap();
+        cpu.execute(&mut mem).unwrap();
+        assert_eq!(cpu.a().value, 0xA2);
+        assert_eq!(cpu.n_flag(), false);
+        assert_eq!(cpu.c_flag(), true);
+        assert_eq!(cpu.v_flag(), true);
+    }
+
+    #[test]
+    fn test_sbx_i() {
+        let mut cpu: CPU = CPU::new(0xF0, 0x3C, 0, 0);
+        let mut mem: Memory = Memory::new();
+        mem.push_back_ins(Instruction::new(SBX_I, &vec![0x10]).unwrap());
+        cpu.execute(&mut mem).unwrap();
+        assert_eq!(cpu.x().value, 0x20);
+        assert_eq!(cpu.a().value, 0xF0);
+        assert_eq!(cpu.c_flag(), true);
+    }
+
+    #[test]
+    fn test_sbc_i_eb() {
+        let mut cpu: CPU = CPU::new(0x10, 0, 0, 0);
+        let mut mem: Memory = Memory::new();
+        mem.push_back_ins(Instruction::new(SEC, &vec![]).unwrap());
+        mem.push_back_ins(Instruction::new(SBC_I_EB, &vec![0x01]).unwrap());
+        cpu.execute(&mut mem).unwrap();
+        cpu.execute(&mut mem).unwrap();
+        assert_eq!(cpu.a().value, 0x0F);
+        assert_eq!(cpu.c_flag(), true);
+    }
+
+    #[test]
+    fn test_las_ay() {
+        let mut cpu: CPU = CPU::new(0, 0, 0x01, 0);
+        let mut mem: Memory = Memory::new();
+        mem.push_back_ins(Instruction::new(LAS_AY, &vec![0x33, 0x12]).unwrap());
+        mem.write_byte(&(0x1234), &(0x5A));
+        cpu.execute(&mut mem).unwrap();
+        assert_eq!(cpu.a().value, 0x5A);
+        assert_eq!(cpu.x().value, 0x5A);
+        assert_eq!(cpu.s().value, 0x5A);
+    }
+
+    #[test]
+    fn test_ane_i() {
+        let mut cpu: CPU = CPU::new(0x00, 0xFF, 0, 0);
+        let mut mem: Memory = Memory::new();
+        mem.push_back_ins(Instruction::new(ANE_I, &vec![0x0F]).unwrap());
+        cpu.execute(&mut mem).unwrap();
+        assert_eq!(cpu.a().value, 0x0E);
+    }
+
+    #[test]
+    fn test_lxa_i() {
+        let mut cpu: CPU = CPU::new(0x01, 0, 0, 0);
+        let mut mem: Memory = Memory::new();
+        mem.push_back_ins(Instruction::new(LXA_I, &vec![0xFF]).unwrap());
+        cpu.execute(&mut mem).unwrap();
+        assert_eq!(cpu.a().value, 0xEF);
+        assert_eq!(cpu.x().value, 0xEF);
+    }
+
+    #[test]
+    fn test_sha_ay() {
+        let mut cpu: CPU = CPU::new(0xFF, 0xF0, 0, 0);
+        let mut mem: Memory = Memory::new();
+        mem.push_back_ins(Instruction::new(SHA_AY, &vec![0x00, 0x12]).unwrap());
+        cpu.execute(&mut mem).unwrap();
+        assert_eq!(*mem.read_byte(&(0x1200)), 0x10);
+    }
+
+    #[test]
+    fn test_shx_ay() {
+        let mut cpu: CPU = CPU::new(0, 0xFF, 0x01, 0);
+        let mut mem: Memory = Memory::new();
+        mem.push_back_ins(Instruction::new(SHX_AY, &vec![0x00, 0x12]).unwrap());
+        cpu.execute(&mut mem).unwrap();
+        assert_eq!(*mem.read_byte(&(0x1201)), 0x13);
+    }
+
+    #[test]
+    fn test_shy_ax_page_cross() {
+        let mut cpu: CPU = CPU::new(0, 0x20, 0x0F, 0);
+        let mut mem: Memory = Memory::new();
+        mem.push_back_ins(Instruction::new(SHY_AX, &vec![0xF0, 0x12]).unwrap());
+        cpu.execute(&mut mem).unwrap();
+        /* the stored value ends up as the high byte of the address */
+        assert_eq!(*mem.read_byte(&(0x0310)), 0x03);
+        assert_eq!(*mem.read_byte(&(0x1310)), 0x00);
+        assert_eq!(cpu.clock_cycles_elapsed(), 5);
+    }
+
+    #[test]
+    fn test_tas_ay() {
+        let mut cpu: CPU = CPU::new(0xF3, 0x3F, 0, 0);
+        let mut mem: Memory = Memory::new();
+        mem.push_back_ins(Instruction::new(TAS_AY, &vec![0x00, 0x12]).unwrap());
+        cpu.execute(&mut mem).unwrap();
+        assert_eq!(cpu.s().value, 0x33);
+        assert_eq!(*mem.read_byte(&(0x1200)), 0x13);
+    }
+
+    #[test]
+    fn test_nop_undocumented() {
+        let mut cpu: CPU = CPU::new(0x01, 0x20, 0x03, 0);
+        let mut mem: Memory = Memory::new();
+        mem.push_back_ins(Instruction::new(NOP_1A, &vec![]).unwrap());
+        mem.push_back_ins(Instruction::new(NOP_I_80, &vec![0x12]).unwrap());
+        mem.push_back_ins(Instruction::new(NOP_ZPX_14, &vec![0x10]).unwrap());
+        mem.push_back_ins(Instruction::new(NOP_AX_1C, &vec![0xF0, 0x10]).unwrap());
+        for _ in 0..4 {
+            cpu.execute(&mut mem).unwrap();
+        }
+        assert_eq!(cpu.program_counter().value, 0x8008);
+        assert_eq!(cpu.a().value, 0x01);
+        assert_eq!(cpu.x().value, 0x20);
+        assert_eq!(cpu.y().value, 0x03);
+        /* the absolute,X NOP takes the page cross penalty */
+        assert_eq!(cpu.clock_cycles_elapsed(), 13);
+    }
+
+    #[test]
+    fn test_jam() {
+        let mut cpu: CPU = CPU::new(0, 0, 0, 0);
+        let mut mem: Memory = Memory::new();
+        mem.push_back_ins(Instruction::new(JAM_02, &vec![]).unwrap());
+        mem.push_back_ins(Instruction::new(INX, &vec![]).unwrap());
+        mem.write_byte(&(0xFFFC), &(0x01));
+        mem.write_byte(&(0xFFFD), &(0x80));
+        let jammed = CpuError::Jammed {
+            opcode: JAM_02.byte(),
+            addr: 0x8000,
+        };
+        assert_eq!(cpu.execute(&mut mem), Err(jammed));
+        assert_eq!(cpu.execute(&mut mem), Err(jammed));
+        assert_eq!(cpu.halted(), Some(jammed));
+        assert_eq!(cpu.program_counter().value, 0x8000);
+        assert_eq!(cpu.x().value, 0x00);
+        /* only a reset gets the cpu running again */
+        cpu.reset(&mut mem);
+        cpu.execute(&mut mem).unwrap();
+        assert_eq!(cpu.halted(), None);
+        assert_eq!(cpu.x().value, 0x01);
+    }
+
+    #[test]
+    fn test_trap_undocumented() {
+        let mut cpu: CPU = CPU::new(0, 0, 0, 0);
+        let mut mem: Memory = Memory::new();
+        cpu.set_trap_undocumented(true);
+        mem.push_back_ins(Instruction::new(LAX_ZP, &vec![0x10]).unwrap());
+        mem.write_byte(&(ZP_S + 0x10), &(0x42));
+        let illegal = CpuError::IllegalOpcode {
+            opcode: LAX_ZP.byte(),
+            addr: 0x8000,
+        };
+        assert_eq!(cpu.execute(&mut mem), Err(illegal));
+        assert_eq!(cpu.halted(), Some(illegal));
+        assert_eq!(cpu.a().value, 0x00);
+        assert_eq!(cpu.program_counter().value, 0x8000);
+        assert_eq!(cpu.clock_cycles_elapsed(), 0);
+    }
+
+    #[test]
+    fn test_all_opcodes_decode() {
+        let mut undocumented = 0;
+        for byte in 0..=u8::MAX {
+            let ins = Instruction::try_from(byte).unwrap();
+            assert_eq!(ins.opc().byte(), byte);
+            if ins.opc().undocumented() {
+                undocumented += 1;
+            }
+        }
+        assert_eq!(undocumented, 105);
+        assert!(!OPCODE::NOP.undocumented());
+    }
+
+    #[test]
+    fn test_cmos_reserved_opcodes_are_nops() {
+        let mut cpu: CPU = CPU::new(0, 0, 0, 0);
+        let mut mem: Memory = Memory::new();
+        cpu.set_variant(Variant::CMOS65C02);
+        mem.push_back_ins(Instruction::new(LAX_ZP, &vec![0x10]).unwrap());
+        mem.write_byte(&(ZP_S + 0x10), &(0x42));
+        cpu.execute(&mut mem).unwrap();
+        assert_eq!(cpu.a().value, 0x00);
+        assert_eq!(cpu.program_counter().value, 0x8001);
+        assert_eq!(cpu.clock_cycles_elapsed(), 1);
+    }
+
+    #[test]
+    fn test_cmos_decode() {
+        for byte in 0..=u8::MAX {
+            for variant in [
+                Variant::CMOS65C02,
+                Variant::ROCKWELL65C02,
+                Variant::WDC65C02,
+            ] {
+                let ins = Instruction::decode(byte, variant);
+                assert_eq!(ins.opc().byte(), byte);
+                /* the undocumented NMOS instructions are gone */
+                if ins.opc().undocumented() {
+                    assert!(format!("{:?}", ins.opc()).starts_with("NOP"));
+                }
+            }
+        }
+        let rmb = Instruction::decode(RMB3.byte(), Variant::CMOS65C02);
+        assert_eq!(*rmb.opc(), OPCODE::NOP_C_37);
+        let wai = Instruction::decode(WAI.byte(), Variant::ROCKWELL65C02);
+        assert_eq!(*wai.opc(), OPCODE::NOP_C_CB);
+    }
+
+    #[test]
+    fn test_lda_izp() {
+        let mut cpu: CPU = CPU::new(0, 0, 0x10, 0);
+        let mut mem: Memory = Memory::new();
+        cpu.set_variant(Variant::CMOS65C02);
+        mem.push_back_ins(Instruction::new(LDA_IZP, &vec![0xFF]).unwrap());
+        /* the pointer wraps around in page zero and is not indexed */
+        mem.write_byte(&(0x00FF), &(0x34));
+        mem.write_byte(&(0x0000), &(0x12));
+        mem.write_byte(&(0x1234), &(0x80));
+        cpu.execute(&mut mem).unwrap();
+        assert_eq!(cpu.a().value, 0x80);
+        assert_eq!(cpu.n_flag(), true);
+        assert_eq!(cpu.clock_cycles_elapsed(), 5);
+    }
+
+    #[test]
+    fn test_sta_izp() {
+        let mut cpu: CPU = CPU::new(0x42, 0, 0, 0);
+        let mut mem: Memory = Memory::new();
+        cpu.set_variant(Variant::CMOS65C02);
+        mem.push_back_ins(Instruction::new(STA_IZP, &vec![0x10]).unwrap());
+        mem.write_byte(&(ZP_S + 0x10), &(0x34));
+        mem.write_byte(&(ZP_S + 0x11), &(0x12));
+        cpu.execute(&mut mem).unwrap();
+        assert_eq!(*mem.read_byte(&(0x1234)), 0x42);
+    }
+
+    #[test]
+    fn test_bit_i() {
+        let mut cpu: CPU = CPU::new(0x0F, 0, 0, 0);
+        let mut mem: Memory = Memory::new();
+        cpu.set_variant(Variant::CMOS65C02);
+        mem.push_back_ins(Instruction::new(BIT_I, &vec![0xF0]).unwrap());
+        cpu.execute(&mut mem).unwrap();
+        assert_eq!(cpu.z_flag(), true);
+        assert_eq!(cpu.n_flag(), false);
+        assert_eq!(cpu.v_flag(), false);
+    }
+
+    #[test]
+    fn test_inc_dec_acc() {
+        let mut cpu: CPU = CPU::new(0xFF, 0, 0, 0);
+        let mut mem: Memory = Memory::new();
+        cpu.set_variant(Variant::CMOS65C02);
+        mem.push_back_ins(Instruction::new(INC_ACC, &vec![]).unwrap());
+        mem.push_back_ins(Instruction::new(DEC_ACC, &vec![]).unwrap());
+        cpu.execute(&mut mem).unwrap();
+        assert_eq!(cpu.a().value, 0x00);
+        assert_eq!(cpu.z_flag(), true);
+        cpu.execute(&mut mem).unwrap();
+        assert_eq!(cpu.a().value, 0xFF);
+        assert_eq!(cpu.n_flag(), true);
+    }
+
+    #[test]
+    fn test_jmp_iax() {
+        let mut cpu: CPU = CPU::new(0, 0x04, 0, 0);
+        let mut mem: Memory = Memory::new();
+        cpu.set_variant(Variant::CMOS65C02);
+        mem.push_back_ins(Instruction::new(JMP_IAX, &vec![0x00, 0x10]).unwrap());
+        mem.write_byte(&(0x1004), &(0x34));
+        mem.write_byte(&(0x1005), &(0x12));
+        cpu.execute(&mut mem).unwrap();
+        assert_eq!(cpu.program_counter().value, 0x1234);
+        assert_eq!(cpu.clock_cycles_elapsed(), 6);
+    }
+
+    #[test]
+    fn test_bra() {
+        let mut cpu: CPU = CPU::new(0, 0, 0, 0);
+        let mut mem: Memory = Memory::new();
+        cpu.set_variant(Variant::CMOS65C02);
+        mem.push_back_ins(Instruction::new(BRA, &vec![0x10]).unwrap());
+        cpu.execute(&mut mem).unwrap();
+        assert_eq!(cpu.program_counter().value, 0x8012);
+        assert_eq!(cpu.clock_cycles_elapsed(), 3);
+    }
+
+    #[test]
+    fn test_phx_plx_phy_ply() {
+        let mut cpu: CPU = CPU::new(0, 0x80, 0x42, 0);
+        let mut mem: Memory = Memory::new();
+        cpu.set_variant(Variant::CMOS65C02);
+        mem.push_back_ins(Instruction::new(PHX, &vec![]).unwrap());
+        mem.push_back_ins(Instruction::new(PHY, &vec![]).unwrap());
+        mem.push_back_ins(Instruction::new(PLX, &vec![]).unwrap());
+        mem.push_back_ins(Instruction::new(PLY, &vec![]).unwrap());
+        cpu.execute(&mut mem).unwrap();
+        cpu.execute(&mut mem).unwrap();
+        assert_eq!(*mem.read_byte(&(0x01FF)), 0x80);
+        assert_eq!(*mem.read_byte(&(0x01FE)), 0x42);
+        cpu.execute(&mut mem).unwrap();
+        cpu.execute(&mut mem).unwrap();
+        assert_eq!(cpu.x().value, 0x42);
+        assert_eq!(cpu.y().value, 0x80);
+        assert_eq!(cpu.n_flag(), true);
+        assert_eq!(cpu.s().value, 0xFF);
+    }
+
+    #[test]
+    fn test_stz_zp() {
+        let mut cpu: CPU = CPU::new(0x42, 0, 0, 0);
+        let mut mem: Memory = Memory::new();
+        cpu.set_variant(Variant::CMOS65C02);
+        mem.push_back_ins(Instruction::new(STZ_ZP, &vec![0x10]).unwrap());
+        mem.write_byte(&(ZP_S + 0x10), &(0xFF));
+        cpu.execute(&mut mem).unwrap();
+        assert_eq!(*mem.read_byte(&(ZP_S + 0x10)), 0x00);
+    }
+
+    #[test]
+    fn test_trb_zp() {
+        let mut cpu: CPU = CPU::new(0x0F, 0, 0, 0);
+        let mut mem: Memory = Memory::new();
+        cpu.set_variant(Variant::CMOS65C02);
+        mem.push_back_ins(Instruction::new(TRB_ZP, &vec![0x10]).unwrap());
+        mem.write_byte(&(ZP_S + 0x10), &(0x3C));
+        cpu.execute(&mut mem).unwrap();
+        assert_eq!(*mem.read_byte(&(ZP_S + 0x10)), 0x30);
+        assert_eq!(cpu.z_flag(), false);
+    }
+
+    #[test]
+    fn test_tsb_a() {
+        let mut cpu: CPU = CPU::new(0x0F, 0, 0, 0);
+        let mut mem: Memory = Memory::new();
+        cpu.set_variant(Variant::CMOS65C02);
+        mem.push_back_ins(Instruction::new(TSB_A, &vec![0x34, 0x12]).unwrap());
+        mem.write_byte(&(0x1234), &(0xF0));
+        cpu.execute(&mut mem).unwrap();
+        assert_eq!(*mem.read_byte(&(0x1234)), 0xFF);
+        assert_eq!(cpu.z_flag(), true);
+    }
+
+    #[test]
+    fn test_rmb_smb() {
+        let mut cpu: CPU = CPU::new(0, 0, 0, 0);
+        let mut mem: Memory = Memory::new();
+        cpu.set_variant(Variant::ROCKWELL65C02);
+        mem.push_back_ins(Instruction::new(RMB3, &vec![0x10]).unwrap());
+        mem.push_back_ins(Instruction::new(SMB3, &vec![0x11]).unwrap());
+        mem.write_byte(&(ZP_S + 0x10), &(0xFF));
+        cpu.execute(&mut mem).unwrap();
+        cpu.execute(&mut mem).unwrap();
+        assert_eq!(*mem.read_byte(&(ZP_S + 0x10)), 0xF7);
+        assert_eq!(*mem.read_byte(&(ZP_S + 0x11)), 0x08);
+    }
+
+    #[test]
+    fn test_bbr_bbs() {
+        let mut cpu: CPU = CPU::new(0, 0, 0, 0);
+        let mut mem: Memory = Memory::new();
+        cpu.set_variant(Variant::ROCKWELL65C02);
+        mem.push_back_ins(Instruction::new(BBS3, &vec![0x10, 0x10]).unwrap());
+        mem.push_back_ins(Instruction::new(BBR3, &vec![0x10, 0x10]).unwrap());
+        mem.write_byte(&(ZP_S + 0x10), &(0xF7));
+        cpu.execute(&mut mem).unwrap();
+        assert_eq!(cpu.program_counter().value, 0x8003);
+        assert_eq!(cpu.clock_cycles_elapsed(), 5);
+        cpu.execute(&mut mem).unwrap();
+        assert_eq!(cpu.program_counter().value, 0x8016);
+        assert_eq!(cpu.clock_cycles_elapsed(), 11);
+    }
+
+    #[test]
+    fn test_wai() {
+        let mut cpu: CPU = CPU::new(0, 0, 0, 0);
+        let mut mem: Memory = Memory::new();
+        cpu.set_variant(Variant::WDC65C02);
+        mem.push_back_ins(Instruction::new(SEI, &vec![]).unwrap());
+        mem.push_back_ins(Instruction::new(WAI, &vec![]).unwrap());
+        mem.push_back_ins(Instruction::new(INX, &vec![]).unwrap());
+        cpu.execute(&mut mem).unwrap();
+        cpu.execute(&mut mem).unwrap();
+        cpu.execute(&mut mem).unwrap();
+        assert_eq!(cpu.x().value, 0x00);
+        /* a masked IRQ wakes the cpu up without being serviced */
+        cpu.assert_irq();
+        cpu.execute(&mut mem).unwrap();
+        assert_eq!(cpu.x().value, 0x01);
+        assert_eq!(cpu.program_counter().value, 0x8003);
+    }
+
+    #[test]
+    fn test_stp() {
+        let mut cpu: CPU = CPU::new(0, 0, 0, 0);
+        let mut mem: Memory = Memory::new();
+        cpu.set_variant(Variant::WDC65C02);
+        mem.push_back_ins(Instruction::new(STP, &vec![]).unwrap());
+        mem.push_back_ins(Instruction::new(INX, &vec![]).unwrap());
+        cpu.execute(&mut mem).unwrap();
+        cpu.assert_irq();
+        assert_eq!(cpu.execute(&mut mem), Ok(StepOutcome::Stopped));
+        assert_eq!(cpu.halted(), None);
+        assert_eq!(cpu.x().value, 0x00);
+    }
+
+    #[test]
+    fn test_cmos_brk_clears_d() {
+        let mut cpu: CPU = CPU::new(0, 0, 0, 0);
+        let mut mem: Memory = Memory::new();
+        cpu.set_variant(Variant::CMOS65C02);
+        mem.push_back_ins(Instruction::new(SED, &vec![]).unwrap());
+        mem.push_back_ins(Instruction::new(BRK, &vec![]).unwrap());
+        cpu.execute(&mut mem).unwrap();
+        cpu.execute(&mut mem).unwrap();
+        assert_eq!(cpu.d_flag(), false);
+        /* the pushed status still has D set */
+        assert_eq!(*mem.read_byte(&(0x01FD)) & 0x08, 0x08);
+    }
+
+    #[test]
+    fn test_cmos_cycles() {
+        let mut cpu: CPU = CPU::new(0, 0x01, 0, 0);
+        let mut mem: Memory = Memory::new();
+        cpu.set_variant(Variant::CMOS65C02);
+        mem.push_back_ins(Instruction::new(ASL_AX, &vec![0x00, 0x10]).unwrap());
+        mem.push_back_ins(Instruction::new(ASL_AX, &vec![0xFF, 0x10]).unwrap());
+        mem.push_back_ins(Instruction::new(SED, &vec![]).unwrap());
+        mem.push_back_ins(Instruction::new(ADC_I, &vec![0x01]).unwrap());
+        cpu.execute(&mut mem).unwrap();
+        assert_eq!(cpu.clock_cycles_elapsed(), 6);
+        cpu.execute(&mut mem).unwrap();
+        assert_eq!(cpu.clock_cycles_elapsed(), 13);
+        cpu.execute(&mut mem).unwrap();
+        cpu.execute(&mut mem).unwrap();
+        /* the decimal adjust takes an extra cycle */
+        assert_eq!(cpu.clock_cycles_elapsed(), 18);
+    }
+
+    #[test]
+    fn test_dispatch_table() {
+        for variant in [
+            Variant::NMOS6502,
+            Variant::CMOS65C02,
+            Variant::ROCKWELL65C02,
+            Variant::WDC65C02,
+        ] {
+            for (code, operation) in dispatch::table(variant).iter().enumerate() {
+                assert_eq!(operation.opc.byte() as usize, code);
+            }
+        }
+        let lda = &dispatch::table(Variant::NMOS6502)[LDA_IY.byte() as usize];
+        assert_eq!(lda.mnemonic, "LDA");
+        assert_eq!(lda.mode, AddressingMode::INDIRECTY);
+        assert_eq!(lda.cycles, 5);
+        assert!(lda.page_cross_penalty);
+        let stz = &dispatch::table(Variant::CMOS65C02)[STZ_ZP.byte() as usize];
+        assert_eq!(stz.mnemonic, "STZ");
+        assert_eq!(stz.mode, AddressingMode::ZEROPAGE);
+    }
+
+    #[test]
+    fn test_step_outcome() {
+        let mut cpu: CPU = CPU::new(0, 0, 0, 0);
+        let mut mem: Memory = Memory::new();
+        mem.push_back_ins(Instruction::new(INX, &vec![]).unwrap());
+        mem.write_byte(&(0xFFFE), &(0x00));
+        mem.write_byte(&(0xFFFF), &(0x90));
+        assert_eq!(
+            cpu.execute(&mut mem),
+            Ok(StepOutcome::Executed { opcode: INX.byte() })
+        );
+        cpu.assert_irq();
+        assert_eq!(cpu.execute(&mut mem), Ok(StepOutcome::Interrupt));
+    }
+
+    #[test]
+    fn test_instruction_new_param_count() {
+        assert_eq!(
+            Instruction::new(LDA_A, &vec![0x12]).unwrap_err(),
+            InstructionError {
+                opcode: LDA_A.byte(),
+                expected: 2,
+                got: 1
+            }
+        );
+        assert!(Instruction::new(INX, &vec![0x12]).is_err());
+    }
+
+    #[test]
+    fn test_push_back_ins_wraps() {
+        let mut mem: Memory = Memory::new();
+        /* 0x2AAA three byte instructions fill $8000-$FFFD */
+        for _ in 0..0x2AAA {
+            mem.push_back_ins(Instruction::new(LDA_A, &vec![0x34, 0x12]).unwrap());
+        }
+        mem.push_back_ins(Instruction::new(LDA_A, &vec![0x34, 0x12]).unwrap());
+        assert_eq!(*mem.read_byte(&(0xFFFE)), LDA_A.byte());
+        assert_eq!(*mem.read_byte(&(0xFFFF)), 0x34);
+        assert_eq!(*mem.read_byte(&(0x0000)), 0x12);
+    }
+
+    /* RAM with a counter at $4000 that counts the reads the cpu did on it */
+    struct CountingBus {
+        ram: Memory,
+        reads: u8,
+    }
+
+    impl Bus for CountingBus {
+        fn read(&mut self, addr: u16) -> u8 {
+            if addr == 0x4000 {
+                self.reads += 1;
+                return self.reads;
+            }
+            self.ram.read(addr)
+        }
+
+        fn write(&mut self, addr: u16, value: u8) {
+            if addr == 0x4000 {
+                self.reads = value;
+                return;
+            }
+            self.ram.write(addr, value)
+        }
+
+        fn peek(&self, addr: u16) -> u8 {
+            if addr == 0x4000 {
+                return self.reads;
+            }
+            self.ram.peek(addr)
+        }
+    }
+
+    #[test]
+    fn test_custom_bus() {
+        let mut cpu: CPU = CPU::new(0, 0, 0, 0);
+        let mut bus = CountingBus {
+            ram: Memory::new(),
+            reads: 0,
+        };
+        bus.ram
+            .push_back_ins(Instruction::new(LDA_A, &vec![0x00, 0x40]).unwrap());
+        bus.ram
+            .push_back_ins(Instruction::new(LDX_A, &vec![0x00, 0x40]).unwrap());
+        bus.ram
+            .push_back_ins(Instruction::new(STA_A, &vec![0x00, 0x40]).unwrap());
+        cpu.execute(&mut bus).unwrap();
+        assert_eq!(cpu.a().value, 0x01);
+        assert_eq!(bus.peek(0x4000), 0x01);
+        assert_eq!(bus.peek(0x4000), 0x01);
+        cpu.execute(&mut bus).unwrap();
+        assert_eq!(cpu.x().value, 0x02);
+        cpu.execute(&mut bus).unwrap();
+        assert_eq!(bus.peek(0x4000), 0x01);
+        assert_eq!(bus.peek(0x8000), LDA_A.byte());
+    }
+
+    #[test]
+    fn test_rom_ignores_writes() {
+        let mut cpu: CPU = CPU::new(0x12, 0, 0, 0);
+        let mut mem: Memory = Memory::with_standard_map();
+        mem.push_back_ins(Instruction::new(STA_A, &vec![0x00, 0x90]).unwrap());
+        mem.push_back_ins(Instruction::new(STA_A, &vec![0x00, 0x30]).unwrap());
+        cpu.execute(&mut mem).unwrap();
+        assert_eq!(*mem.read_byte(&(0x9000)), 0x00);
+        cpu.execute(&mut mem).unwrap();
+        assert_eq!(*mem.read_byte(&(0x3000)), 0x12);
+    }
+
+    #[test]
+    fn test_rom_reports_writes() {
+        let mut cpu: CPU = CPU::new(0x12, 0, 0, 0);
+        let mut mem: Memory = Memory::with_standard_map();
+        mem.set_rom_writes(RomWrites::REPORT);
+        mem.push_back_ins(Instruction::new(STA_A, &vec![0x00, 0x90]).unwrap());
+        let fault = CpuError::BusFault { addr: 0x9000 };
+        assert_eq!(cpu.execute(&mut mem), Err(fault));
+        assert_eq!(cpu.halted(), Some(fault));
+        assert_eq!(cpu.program_counter().value, 0x8000);
+        assert_eq!(*mem.read_byte(&(0x9000)), 0x00);
+    }
+
+    /* a register that counts the reads done on it, writes set the count */
+    struct Counter {
+        count: u8,
+    }
+
+    impl Device for Counter {
+        fn read(&mut self, _offset: u16) -> u8 {
+            self.count += 1;
+            self.count
+        }
+
+        fn write(&mut self, _offset: u16, value: u8) {
+            self.count = value;
+        }
+
+        fn peek(&self, _offset: u16) -> u8 {
+            self.count
+        }
+
+        fn snapshot(&self) -> Vec<u8> {
+            vec![self.count]
+        }
+
+        fn check_snapshot(&self, snapshot: &[u8]) -> Result<(), SnapshotError> {
+            if snapshot.len() != 1 {
+                return Err(SnapshotError {
+                    expected: 1,
+                    got: snapshot.len(),
+                });
+            }
+            Ok(())
+        }
+
+        fn restore(&mut self, snapshot: &[u8]) -> Result<(), SnapshotError> {
+            self.check_snapshot(snapshot)?;
+            self.count = snapshot[0];
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_io_device() {
+        let mut cpu: CPU = CPU::new(0x10, 0, 0, 0);
+        let mut mem: Memory = Memory::with_standard_map();
+        mem.attach(0x4000, 0x4000, Box::new(Counter { count: 0 }));
+        mem.push_back_ins(Instruction::new(STA_A, &vec![0x00, 0x40]).unwrap());
+        mem.push_back_ins(Instruction::new(LDX_A, &vec![0x00, 0x40]).unwrap());
+        cpu.execute(&mut mem).unwrap();
+        assert_eq!(mem.peek(0x4000), 0x10);
+        assert_eq!(*mem.read_byte(&(0x4000)), 0x00);
+        cpu.execute(&mut mem).unwrap();
+        assert_eq!(cpu.x().value, 0x11);
+        assert_eq!(mem.peek(0x4000), 0x11);
+    }
+
+    /* serves a program and counts the reads done on each of its bytes */
+    struct ReadTally {
+        data: Vec<u8>,
+        reads: Rc<RefCell<Vec<u32>>>,
+    }
+
+    impl Device for ReadTally {
+        fn read(&mut self, offset: u16) -> u8 {
+            self.reads.borrow_mut()[offset as usize] += 1;
+            self.data[offset as usize]
+        }
+
+        fn write(&mut self, _offset: u16, _value: u8) {}
+
+        fn peek(&self, offset: u16) -> u8 {
+            self.data[offset as usize]
+        }
+    }
+
+    #[test]
+    fn test_operand_read_once() {
+        let mut cpu: CPU = CPU::new(0, 0, 0, 0);
+        let mut mem: Memory = Memory::new();
+        let mut data = vec![0; 0x20];
+        /* LDA $8010, NOP */
+        data[..4].copy_from_slice(&[LDA_A.byte(), 0x10, 0x80, OPCODE::NOP.byte()]);
+        data[0x10] = 0x42;
+        let reads = Rc::new(RefCell::new(vec![0; data.len()]));
+        mem.attach(
+            0x8000,
+            0x801F,
+            Box::new(ReadTally {
+                data,
+                reads: Rc::clone(&reads),
+            }),
+        );
+        cpu.execute(&mut mem).unwrap();
+        cpu.execute(&mut mem).unwrap();
+        assert_eq!(cpu.a().value, 0x42);
+        let mut expected = vec![0; 0x20];
+        for offset in [0x00, 0x01, 0x02, 0x03, 0x10] {
+            expected[offset] = 1;
+        }
+        /* the NOP does not touch the byte behind it */
+        assert_eq!(*reads.borrow(), expected);
+    }
+
+    #[test]
+    fn test_open_bus() {
+        let mut cpu: CPU = CPU::new(0, 0, 0, 0);
+        let mut mem: Memory = Memory::with_standard_map();
+        mem.map(0x3000, 0x30FF, Region::UNMAPPED);
+        mem.write_byte(&(0x3010), &(0xFF));
+        mem.push_back_ins(Instruction::new(LDA_A, &vec![0x00, 0x50]).unwrap());
+        mem.push_back_ins(Instruction::new(LDX_A, &vec![0x10, 0x30]).unwrap());
+        /* nothing is attached in the I/O window, the high byte of the address is still on the bus */
+        cpu.execute(&mut mem).unwrap();
+        assert_eq!(cpu.a().value, 0x50);
+        cpu.execute(&mut mem).unwrap();
+        assert_eq!(cpu.x().value, 0x30);
+        assert_eq!(mem.region(0x3010), Region::UNMAPPED);
+        assert_eq!(mem.region(0x8000), Region::ROM);
+    }
+
+    /* an image whose banks are filled with their bank number */
+    fn banked_image(banks: usize, size: BankSize) -> Vec<u8> {
+        (0..banks)
+            .flat_map(|bank| vec![bank as u8; size.bytes()])
+            .collect()
+    }
+
+    #[test]
+    fn test_latch_mapper() {
+        let mut cpu: CPU = CPU::new(0x02, 0, 0, 0);
+        let mut mem: Memory = Memory::new();
+        mem.set_mapper(Box::new(Latch::new(
+            banked_image(4, BankSize::KB8),
+            0x4000,
+            BankSize::KB8,
+            0x3FFF,
+        )));
+        mem.push_back_ins(Instruction::new(LDX_A, &vec![0x00, 0x50]).unwrap());
+        mem.push_back_ins(Instruction::new(STA_A, &vec![0xFF, 0x3F]).unwrap());
+        mem.push_back_ins(Instruction::new(LDY_A, &vec![0xFF, 0x5F]).unwrap());
+        cpu.execute(&mut mem).unwrap();
+        assert_eq!(cpu.x().value, 0x00);
+        cpu.execute(&mut mem).unwrap();
+        assert_eq!(*mem.read_byte(&(0x3FFF)), 0x00);
+        cpu.execute(&mut mem).unwrap();
+        assert_eq!(cpu.y().value, 0x02);
+        /* outside of the window the memory answers */
+        assert_eq!(mem.peek(0x6000), 0x00);
+        assert_eq!(mem.peek(0x8000), LDX_A.byte());
+        /* the bank number wraps around the number of banks */
+        mem.write(0x3FFF, 0x05);
+        assert_eq!(mem.peek(0x4000), 0x01);
+        assert_eq!(mem.mapper().unwrap().snapshot(), vec![0x01]);
+    }
+
+    #[test]
+    fn test_uxrom_mapper() {
+        let mut mem: Memory = Memory::new();
+        mem.set_mapper(Box::new(UxRom::new(banked_image(8, BankSize::KB16))));
+        assert_eq!(mem.peek(0x8000), 0x00);
+        assert_eq!(mem.peek(0xC000), 0x07);
+        mem.write(0x8123, 0x03);
+        assert_eq!(mem.peek(0xBFFF), 0x03);
+        assert_eq!(mem.peek(0xFFFF), 0x07);
+        assert_eq!(*mem.read_byte(&(0x8123)), 0x00);
+        mem.write(0x1000, 0x42);
+        assert_eq!(mem.peek(0x1000), 0x42);
+    }
+
+    #[test]
+    fn test_mmu_table_mapper() {
+        let mut mem: Memory = Memory::new();
+        mem.set_mapper(Box::new(MmuTable::new(
+            banked_image(2, BankSize::KB8),
+            2,
+            0x0200,
+        )));
+        mem.write(0x2000, 0x11);
+        assert_eq!(mem.peek(0x2000), 0x11);
+        /* slot 1 shows the second ROM bank, slot 2 the first RAM bank */
+        mem.write(0x0201, 0x01);
+        mem.write(0x0202, 0x02);
+        assert_eq!(mem.peek(0x2000), 0x01);
+        mem.write(0x2000, 0x55);
+        assert_eq!(mem.peek(0x2000), 0x01);
+        mem.write(0x4000, 0x66);
+        assert_eq!(mem.peek(0x4000), 0x66);
+        assert_eq!(*mem.read_byte(&(0x4000)), 0x00);
+
+        let snapshot = mem.mapper().unwrap().snapshot();
+        mem.write(0x0202, 0x03);
+        assert_eq!(mem.peek(0x4000), 0x00);
+        mem.write(0x0201, UNBANKED);
+        assert_eq!(mem.peek(0x2000), 0x11);
+        mem.mapper_mut().unwrap().restore(&snapshot).unwrap();
+        assert_eq!(mem.peek(0x2000), 0x01);
+        assert_eq!(mem.peek(0x4000), 0x66);
+        assert_eq!(
+            mem.mapper_mut().unwrap().restore(&[0x00]),
+            Err(SnapshotError {
+                expected: 8 + 2 * BankSize::KB8.bytes(),
+                got: 1
+            })
+        );
+    }
+
+    #[test]
+    fn test_mmu_table_rom_writes() {
+        let mut cpu: CPU = CPU::new(0x77, 0, 0, 0);
+        let mut mem: Memory = Memory::new();
+        mem.set_mapper(Box::new(MmuTable::new(
+            banked_image(2, BankSize::KB8),
+            1,
+            0x0200,
+        )));
+        mem.set_rom_writes(RomWrites::REPORT);
+        /* slot 1 shows the second ROM bank, slot 2 the RAM bank */
+        mem.write(0x0201, 0x01);
+        mem.write(0x0202, 0x02);
+        mem.push_back_ins(Instruction::new(STA_A, &vec![0x00, 0x40]).unwrap());
+        mem.push_back_ins(Instruction::new(STA_A, &vec![0x00, 0x20]).unwrap());
+        cpu.execute(&mut mem).unwrap();
+        assert_eq!(mem.peek(0x4000), 0x77);
+        assert_eq!(
+            cpu.execute(&mut mem),
+            Err(CpuError::BusFault { addr: 0x2000 })
+        );
+        assert_eq!(mem.peek(0x2000), 0x01);
+        /* the write did not leak into the memory behind the slot either */
+        assert_eq!(*mem.read_byte(&(0x2000)), 0x00);
+    }
+
+    #[test]
+    fn test_latch_rom_writes() {
+        let mut cpu: CPU = CPU::new(0x77, 0, 0, 0);
+        let mut mem: Memory = Memory::new();
+        mem.set_mapper(Box::new(Latch::new(
+            banked_image(2, BankSize::KB8),
+            0x4000,
+            BankSize::KB8,
+            0x5000,
+        )));
+        mem.push_back_ins(Instruction::new(STA_A, &vec![0x10, 0x40]).unwrap());
+        mem.push_back_ins(Instruction::new(STA_A, &vec![0x00, 0x50]).unwrap());
+        mem.push_back_ins(Instruction::new(STA_A, &vec![0x10, 0x40]).unwrap());
+        /* ignored, the bank keeps its byte and the memory under the window stays untouched */
+        cpu.execute(&mut mem).unwrap();
+        assert_eq!(mem.peek(0x4010), 0x00);
+        assert_eq!(*mem.read_byte(&(0x4010)), 0x00);
+        /* the latch register inside the window still switches the bank */
+        cpu.execute(&mut mem).unwrap();
+        assert_eq!(mem.peek(0x4010), 0x01);
+        mem.set_rom_writes(RomWrites::REPORT);
+        assert_eq!(
+            cpu.execute(&mut mem),
+            Err(CpuError::BusFault { addr: 0x4010 })
+        );
+        assert_eq!(mem.peek(0x4010), 0x01);
+        assert_eq!(*mem.read_byte(&(0x4010)), 0x00);
+    }
+
+    #[test]
+    fn test_mapper_restore() {
+        let mut mapper = UxRom::new(banked_image(4, BankSize::KB16));
+        mapper.write(0x8000, 0x02);
+        let snapshot = mapper.snapshot();
+        mapper.write(0x8000, 0x01);
+        mapper.restore(&snapshot).unwrap();
+        assert_eq!(mapper.bank(), 0x02);
+        assert_eq!(mapper.read(0x8000), Some(0x02));
+        assert_eq!(mapper.read(0x7FFF), None);
+        assert!(mapper.restore(&[]).is_err());
+    }
+
+    #[test]
+    fn test_load_raw() {
+        let mut mem: Memory = Memory::with_standard_map();
+        loader::load(&mut mem, &Image::raw(&[0xA9, 0x42, 0xEA], 0xFFFD)).unwrap();
+        assert_eq!(*mem.read_byte(&(0xFFFD)), 0xA9);
+        assert_eq!(*mem.read_byte(&(0xFFFF)), 0xEA);
+        assert_eq!(
+            loader::load(&mut mem, &Image::raw(&[0x01, 0x02, 0x03, 0x04], 0xFFFD)),
+            Err(LoadError::OutOfRange {
+                addr: 0xFFFD,
+                len: 4
+            })
+        );
+        assert_eq!(*mem.read_byte(&(0xFFFD)), 0xA9);
+    }
+
+    #[test]
+    fn test_load_overlap() {
+        let mut mem: Memory = Memory::new();
+        let image = Image {
+            segments: vec![
+                Segment {
+                    addr: 0x1004,
+                    data: vec![0xFF; 4],
+                },
+                Segment {
+                    addr: 0x1000,
+                    data: vec![0x11; 5],
+                },
+            ],
+            start: None,
+        };
+        assert_eq!(
+            loader::load(&mut mem, &image),
+            Err(LoadError::Overlap { addr: 0x1004 })
+        );
+        assert_eq!(*mem.read_byte(&(0x1000)), 0x00);
+    }
+
+    #[test]
+    fn test_parse_intel_hex() {
+        let image = loader::parse_intel_hex(
+            ":02800000A94293\n:01800200EA93\n:040000050000800077\n:00000001FF\n",
+        )
+        .unwrap();
+        assert_eq!(
+            image.segments,
+            vec![Segment {
+                addr: 0x8000,
+                data: vec![0xA9, 0x42, 0xEA]
+            }]
+        );
+        assert_eq!(image.start, Some(0x8000));
+        assert_eq!(
+            loader::parse_intel_hex(":02800000A94293\n:01800200EA94\n"),
+            Err(LoadError::Checksum {
+                line: 2,
+                expected: 0x93,
+                got: 0x94
+            })
+        );
+        assert_eq!(
+            loader::parse_intel_hex("\n02800000A94293\n"),
+            Err(LoadError::Malformed { line: 2 })
+        );
+        /* an extended linear address moves the data above 64K */
+        let image = loader::parse_intel_hex(":020000040001F9\n:02800000A94293\n").unwrap();
+        let mut mem: Memory = Memory::new();
+        assert_eq!(
+            loader::load(&mut mem, &image),
+            Err(LoadError::OutOfRange {
+                addr: 0x18000,
+                len: 2
+            })
+        );
+    }
+
+    #[test]
+    fn test_parse_srec() {
+        let image = loader::parse_srec(
+            "S00700007465737438\nS1068000A942EAA4\nS2050080030077\nS90380007C\n",
+        )
+        .unwrap();
+        assert_eq!(
+            image.segments,
+            vec![Segment {
+                addr: 0x8000,
+                data: vec![0xA9, 0x42, 0xEA, 0x00]
+            }]
+        );
+        assert_eq!(image.start, Some(0x8000));
+        assert_eq!(
+            loader::parse_srec("S1068000A942EAA5"),
+            Err(LoadError::Checksum {
+                line: 1,
+                expected: 0xA4,
+                got: 0xA5
+            })
+        );
+        assert_eq!(
+            loader::parse_srec("S4068000A942EAA4"),
+            Err(LoadError::Malformed { line: 1 })
+        );
+    }
+
+    #[test]
+    fn test_parse_records_at_the_end_of_the_address_space() {
+        /* the segment at $FFFFFFFF ends beyond 32 bits, the next record starts a new one */
+        let image =
+            loader::parse_intel_hex(":02000004FFFFFC\n:01FFFF00AA57\n:01000000BB44\n").unwrap();
+        assert_eq!(image.segments.len(), 2);
+        let mut mem: Memory = Memory::new();
+        assert_eq!(
+            loader::load(&mut mem, &image),
+            Err(LoadError::OutOfRange {
+                addr: 0xFFFF0000,
+                len: 1
+            })
+        );
+        let image = loader::parse_srec("S306FFFFFFFFAA53\nS30600000000BB3E\n").unwrap();
+        assert_eq!(
+            image.segments,
+            vec![
+                Segment {
+                    addr: 0xFFFFFFFF,
+                    data: vec![0xAA]
+                },
+                Segment {
+                    addr: 0x00000000,
+                    data: vec![0xBB]
+                },
+            ]
+        );
+        assert_eq!(
+            loader::load(&mut mem, &image),
+            Err(LoadError::OutOfRange {
+                addr: 0xFFFFFFFF,
+                len: 1
+            })
+        );
+        assert_eq!(*mem.read_byte(&(0x0000)), 0x00);
+    }
+
+    #[test]
+    fn test_parse_prg() {
+        let image = loader::parse_prg(&[0x01, 0x08, 0x0B, 0x08]).unwrap();
+        assert_eq!(
+            image.segments,
+            vec![Segment {
+                addr: 0x0801,
+                data: vec![0x0B, 0x08]
+            }]
+        );
+        assert_eq!(loader::parse_prg(&[0x01]), Err(LoadError::Truncated));
+    }
+
+    #[test]
+    fn test_parse_o65() {
+        #[rustfmt::skip]
+        let file = [
+            /* magic, mode */
+            0x01, 0x00, b'o', b'6', b'5', 0x00, 0x00, 0x00,
+            /* text $1000+5, data $2000+2, bss $3000+0, zero page $0000+0, stack */
+            0x00, 0x10, 0x05, 0x00, 0x00, 0x20, 0x02, 0x00, 0x00, 0x30, 0x00, 0x00,
+            0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+            /* one option, then the end of the options */
+            0x03, 0x00, 0x00, 0x00,
+            /* text: LDA $2000, LDA #>$1000 */
+            0xAD, 0x00, 0x20, 0xA9, 0x10,
+            /* data */
+            0x34, 0x12,
+            /* no undefined references */
+            0x00, 0x00,
+            /* text relocation: WORD to data at 1, HIGH to text at 4 with low byte $00 */
+            0x02, 0x83, 0x03, 0x42, 0x00, 0x00,
+            /* data relocation */
+            0x00,
+            /* no exported globals */
+            0x00, 0x00,
+        ];
+        let image = loader::parse_o65(&file, 0x8000).unwrap();
+        assert_eq!(
+            image.segments,
+            vec![
+                Segment {
+                    addr: 0x8000,
+                    data: vec![0xAD, 0x05, 0x80, 0xA9, 0x80]
+                },
+                Segment {
+                    addr: 0x8005,
+                    data: vec![0x34, 0x12]
+                },
+            ]
+        );
+        assert_eq!(
+            loader::parse_o65(&file[..20], 0x8000),
+            Err(LoadError::Truncated)
+        );
+        assert_eq!(
+            loader::parse_o65(&file[1..], 0x8000),
+            Err(LoadError::BadHeader)
+        );
+    }
+
+    #[test]
+    fn test_assemble() {
+        let source = "
+            count = 3
+            result = $0200
+            start:  ldx #count      ; loop counter
+                    lda #0
+            loop:   clc
+                    adc table-1,x
+                    dex
+                    bne loop
+                    sta result
+                    lda #<table
+                    ldy #>table
+                    stx $10
+                    asl a
+                    jmp (vector)
+            vector: .word start
+            table:  .byte 1, 2, %100, \"AB\"
+        ";
+        let mut mem: Memory = Memory::new();
+        let program = assembler::assemble_into(&mut mem, source, Variant::NMOS6502).unwrap();
+        assert_eq!(program.symbols["start"], 0x8000);
+        assert_eq!(program.symbols["count"], 3);
+        assert_eq!(program.symbols["table"], 0x801A);
+        assert_eq!(
+            program.image.segments[0].data[..8],
+            [
+                LDX_I.byte(),
+                0x03,
+                LDA_I.byte(),
+                0x00,
+                CLC.byte(),
+                ADC_AX.byte(),
+                0x19,
+                0x80
+            ]
+        );
+        assert_eq!(*mem.read_byte(&(0x8008)), DEX.byte());
+        assert_eq!(*mem.read_byte(&(0x800A)), 0xF9);
+        assert_eq!(*mem.read_byte(&(0x8012)), STX_ZP.byte());
+        assert_eq!(*mem.read_byte(&(0x8014)), ASL_ACC.byte());
+        assert_eq!(*mem.read_byte(&(0x801E)), b'B');
+
+        let mut cpu: CPU = CPU::new(0, 0, 0, 0);
+        for _ in 0..20 {
+            cpu.execute(&mut mem).unwrap();
+        }
+        assert_eq!(*mem.read_byte(&(0x0200)), 7);
+        assert_eq!(cpu.a().value, 0x34);
+        assert_eq!(cpu.y().value, 0x80);
+        assert_eq!(cpu.program_counter().value, 0x8000);
+    }
+
+    #[test]
+    fn test_assemble_addressing_modes() {
+        let program = assembler::assemble(
+            "lda ($10,x)\n lda ($10),y\n jmp ($1234)\n lda later,y\n ldx $10,y\n lda (2+3)*2\nlater:",
+            Variant::NMOS6502,
+        )
+        .unwrap();
+        assert_eq!(
+            program.image.segments[0].data,
+            vec![
+                LDA_IX.byte(),
+                0x10,
+                LDA_IY.byte(),
+                0x10,
+                JMP_I.byte(),
+                0x34,
+                0x12,
+                LDA_AY.byte(),
+                0x0E,
+                0x80,
+                LDX_ZPY.byte(),
+                0x10,
+                LDA_ZP.byte(),
+                0x0A
+            ]
+        );
+        let program = assembler::assemble(
+            "lda ($10)\n jmp ($1234,x)\n inc a\n bbr3 $12,* \n stz $10",
+            Variant::ROCKWELL65C02,
+        )
+        .unwrap();
+        assert_eq!(
+            program.image.segments[0].data,
+            vec![
+                LDA_IZP.byte(),
+                0x10,
+                JMP_IAX.byte(),
+                0x34,
+                0x12,
+                INC_ACC.byte(),
+                BBR3.byte(),
+                0x12,
+                0xFD,
+                STZ_ZP.byte(),
+                0x10
+            ]
+        );
+    }
+
+    #[test]
+    fn test_assemble_errors() {
+        let error = |source: &str| assembler::assemble(source, Variant::NMOS6502).unwrap_err();
+        assert_eq!(
+            error("  nop\n  lda #1\n  foo $10"),
+            AsmError {
+                line: 3,
+                column: 3,
+                kind: AsmErrorKind::UnknownMnemonic(String::from("FOO"))
+            }
+        );
+        assert_eq!(
+            error("lda missing + 1"),
+            AsmError {
+                line: 1,
+                column: 5,
+                kind: AsmErrorKind::UndefinedSymbol(String::from("missing"))
+            }
+        );
+        assert_eq!(
+            error("stz $10").kind,
+            AsmErrorKind::UnknownMnemonic(String::from("STZ"))
+        );
+        assert_eq!(
+            error("start: nop\n .org $8100\n beq start"),
+            AsmError {
+                line: 3,
+                column: 6,
+                kind: AsmErrorKind::BranchOutOfRange(-0x102)
+            }
+        );
+        assert_eq!(
+            error("  sta #$10"),
+            AsmError {
+                line: 1,
+                column: 7,
+                kind: AsmErrorKind::AddressingMode
+            }
+        );
+        assert_eq!(
+            error("a: nop\na: nop").kind,
+            AsmErrorKind::DuplicateSymbol(String::from("a"))
+        );
+        assert_eq!(
+            error("nop\n *= $8000\n brk"),
+            AsmError {
+                line: 3,
+                column: 2,
+                kind: AsmErrorKind::Overlap(0x8000)
+            }
+        );
+        assert_eq!(error("lda #256").kind, AsmErrorKind::ValueOutOfRange(256));
+        assert_eq!(
+            error(" .byte 1 2").kind,
+            AsmErrorKind::Syntax("unexpected character")
+        );
+        assert_eq!(
+            error(".bogus"),
+            AsmError {
+                line: 1,
+                column: 1,
+                kind: AsmErrorKind::UnknownDirective(String::from("bogus"))
+            }
+        );
+        assert_eq!(error("lda #1/0").kind, AsmErrorKind::DivisionByZero);
+    }
+
+    #[test]
+    fn test_assembled_program_load_error() {
+        let mut mem: Memory = Memory::new();
+        let mut program =
+            assembler::assemble("  nop\n  .org $FFFE\n  .word 0", Variant::NMOS6502).unwrap();
+        /* grow the second segment past the end of the address space */
+        program.image.segments[1].data.push(0x00);
+        assert_eq!(
+            program.load(&mut mem),
+            Err(AsmError {
+                line: 3,
+                column: 9,
+                kind: AsmErrorKind::Load(LoadError::OutOfRange {
+                    addr: 0xFFFE,
+                    len: 3
+                })
+            })
+        );
+        assert_eq!(*mem.read_byte(&(0x8000)), 0x00);
+    }
+
+    #[test]
+    fn test_disassemble() {
+        let mut mem: Memory = Memory::new();
+        mem.push_back_ins(Instruction::new(LDA_AX, &vec![0x34, 0x12]).unwrap());
+        mem.push_back_ins(Instruction::new(STA_IY, &vec![0x10]).unwrap());
+        mem.push_back_ins(Instruction::new(ASL_ACC, &vec![]).unwrap());
+        mem.push_back_ins(Instruction::new(LDX_ZPY, &vec![0x20]).unwrap());
+        mem.push_back_ins(Instruction::new(JMP_I, &vec![0x00, 0x90]).unwrap());
+        mem.push_back_ins(Instruction::new(BNE, &vec![0xF6]).unwrap());
+        mem.push_back_ins(Instruction::new(NOP_1A, &vec![]).unwrap());
+        mem.push_back_ins(Instruction::new(LDA_I, &vec![0x01]).unwrap());
+        let lines = Disassembler::new(Variant::NMOS6502).disassemble(&mem, 0x8000, 0x800E);
+        let text: Vec<&str> = lines.iter().map(|line| line.text.as_str()).collect();
+        assert_eq!(
+            text,
+            vec![
+                "LDA $1234,X",
+                "STA ($10),Y",
+                "ASL A",
+                "LDX $20,Y",
+                "JMP ($9000)",
+                "BNE $8003",
+                ".byte $1A",
+                /* the operand of LDA # is outside of the range */
+                ".byte $A9",
+            ]
+        );
+        assert_eq!(lines[0].to_string(), "8000  BD 34 12  LDA $1234,X");
+        let lines = Disassembler::new(Variant::NMOS6502)
+            .undocumented(true)
+            .disassemble(&mem, 0x800D, 0x800D);
+        assert_eq!(lines[0].text, "NOP");
+        let lines = Disassembler::new(Variant::CMOS65C02).disassemble(&mem, 0x800D, 0x800D);
+        assert_eq!(lines[0].text, "INC A");
+    }
+
+    #[test]
+    fn test_disassemble_symbols() {
+        let source = "
+            ptr = $10
+            start:  lda (ptr),y
+                    bbr3 ptr,start
+                    jmp (table,x)
+            table:  .word start
+        ";
+        let mut mem: Memory = Memory::new();
+        let program = assembler::assemble_into(&mut mem, source, Variant::ROCKWELL65C02).unwrap();
+        let lines = Disassembler::new(Variant::ROCKWELL65C02)
+            .symbols(&program.symbols)
+            .disassemble(&mem, 0x8000, 0x8007);
+        assert_eq!(
+            disassembler::listing(&lines),
+            "start:\n\
+             8000  B1 10     LDA (ptr),Y\n\
+             8002  3F 10 FB  BBR3 ptr,start\n\
+             8005  7C 08 80  JMP (table,X)\n"
+        );
+    }
+
+    #[test]
+    fn test_disassemble_round_trip() {
+        let source = "
+                    ldx #$05
+            loop:   lda $0300,x
+                    sta $20,x
+                    inc $0400
+                    ror a
+                    cmp ($40,x)
+                    dex
+                    bpl loop
+                    jsr $9000
+                    rts
+        ";
+        let mut mem: Memory = Memory::new();
+        let program = assembler::assemble_into(&mut mem, source, Variant::NMOS6502).unwrap();
+        let len = program.image.segments[0].data.len() as u16;
+        let lines =
+            Disassembler::new(Variant::NMOS6502).disassemble(&mem, 0x8000, 0x8000 + len - 1);
+        let text: Vec<String> = lines.iter().map(|line| line.text.clone()).collect();
+        let again = assembler::assemble(&text.join("\n"), Variant::NMOS6502).unwrap();
+        assert_eq!(again.image, program.image);
+    }
+
+    fn run_program(source: &str, variant: Variant) -> (CPU, Memory) {
+        let mut cpu: CPU = CPU::new(0, 0, 0, 0);
+        let mut mem: Memory = Memory::new();
+        cpu.set_variant(variant);
+        assembler::assemble_into(&mut mem, source, variant).unwrap();
+        (cpu, mem)
+    }
+
+    #[test]
+    fn test_run_to_brk() {
+        let (mut cpu, mut mem) = run_program(
+            "
+                    ldx #10
+            loop:   dex
+                    bne loop
+                    brk
+            ",
+            Variant::NMOS6502,
+        );
+        let summary = cpu.run(&mut mem, &RunLimits::new().brk());
+        assert_eq!(summary.reason, StopReason::Brk { addr: 0x8005 });
+        assert_eq!(summary.instructions, 21);
+        /* LDX 2, 10 DEX 2, 9 taken BNE 3 and the last one 2 */
+        assert_eq!(summary.cycles, 2 + 20 + 27 + 2);
+        assert_eq!(cpu.program_counter().value, 0x8005);
+        assert_eq!(cpu.x().value, 0);
+    }
+
+    #[test]
+    fn test_run_to_brk_after_interrupt() {
+        let (mut cpu, mut mem) = run_program(
+            "
+                    brk
+                    .org $9000
+            nmi:    inx
+                    rti
+                    .org $FFFA
+                    .word nmi
+            ",
+            Variant::NMOS6502,
+        );
+        /* the pending NMI is serviced before the BRK it interrupts */
+        cpu.assert_nmi();
+        let summary = cpu.run(&mut mem, &RunLimits::new().brk());
+        assert_eq!(summary.reason, StopReason::Brk { addr: 0x8000 });
+        assert_eq!(summary.instructions, 2);
+        assert_eq!(cpu.x().value, 1);
+    }
+
+    #[test]
+    fn test_run_limits() {
+        let source = "loop: inx\n jmp loop";
+        let (mut cpu, mut mem) = run_program(source, Variant::NMOS6502);
+        let summary = cpu.run(&mut mem, &RunLimits::new().instructions(5));
+        assert_eq!(summary.reason, StopReason::InstructionCount);
+        assert_eq!(summary.instructions, 5);
+        assert_eq!(cpu.x().value, 3);
+
+        let (mut cpu, mut mem) = run_program(source, Variant::NMOS6502);
+        let summary = cpu.run(&mut mem, &RunLimits::new().cycles(10));
+        assert_eq!(summary.reason, StopReason::CycleBudget);
+        /* INX 2, JMP 3, INX 2, JMP 3 */
+        assert_eq!(summary.cycles, 10);
+        assert_eq!(summary.instructions, 4);
+
+        let (mut cpu, mut mem) = run_program(source, Variant::NMOS6502);
+        let summary = cpu.run(&mut mem, &RunLimits::new().pc(0x8001).instructions(100));
+        assert_eq!(summary.reason, StopReason::PcReached(0x8001));
+        assert_eq!(summary.instructions, 1);
+
+        let (mut cpu, mut mem) = run_program(source, Variant::NMOS6502);
+        let summary = cpu.run_until(&mut mem, &RunLimits::new(), |cpu| cpu.x().value == 0x80);
+        assert_eq!(summary.reason, StopReason::Predicate);
+        assert_eq!(summary.instructions, 0x80 * 2 - 1);
+        assert_eq!(cpu.n_flag(), true);
+    }
+
+    #[test]
+    fn test_run_halts() {
+        let (mut cpu, mut mem) = run_program(" nop\n lax $10", Variant::NMOS6502);
+        cpu.set_trap_undocumented(true);
+        let summary = cpu.run(&mut mem, &RunLimits::new());
+        assert_eq!(
+            summary.reason,
+            StopReason::Halted(CpuError::IllegalOpcode {
+                opcode: LAX_ZP.byte(),
+                addr: 0x8001
+            })
+        );
+        assert_eq!(summary.instructions, 1);
+
+        let (mut cpu, mut mem) = run_program(" cli\n wai", Variant::WDC65C02);
+        let summary = cpu.run(&mut mem, &RunLimits::new());
+        assert_eq!(summary.reason, StopReason::Waiting);
+
+        let (mut cpu, mut mem) = run_program(" stp", Variant::WDC65C02);
+        let summary = cpu.run(&mut mem, &RunLimits::new());
+        assert_eq!(summary.reason, StopReason::Stopped);
+        assert_eq!(summary.instructions, 1);
+    }
+
+    const WATCHED_LOOP: &str = "
+                ldx #3
+        loop:   stx $10
+                lda $20
+                dex
+                bne loop
+                brk
+    ";
+
+    #[test]
+    fn test_debugger_breakpoint() {
+        let (mut cpu, mut mem) = run_program(WATCHED_LOOP, Variant::NMOS6502);
+        let mut debugger = Debugger::new();
+        let id = debugger.add_breakpoint(0x8002);
+        let limits = RunLimits::new().brk();
+        let summary = debugger.run(&mut cpu, &mut mem, &limits);
+        assert_eq!(summary.reason, StopReason::Breakpoint { id, addr: 0x8002 });
+        assert_eq!(summary.instructions, 1);
+        /* continuing executes the instruction at the breakpoint first */
+        let summary = debugger.run(&mut cpu, &mut mem, &limits);
+        assert_eq!(summary.reason, StopReason::Breakpoint { id, addr: 0x8002 });
+        assert_eq!(summary.instructions, 4);
+        assert_eq!(cpu.x().value, 2);
+        assert_eq!(debugger.breakpoint(id).unwrap().hits, 2);
+
+        assert_eq!(debugger.remove(id), true);
+        assert_eq!(debugger.remove(id), false);
+        let summary = debugger.run(&mut cpu, &mut mem, &limits);
+        assert_eq!(summary.reason, StopReason::Brk { addr: 0x8009 });
+    }
+
+    #[test]
+    fn test_debugger_hit_count() {
+        let (mut cpu, mut mem) = run_program(WATCHED_LOOP, Variant::NMOS6502);
+        let mut debugger = Debugger::new();
+        let id = debugger.add_breakpoint(0x8006);
+        debugger.breakpoint_mut(id).unwrap().ignore = 2;
+        let summary = debugger.run(&mut cpu, &mut mem, &RunLimits::new().brk());
+        assert_eq!(summary.reason, StopReason::Breakpoint { id, addr: 0x8006 });
+        assert_eq!(cpu.x().value, 1);
+        assert_eq!(debugger.breakpoint(id).unwrap().hits, 3);
+
+        debugger.breakpoint_mut(id).unwrap().enabled = false;
+        let summary = debugger.run(&mut cpu, &mut mem, &RunLimits::new().brk());
+        assert_eq!(summary.reason, StopReason::Brk { addr: 0x8009 });
+    }
+
+    #[test]
+    fn test_debugger_conditional_breakpoint() {
+        let (mut cpu, mut mem) = run_program(WATCHED_LOOP, Variant::NMOS6502);
+        let mut debugger = Debugger::new();
+        let id = debugger.add_conditional(
+            Some(0x8006),
+            Some(Condition::Register {
+                register: CpuRegister::X,
+                compare: Compare::LT,
+                value: 2,
+            }),
+        );
+        let summary = debugger.run(&mut cpu, &mut mem, &RunLimits::new().brk());
+        assert_eq!(summary.reason, StopReason::Breakpoint { id, addr: 0x8006 });
+        assert_eq!(cpu.x().value, 1);
+        assert_eq!(debugger.breakpoint(id).unwrap().hits, 1);
+
+        /* without an address the condition is checked after every instruction */
+        let (mut cpu, mut mem) = run_program(WATCHED_LOOP, Variant::NMOS6502);
+        let mut debugger = Debugger::new();
+        let id = debugger.add_conditional(
+            None,
+            Some(Condition::All(vec![
+                Condition::Flag {
+                    flag: Flag::Z,
+                    set: true,
+                },
+                Condition::Register {
+                    register: CpuRegister::PC,
+                    compare: Compare::EQ,
+                    value: 0x8007,
+                },
+            ])),
+        );
+        let summary = debugger.run(&mut cpu, &mut mem, &RunLimits::new().brk());
+        assert_eq!(summary.reason, StopReason::Breakpoint { id, addr: 0x8007 });
+        assert_eq!(cpu.x().value, 0);
+    }
+
+    #[test]
+    fn test_debugger_watchpoints() {
+        let (mut cpu, mut mem) = run_program(WATCHED_LOOP, Variant::NMOS6502);
+        mem.write_byte(&0x20, &0x55);
+        let mut debugger = Debugger::new();
+        let write = debugger.add_watchpoint(0x10, 0x10, Access::WRITE);
+        let read = debugger.add_watchpoint(0x20, 0x2F, Access::READ);
+        let limits = RunLimits::new().brk();
+        /* the watchpoint stops after the instruction that hit it */
+        let summary = debugger.run(&mut cpu, &mut mem, &limits);
+        assert_eq!(
+            summary.reason,
+            StopReason::Watchpoint {
+                id: write,
+                addr: 0x10,
+                access: Access::WRITE,
+                value: 3,
+            }
+        );
+        assert_eq!(cpu.program_counter().value, 0x8004);
+        let summary = debugger.run(&mut cpu, &mut mem, &limits);
+        assert_eq!(
+            summary.reason,
+            StopReason::Watchpoint {
+                id: read,
+                addr: 0x20,
+                access: Access::READ,
+                value: 0x55,
+            }
+        );
+        assert_eq!(cpu.program_counter().value, 0x8006);
+
+        /* an access watchpoint reacts to both, a read watchpoint ignores writes */
+        debugger.remove(write);
+        debugger.remove(read);
+        let access = debugger.add_watchpoint(0x10, 0x10, Access::ACCESS);
+        debugger.add_watchpoint(0x10, 0x10, Access::READ);
+        let summary = debugger.run(&mut cpu, &mut mem, &limits);
+        assert_eq!(
+            summary.reason,
+            StopReason::Watchpoint {
+                id: access,
+                addr: 0x10,
+                access: Access::WRITE,
+                value: 2,
+            }
+        );
+        assert_eq!(debugger.watchpoint(access).unwrap().hits, 1);
+    }
+
+    #[test]
+    fn test_trace_ring() {
+        let (mut cpu, mut mem) = run_program(
+            "
+                    ldx #$02
+                    lda $10,x
+                    sta ($20),y
+                    jmp done
+            done:   brk
+            ",
+            Variant::NMOS6502,
+        );
+        mem.write_byte(&0x12, &0xAB);
+        mem.write_byte(&0x21, &0x02);
+        cpu.set_tracer(Some(Tracer::ring(3)));
+        cpu.run(&mut mem, &RunLimits::new().brk());
+        /* the ring keeps the newest lines, the BRK the run stopped at was not executed */
+        let tracer = cpu.set_tracer(None).unwrap();
+        assert_eq!(
+            tracer.lines(),
+            vec![
+                "8002  B5 10     LDA $10,X @ 12 = AB             A:00 X:02 Y:00 P:20 SP:FF CYC:2",
+                "8004  91 20     STA ($20),Y = 0200 @ 0200 = 00  A:AB X:02 Y:00 P:A0 SP:FF CYC:6",
+                "8006  4C 09 80  JMP $8009                       A:AB X:02 Y:00 P:A0 SP:FF CYC:12",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_trace_undocumented() {
+        let mut cpu: CPU = CPU::new(0, 0, 0, 0);
+        let mut mem: Memory = Memory::new();
+        mem.push_back_ins(Instruction::new(LAX_ZP, &vec![0x30]).unwrap());
+        mem.write_byte(&0x30, &0x7F);
+        assert_eq!(
+            trace::trace_line(&cpu, &mem),
+            "8000  A7 30    *LAX $30 = 7F                    A:00 X:00 Y:00 P:20 SP:FF CYC:0"
+        );
+        cpu.execute(&mut mem).unwrap();
+        assert_eq!(cpu.a().value, 0x7F);
+    }
+
+    #[test]
+    fn test_trace_file() {
+        let path = std::env::temp_dir().join(format!("trace_{}.log", std::process::id()));
+        let (mut cpu, mut mem) = run_program("lda #$01\n asl a\n brk", Variant::NMOS6502);
+        cpu.set_tracer(Some(Tracer::file(&path).unwrap()));
+        cpu.run(&mut mem, &RunLimits::new().brk());
+        let mut tracer = cpu.set_tracer(None).unwrap();
+        tracer.flush().unwrap();
+        assert_eq!(tracer.lines().len(), 0);
+        let log = std::fs::read_to_string(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(
+            log,
+            "8000  A9 01     LDA #$01                        A:00 X:00 Y:00 P:20 SP:FF CYC:0\n\
+             8002  0A        ASL A                           A:01 X:00 Y:00 P:20 SP:FF CYC:2\n"
+        );
+    }
+
+    const COUNTDOWN: &str = "
+                ldx #5
+        loop:   stx $10
+                inc $0200,x
+                dex
+                bne loop
+                brk
+    ";
+
+    #[test]
+    fn test_watchpoint_ignores_fetches() {
+        let (mut cpu, mut mem) = run_program(
+            "
+                    lda data
+                    brk
+            data:   .byte $5A
+            ",
+            Variant::NMOS6502,
+        );
+        let mut debugger = Debugger::new();
+        /* the code and the data are both inside the watched range */
+        let id = debugger.add_watchpoint(0x8000, 0x80FF, Access::READ);
+        let summary = debugger.run(&mut cpu, &mut mem, &RunLimits::new().brk());
+        assert_eq!(
+            summary.reason,
+            StopReason::Watchpoint {
+                id,
+                addr: 0x8004,
+                access: Access::READ,
+                value: 0x5A,
+            }
+        );
+        assert_eq!(debugger.watchpoint(id).unwrap().hits, 1);
+    }
+
+    #[test]
+    fn test_watchpoint_on_instruction_bytes() {
+        let (mut cpu, mut mem) = run_program(
+            "
+                    lda $8001
+                    brk
+            ",
+            Variant::NMOS6502,
+        );
+        let mut debugger = Debugger::new();
+        /* the operand byte is fetched first and then read as data */
+        let id = debugger.add_watchpoint(0x8001, 0x8001, Access::READ);
+        let summary = debugger.run(&mut cpu, &mut mem, &RunLimits::new().brk());
+        assert_eq!(
+            summary.reason,
+            StopReason::Watchpoint {
+                id,
+                addr: 0x8001,
+                access: Access::READ,
+                value: 0x01,
+            }
+        );
+        assert_eq!(debugger.watchpoint(id).unwrap().hits, 1);
+        assert_eq!(cpu.a().value, 0x01);
+    }
+
+    #[test]
+    fn test_watchpoint_on_the_last_step() {
+        let source = "
+                    lda #$42
+                    sta $0200
+                    brk
+            ";
+        /* the store also reaches the pc limit */
+        let (mut cpu, mut mem) = run_program(source, Variant::NMOS6502);
+        let mut debugger = Debugger::new();
+        let id = debugger.add_watchpoint(0x0200, 0x0200, Access::WRITE);
+        let watchpoint = StopReason::Watchpoint {
+            id,
+            addr: 0x0200,
+            access: Access::WRITE,
+            value: 0x42,
+        };
+        let summary = debugger.run(&mut cpu, &mut mem, &RunLimits::new().pc(0x8005));
+        assert_eq!(summary.reason, watchpoint);
+        assert_eq!(cpu.program_counter().value, 0x8005);
+
+        /* the store also halts the cpu */
+        let (mut cpu, mut mem) = run_program(source, Variant::NMOS6502);
+        mem.map(0x0200, 0x02FF, Region::ROM);
+        mem.set_rom_writes(RomWrites::REPORT);
+        let mut debugger = Debugger::new();
+        debugger.add_watchpoint(0x0200, 0x0200, Access::WRITE);
+        let summary = debugger.run(&mut cpu, &mut mem, &RunLimits::new().brk());
+        assert_eq!(summary.reason, watchpoint);
+        assert_eq!(cpu.halted(), Some(CpuError::BusFault { addr: 0x0200 }));
+    }
+
+    #[test]
+    fn test_history_step_back() {
+        let (mut cpu, mut mem) = run_program(COUNTDOWN, Variant::NMOS6502);
+        let mut history = History::new(100);
+        let summary = history.run(&mut cpu, &mut mem, &RunLimits::new().brk());
+        assert_eq!(summary.reason, StopReason::Brk { addr: 0x800A });
+        assert_eq!(summary.instructions, 21);
+        assert_eq!(history.len(), 21);
+        assert_eq!(*mem.read_byte(&0x10), 1);
+        assert_eq!(*mem.read_byte(&0x0201), 1);
+
+        /* back to the second pass through the loop, in front of stx */
+        assert_eq!(history.step_back(&mut cpu, &mut mem, 16), 16);
+        assert_eq!(cpu.program_counter().value, 0x8002);
+        assert_eq!(cpu.x().value, 4);
+        assert_eq!(*mem.read_byte(&0x10), 5);
+        assert_eq!(*mem.read_byte(&0x0205), 1);
+        assert_eq!(*mem.read_byte(&0x0204), 0);
+
+        /* running forward again gives the same result */
+        let summary = history.run(&mut cpu, &mut mem, &RunLimits::new().brk());
+        assert_eq!(summary.instructions, 16);
+        assert_eq!(*mem.read_byte(&0x10), 1);
+
+        assert_eq!(history.step_back(&mut cpu, &mut mem, 100), 21);
+        assert_eq!(cpu.program_counter().value, 0x8000);
+        assert_eq!(cpu.clock_cycles_elapsed(), 0);
+        assert_eq!(*mem.read_byte(&0x10), 0);
+        assert_eq!(history.is_empty(), true);
+    }
+
+    #[test]
+    fn test_history_rewind_to_cycle() {
+        let (mut cpu, mut mem) = run_program(COUNTDOWN, Variant::NMOS6502);
+        let mut history = History::new(100);
+        history.run(&mut cpu, &mut mem, &RunLimits::new().brk());
+        /* ldx takes 2 cycles, stx 3, inc abs,x 7 */
+        assert_eq!(history.rewind_to_cycle(&mut cpu, &mut mem, 11), true);
+        assert_eq!(cpu.clock_cycles_elapsed(), 5);
+        assert_eq!(cpu.program_counter().value, 0x8004);
+        assert_eq!(*mem.read_byte(&0x10), 5);
+        assert_eq!(*mem.read_byte(&0x0205), 0);
+    }
+
+    #[test]
+    fn test_history_leaves_journal_alone() {
+        let (mut cpu, mut mem) = run_program(COUNTDOWN, Variant::NMOS6502);
+        let mut history = History::new(100);
+        history.step(&mut cpu, &mut mem);
+        history.step(&mut cpu, &mut mem);
+        assert!(!mem.journaling());
+        assert_eq!(history.len(), 2);
+
+        /* a journal that was already recording gets the writes of the run */
+        mem.set_journaling(true);
+        mem.write_byte(&0x30, &0x01);
+        history.step(&mut cpu, &mut mem);
+        assert!(mem.journaling());
+        assert_eq!(
+            mem.take_journal(),
+            vec![
+                UndoWrite { addr: 0x30, old: 0 },
+                UndoWrite {
+                    addr: 0x0205,
+                    old: 0,
+                },
+            ]
+        );
+        mem.set_journaling(false);
+
+        /* the cpu was moved outside of the history, the records no longer fit */
+        cpu.run(&mut mem, &RunLimits::new().instructions(1));
+        assert_eq!(history.step_back(&mut cpu, &mut mem, 1), 0);
+        assert!(history.is_empty());
+        assert_eq!(cpu.program_counter().value, 0x8008);
+    }
+
+    #[test]
+    fn test_history_bounded() {
+        let (mut cpu, mut mem) = run_program(COUNTDOWN, Variant::NMOS6502);
+        let mut history = History::new(4);
+        history.run(&mut cpu, &mut mem, &RunLimits::new().brk());
+        assert_eq!(history.len(), 4);
+        assert_eq!(history.rewind_to_cycle(&mut cpu, &mut mem, 0), false);
+        assert_eq!(history.is_empty(), true);
+        /* the four newest steps were taken back: stx, inc, dex and bne of the last pass */
+        assert_eq!(cpu.program_counter().value, 0x8002);
+        assert_eq!(cpu.x().value, 1);
+
+        history.step(&mut cpu, &mut mem);
+        assert_eq!(history.len(), 1);
+        assert_eq!(cpu.program_counter().value, 0x8004);
+    }
+
+    #[test]
+    fn test_debugger_step_back() {
+        let (mut cpu, mut mem) = run_program(COUNTDOWN, Variant::NMOS6502);
+        let mut debugger = Debugger::new();
+        let mut history = History::new(100);
+        let id = debugger.add_watchpoint(0x0203, 0x0203, Access::WRITE);
+        let summary = debugger.run_recorded(&mut cpu, &mut mem, &mut history, &RunLimits::new());
+        assert_eq!(
+            summary.reason,
+            StopReason::Watchpoint {
+                id,
+                addr: 0x0203,
+                access: Access::WRITE,
+                value: 1,
+            }
+        );
+        assert_eq!(cpu.program_counter().value, 0x8007);
+
+        /* take back the inc that hit the watchpoint */
+        assert_eq!(history.step_back(&mut cpu, &mut mem, 1), 1);
+        assert_eq!(cpu.program_counter().value, 0x8004);
+        assert_eq!(cpu.x().value, 3);
+        assert_eq!(*mem.read_byte(&0x0203), 0);
+
+        debugger.remove(id);
+        let id = debugger.add_breakpoint(0x800A);
+        let summary = debugger.run_recorded(&mut cpu, &mut mem, &mut history, &RunLimits::new());
+        assert_eq!(summary.reason, StopReason::Breakpoint { id, addr: 0x800A });
+        assert_eq!(*mem.read_byte(&0x0203), 1);
+        assert_eq!(history.len(), 21);
+    }
+
+    #[test]
+    fn test_history_mapper() {
+        let (mut cpu, mut mem) = run_program(
+            "
+                    lda #2
+                    sta $0201
+                    lda #$55
+                    sta $2000
+                    lda #1
+                    sta $0201
+                    brk
+            ",
+            Variant::NMOS6502,
+        );
+        /* slot 1 shows the RAM bank, then the second ROM bank */
+        mem.set_mapper(Box::new(MmuTable::new(
+            banked_image(2, BankSize::KB8),
+            1,
+            0x0200,
+        )));
+        let mut history = History::new(100);
+        history.run(&mut cpu, &mut mem, &RunLimits::new().brk());
+        assert_eq!(mem.peek(0x2000), 0x01);
+
+        assert_eq!(history.step_back(&mut cpu, &mut mem, 2), 2);
+        assert_eq!(mem.peek(0x2000), 0x55);
+        assert_eq!(history.step_back(&mut cpu, &mut mem, 2), 2);
+        assert_eq!(mem.peek(0x2000), 0x00);
+        assert_eq!(history.step_back(&mut cpu, &mut mem, 2), 2);
+        assert_eq!(mem.mapper().unwrap().snapshot()[1], UNBANKED);
+        assert_eq!(cpu.program_counter().value, 0x8000);
+
+        /* the same run again from the start */
+        history.run(&mut cpu, &mut mem, &RunLimits::new().brk());
+        assert_eq!(mem.peek(0x2000), 0x01);
+        assert_eq!(history.len(), 6);
+    }
+
+    /* a counter at $4000 and a latch mapper that switches $A000-$BFFF with writes to $9000 */
+    fn save_state_machine() -> (CPU, Memory) {
+        let (mut cpu, mut mem) = run_program(
+            "
+                    ldx #0
+            loop:   lda $4000
+                    sta $9000
+                    sta $10
+                    inx
+                    cpx #10
+                    bne loop
+                    brk
+            ",
+            Variant::CMOS65C02,
+        );
+        mem.attach(0x4000, 0x4000, Box::new(Counter { count: 0 }));
+        mem.set_mapper(Box::new(Latch::new(
+            banked_image(4, BankSize::KB8),
+            0xA000,
+            BankSize::KB8,
+            0x9000,
+        )));
+        cpu.set_trap_undocumented(true);
+        (cpu, mem)
+    }
+
+    #[test]
+    fn test_save_state_round_trip() {
+        let (mut cpu, mut mem) = save_state_machine();
+        cpu.run(&mut mem, &RunLimits::new().instructions(15));
+        let state = savestate::save(&cpu, &mem);
+        cpu.run(&mut mem, &RunLimits::new().brk());
+        assert_eq!(mem.peek(0x10), 10);
+        assert_eq!(mem.peek(0xA000), 2);
+
+        /* a fresh machine continues from the saved state to the same end */
+        let (mut other_cpu, mut other_mem) = save_state_machine();
+        other_cpu.set_variant(Variant::NMOS6502);
+        savestate::load(&mut other_cpu, &mut other_mem, &state).unwrap();
+        assert_eq!(other_cpu.variant(), Variant::CMOS65C02);
+        assert_eq!(other_cpu.x().value, 2);
+        assert_eq!(other_mem.peek(0x4000), 3);
+        assert_eq!(other_mem.peek(0xA000), 3);
+        other_cpu.run(&mut other_mem, &RunLimits::new().brk());
+        assert_eq!(other_cpu.state(), cpu.state());
+        assert_eq!(
+            savestate::save(&other_cpu, &other_mem),
+            savestate::save(&cpu, &mem)
+        );
+    }
+
+    #[test]
+    fn test_save_state_cpu_flags() {
+        let mut cpu: CPU = CPU::new(0x12, 0x34, 0x56, 0);
+        let mut mem: Memory = Memory::new();
+        mem.push_back_ins(Instruction::new(JAM_02, &vec![]).unwrap());
+        mem.set_rom_writes(RomWrites::REPORT);
+        mem.map(0xC000, 0xCFFF, Region::UNMAPPED);
+        let jammed = CpuError::Jammed {
+            opcode: JAM_02.byte(),
+            addr: 0x8000,
+        };
+        assert_eq!(cpu.execute(&mut mem), Err(jammed));
+        cpu.set_trap_undocumented(true);
+        cpu.assert_irq();
+        cpu.assert_nmi();
+        cpu.deassert_nmi();
+        let state = savestate::save(&cpu, &mem);
+
+        let mut other_cpu: CPU = CPU::new(0, 0, 0, 0);
+        let mut other_mem: Memory = Memory::new();
+        savestate::load(&mut other_cpu, &mut other_mem, &state).unwrap();
+        assert_eq!(other_cpu.state(), cpu.state());
+        assert_eq!(other_cpu.trap_undocumented(), true);
+        assert_eq!(other_mem.region(0xC000), Region::UNMAPPED);
+        assert_eq!(savestate::save(&other_cpu, &other_mem), state);
+        /* the restored cpu is still jammed */
+        assert_eq!(other_cpu.halted(), Some(jammed));
+        assert_eq!(other_cpu.execute(&mut other_mem), Err(jammed));
+    }
+
+    #[test]
+    fn test_save_state_errors() {
+        let (cpu, mem) = save_state_machine();
+        let state = savestate::save(&cpu, &mem);
+        let (mut other_cpu, mut other_mem) = save_state_machine();
+
+        let mut bad_magic = state.clone();
+        bad_magic[0] = b'X';
+        assert_eq!(
+            savestate::load(&mut other_cpu, &mut other_mem, &bad_magic),
+            Err(SaveStateError::BadMagic)
+        );
+        let mut newer = state.clone();
+        newer[4] = 2;
+        assert_eq!(
+            savestate::load(&mut other_cpu, &mut other_mem, &newer),
+            Err(SaveStateError::UnsupportedVersion(2))
+        );
+        assert_eq!(
+            savestate::load(&mut other_cpu, &mut other_mem, &state[..state.len() - 1]),
+            Err(SaveStateError::Truncated)
+        );
+        /* the state has to be loaded into a memory with the same mapper and devices */
+        let (mut plain_cpu, mut plain_mem) = run_program("brk", Variant::CMOS65C02);
+        assert_eq!(
+            savestate::load(&mut plain_cpu, &mut plain_mem, &state),
+            Err(SaveStateError::Mismatch("mapper"))
+        );
+        plain_mem.set_mapper(Box::new(UxRom::new(vec![0; 0x4000])));
+        assert_eq!(
+            savestate::load(&mut plain_cpu, &mut plain_mem, &state),
+            Err(SaveStateError::Mismatch("devices"))
+        );
+        assert_eq!(plain_cpu.state(), CPU::new(0, 0, 0, 0).state());
+
+        let mut longer = state.clone();
+        longer.push(0);
+        assert_eq!(
+            savestate::load(&mut other_cpu, &mut other_mem, &longer),
+            Err(SaveStateError::Invalid("trailing bytes"))
+        );
+    }
+
+    #[test]
+    fn test_save_state_rejected_snapshot() {
+        /* saved with a device at $4000 that has no state, loaded where a counter is */
+        let (mut cpu, mut mem) = run_program("brk", Variant::CMOS65C02);
+        mem.attach(
+            0x4000,
+            0x4000,
+            Box::new(ReadTally {
+                data: vec![0],
+                reads: Rc::new(RefCell::new(vec![0])),
+            }),
+        );
+        mem.set_mapper(Box::new(UxRom::new(banked_image(4, BankSize::KB16))));
+        mem.write(0x8000, 0x02);
+        mem.write_byte(&0x10, &0x99);
+        cpu.run(&mut mem, &RunLimits::new().instructions(1));
+        let state = savestate::save(&cpu, &mem);
+
+        let (mut other_cpu, mut other_mem) = run_program("brk", Variant::CMOS65C02);
+        other_mem.attach(0x4000, 0x4000, Box::new(Counter { count: 0x21 }));
+        other_mem.set_mapper(Box::new(UxRom::new(banked_image(4, BankSize::KB16))));
+        assert_eq!(
+            savestate::load(&mut other_cpu, &mut other_mem, &state),
+            Err(SaveStateError::Snapshot(SnapshotError {
+                expected: 1,
+                got: 0
+            }))
+        );
+        /* neither the mapper in front of the device nor anything else was touched */
+        assert_eq!(other_mem.peek(0x8000), 0x00);
+        assert_eq!(other_mem.peek(0x4000), 0x21);
+        assert_eq!(other_mem.peek(0x10), 0x00);
+        assert_eq!(other_cpu.state(), CPU::new(0, 0, 0, 0).state());
+    }
+
+    /* run with: cargo test --release -- --ignored --nocapture bench_execute */
+    #[test]
+    #[ignore]
+    fn bench_execute() {
+        const INSTRUCTIONS: u32 = 50_000_000;
+        let mut cpu: CPU = CPU::new(0, 0, 0, 0);
+        let mut mem: Memory = Memory::new();
+        mem.push_back_ins(Instruction::new(LDA_ZP, &vec![0x10]).unwrap());
+        mem.push_back_ins(Instruction::new(ADC_I, &vec![0x01]).unwrap());
+        mem.push_back_ins(Instruction::new(STA_ZP, &vec![0x10]).unwrap());
+        mem.push_back_ins(Instruction::new(INX, &vec![]).unwrap());
+        mem.push_back_ins(Instruction::new(BNE, &vec![0xF7]).unwrap()); // -9: back to LDA
+        mem.push_back_ins(Instruction::new(JMP_A, &vec![0x00, 0x80]).unwrap());
+        let start = Instant::now();
+        for _ in 0..INSTRUCTIONS {
+            cpu.execute(&mut mem).unwrap();
+        }
+        let per_second = INSTRUCTIONS as f64 / start.elapsed().as_secs_f64();
+        println!(
+            "{:.1} million instructions per second, {:.1} MHz",
+            per_second / 1e6,
+            cpu.clock_cycles_elapsed() as f64 / INSTRUCTIONS as f64 * per_second / 1e6
+        );
+        assert!(per_second > 1e6);
+    }
 }
@@ -0,0 +1,252 @@
+use std::collections::VecDeque;
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+use std::path::Path;
+
+use crate::bus::Bus;
+use crate::cpu::{AddressingMode, Variant, CPU};
+use crate::dispatch::{self, Operation};
+use crate::OPCODE;
+
+/* where the trace lines go */
+enum Sink {
+    Writer(Box<dyn Write>),
+    /* the newest lines, the oldest one is dropped when the buffer is full */
+    Ring {
+        lines: VecDeque<String>,
+        capacity: usize,
+    },
+}
+
+/*
+    Writes one line per executed instruction, installed with CPU::set_tracer.
+    The first write error stops the trace, it is returned by flush.
+*/
+pub struct Tracer {
+    sink: Sink,
+    error: Option<io::Error>,
+}
+
+impl Tracer {
+    pub fn file(path: impl AsRef<Path>) -> io::Result<Tracer> {
+        Ok(Tracer::writer(Box::new(BufWriter::new(File::create(
+            path,
+        )?))))
+    }
+    pub fn writer(writer: Box<dyn Write>) -> Tracer {
+        Tracer {
+            sink: Sink::Writer(writer),
+            error: None,
+        }
+    }
+    pub fn ring(capacity: usize) -> Tracer {
+        Tracer {
+            sink: Sink::Ring {
+                lines: VecDeque::with_capacity(capacity),
+                capacity,
+            },
+            error: None,
+        }
+    }
+
+    /* the lines in the ring buffer, oldest first, nothing for a writer */
+    pub fn lines(&self) -> Vec<&str> {
+        match &self.sink {
+            Sink::Writer(_) => Vec::new(),
+            Sink::Ring { lines, .. } => lines.iter().map(|line| line.as_str()).collect(),
+        }
+    }
+
+    pub fn flush(&mut self) -> io::Result<()> {
+        if let Some(error) = self.error.take() {
+            return Err(error);
+        }
+        match &mut self.sink {
+            Sink::Writer(writer) => writer.flush(),
+            Sink::Ring { .. } => Ok(()),
+        }
+    }
+
+    /* called by CPU::execute in front of every instruction */
+    pub(crate) fn record<B: Bus + ?Sized>(&mut self, cpu: &CPU, bus: &B) {
+        if self.error.is_some() {
+            return;
+        }
+        match &mut self.sink {
+            Sink::Writer(writer) => {
+                if let Err(error) = writeln!(writer, "{}", trace_line(cpu, bus)) {
+                    self.error = Some(error);
+                }
+            }
+            Sink::Ring { lines, capacity } => {
+                if *capacity == 0 {
+                    return;
+                }
+                if lines.len() == *capacity {
+                    lines.pop_front();
+                }
+                lines.push_back(trace_line(cpu, bus));
+            }
+        }
+    }
+}
+
+/*
+    The instruction at the program counter in the format of Nintendulator and nestest.log:
+    C000  4C F5 C5  JMP $C5F5                       A:00 X:00 Y:00 P:24 SP:FD CYC:7
+    Undocumented opcodes get a "*" in front of the mnemonic, memory operands show the address
+    they resolve to and the byte stored there before the instruction runs.
+    There is no PPU, so the PPU column is left out.
+*/
+pub fn trace_line<B: Bus + ?Sized>(cpu: &CPU, bus: &B) -> String {
+    let pc = cpu.program_counter().value;
+    let operation = &dispatch::table(cpu.variant())[bus.peek(pc) as usize];
+    let bytes: Vec<u8> = (0..operation.size)
+        .map(|i| bus.peek(pc.wrapping_add(i)))
+        .collect();
+    let hex: Vec<String> = bytes.iter().map(|byte| format!("{:02X}", byte)).collect();
+    let operand = operand(cpu, bus, operation, pc, &bytes);
+    let text = if operand.is_empty() {
+        operation.mnemonic.to_string()
+    } else {
+        format!("{} {}", operation.mnemonic, operand)
+    };
+    format!(
+        "{:04X}  {:<8} {}{:<32}A:{:02X} X:{:02X} Y:{:02X} P:{:02X} SP:{:02X} CYC:{}",
+        pc,
+        hex.join(" "),
+        if operation.opc.undocumented() {
+            '*'
+        } else {
+            ' '
+        },
+        text,
+        cpu.a().value,
+        cpu.x().value,
+        cpu.y().value,
+        cpu.p().to_byte(false),
+        cpu.s().value,
+        cpu.clock_cycles_elapsed()
+    )
+}
+
+fn operand<B: Bus + ?Sized>(
+    cpu: &CPU,
+    bus: &B,
+    operation: &Operation,
+    pc: u16,
+    bytes: &[u8],
+) -> String {
+    let x = cpu.x().value;
+    let y = cpu.y().value;
+    let zero_page = || bytes[1];
+    let absolute = || bytes[1] as u16 | ((bytes[2] as u16) << 8);
+    /* a pointer in the zero page, the high byte wraps around inside the page */
+    let pointer = |addr: u8| {
+        bus.peek(addr as u16) as u16 | ((bus.peek(addr.wrapping_add(1) as u16) as u16) << 8)
+    };
+    let target = |offset: u8| {
+        pc.wrapping_add(operation.size)
+            .wrapping_add(offset as i8 as u16)
+    };
+    match operation.mode {
+        AddressingMode::IMPLIED => String::new(),
+        AddressingMode::ACCUMULATOR => String::from("A"),
+        AddressingMode::IMMEDIATE => format!("#${:02X}", bytes[1]),
+        AddressingMode::RELATIVE => format!("${:04X}", target(bytes[1])),
+        /* BBR and BBS */
+        AddressingMode::ZEROPAGE if operation.size == 3 => {
+            format!("${:02X},${:04X}", zero_page(), target(bytes[2]))
+        }
+        AddressingMode::ZEROPAGE => {
+            let addr = zero_page();
+            format!("${:02X} = {:02X}", addr, bus.peek(addr as u16))
+        }
+        AddressingMode::ZEROPAGEX | AddressingMode::ZEROPAGEY => {
+            let (name, index) = if operation.mode == AddressingMode::ZEROPAGEX {
+                ('X', x)
+            } else {
+                ('Y', y)
+            };
+            let addr = zero_page().wrapping_add(index);
+            format!(
+                "${:02X},{} @ {:02X} = {:02X}",
+                zero_page(),
+                name,
+                addr,
+                bus.peek(addr as u16)
+            )
+        }
+        /* jumps show their target, not the byte stored there */
+        AddressingMode::ABSOLUTE
+            if operation.opc == OPCODE::JMP_A || operation.opc == OPCODE::JSR =>
+        {
+            format!("${:04X}", absolute())
+        }
+        AddressingMode::ABSOLUTE => {
+            format!("${:04X} = {:02X}", absolute(), bus.peek(absolute()))
+        }
+        AddressingMode::ABSOLUTEX | AddressingMode::ABSOLUTEY => {
+            let (name, index) = if operation.mode == AddressingMode::ABSOLUTEX {
+                ('X', x)
+            } else {
+                ('Y', y)
+            };
+            let addr = absolute().wrapping_add(index as u16);
+            format!(
+                "${:04X},{} @ {:04X} = {:02X}",
+                absolute(),
+                name,
+                addr,
+                bus.peek(addr)
+            )
+        }
+        /* JMP ($xxFF) of the NMOS chip takes the high byte from the start of the same page */
+        AddressingMode::INDIRECT => {
+            let low = absolute();
+            let high = if cpu.variant() == Variant::NMOS6502 {
+                (low & 0xFF00) | (low.wrapping_add(1) & 0x00FF)
+            } else {
+                low.wrapping_add(1)
+            };
+            let addr = bus.peek(low) as u16 | ((bus.peek(high) as u16) << 8);
+            format!("(${:04X}) = {:04X}", low, addr)
+        }
+        AddressingMode::INDIRECTX => {
+            let ptr = zero_page().wrapping_add(x);
+            let addr = pointer(ptr);
+            format!(
+                "(${:02X},X) @ {:02X} = {:04X} = {:02X}",
+                zero_page(),
+                ptr,
+                addr,
+                bus.peek(addr)
+            )
+        }
+        AddressingMode::INDIRECTY => {
+            let base = pointer(zero_page());
+            let addr = base.wrapping_add(y as u16);
+            format!(
+                "(${:02X}),Y = {:04X} @ {:04X} = {:02X}",
+                zero_page(),
+                base,
+                addr,
+                bus.peek(addr)
+            )
+        }
+        AddressingMode::INDIRECTZP => {
+            let addr = pointer(zero_page());
+            format!(
+                "(${:02X}) = {:04X} = {:02X}",
+                zero_page(),
+                addr,
+                bus.peek(addr)
+            )
+        }
+        AddressingMode::INDIRECTABSX => {
+            let ptr = absolute().wrapping_add(x as u16);
+            let addr = bus.peek(ptr) as u16 | ((bus.peek(ptr.wrapping_add(1)) as u16) << 8);
+            format!("(${:04X},X) = {:04X}", absolute(), addr)
+        }
+    }
+}